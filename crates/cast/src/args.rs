@@ -505,6 +505,7 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
 
         // Calls & transactions
         CastSubcommand::Call(cmd) => cmd.run().await?,
+        CastSubcommand::Multicall(cmd) => cmd.run().await?,
         CastSubcommand::Estimate(cmd) => cmd.run().await?,
         CastSubcommand::MakeTx(cmd) => cmd.run().await?,
         CastSubcommand::PublishTx { raw_tx, cast_async, rpc } => {
@@ -532,6 +533,7 @@ pub async fn run_command(args: CastArgs) -> Result<()> {
             )?
         }
         CastSubcommand::Run(cmd) => cmd.run().await?,
+        CastSubcommand::RunBlock(cmd) => cmd.run().await?,
         CastSubcommand::SendTx(cmd) => cmd.run().await?,
         CastSubcommand::Tx { tx_hash, from, nonce, field, raw, rpc, to_request } => {
             let config = rpc.load_config()?;
@@ -20,8 +20,10 @@
 pub mod interface;
 pub mod logs;
 pub mod mktx;
+pub mod multicall;
 pub mod rpc;
 pub mod run;
+pub mod run_block;
 pub mod send;
 pub mod storage;
 pub mod txpool;
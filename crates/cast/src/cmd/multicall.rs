@@ -0,0 +1,315 @@
+use alloy_dyn_abi::{DynSolValue, FunctionExt};
+use alloy_ens::NameOrAddress;
+use alloy_json_abi::Function;
+use alloy_primitives::Address;
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockId, TransactionRequest};
+use alloy_serde::WithOtherFields;
+use alloy_sol_types::SolCall;
+use clap::{Parser, ValueHint};
+use eyre::{OptionExt, Result, WrapErr};
+use foundry_cli::{
+    opts::RpcOpts,
+    utils::{self, LoadConfig},
+};
+use foundry_common::{
+    abi::{encode_function_args, get_func},
+    fmt::{format_token, serialize_value_as_json},
+    fs, shell,
+};
+use foundry_evm::decode::RevertDecoder;
+use serde::Serialize;
+use std::path::PathBuf;
+use tempo_contracts::{MULTICALL_ADDRESS, Multicall};
+
+/// CLI arguments for `cast multicall`.
+///
+/// Calls are given as `<TO> <SIG> [ARGS]...` groups, where the number of arguments of each call is
+/// the number of inputs of its signature:
+///
+/// ```bash
+/// cast multicall \
+///   0x... "decimals()(uint8)" \
+///   0x... "balanceOf(address)(uint256)" 0x...
+/// ```
+#[derive(Debug, Parser)]
+pub struct MulticallArgs {
+    /// The calls to aggregate, as `<TO> <SIG> [ARGS]...` groups.
+    #[arg(value_name = "CALLS", allow_negative_numbers = true)]
+    calls: Vec<String>,
+
+    /// Path to a file containing the calls to aggregate, one `<TO> <SIG> [ARGS]...` call per line.
+    ///
+    /// Arguments containing whitespace can be double-quoted. Empty lines and lines starting with
+    /// `#` are ignored.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    file: Option<PathBuf>,
+
+    /// Allow calls to fail without reverting the whole multicall.
+    ///
+    /// The revert reason of failed calls is printed instead of their result.
+    #[arg(long)]
+    allow_failure: bool,
+
+    /// The address of the Multicall3 contract.
+    #[arg(long, value_name = "ADDRESS", default_value_t = MULTICALL_ADDRESS)]
+    multicall_address: Address,
+
+    /// The block height to query at.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending.
+    #[arg(long, short)]
+    block: Option<BlockId>,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+/// A call to aggregate.
+#[derive(Debug)]
+struct Call {
+    to: NameOrAddress,
+    func: Function,
+    sig: String,
+    args: Vec<String>,
+}
+
+/// The JSON output of a call.
+#[derive(Serialize)]
+struct JsonResult {
+    target: Address,
+    signature: String,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl MulticallArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { calls, file, allow_failure, multicall_address, block, rpc } = self;
+
+        let mut calls = parse_calls(calls)?;
+        if let Some(file) = file {
+            let contents = fs::read_to_string(&file)?;
+            calls.extend(
+                parse_file_calls(&contents)
+                    .wrap_err_with(|| format!("failed to parse {}", file.display()))?,
+            );
+        }
+        if calls.is_empty() {
+            eyre::bail!("no calls to aggregate; pass them as arguments or with `--file`");
+        }
+
+        let config = rpc.load_config()?;
+        let provider = utils::get_provider(&config)?;
+
+        let mut targets = Vec::with_capacity(calls.len());
+        let mut call3s = Vec::with_capacity(calls.len());
+        for call in &calls {
+            let target = call.to.resolve(&provider).await?;
+            let calldata = encode_function_args(&call.func, &call.args)?;
+            targets.push(target);
+            call3s.push(Multicall::Call3 {
+                target,
+                allowFailure: allow_failure,
+                callData: calldata.into(),
+            });
+        }
+
+        let tx = TransactionRequest::default()
+            .to(multicall_address)
+            .input(Multicall::aggregate3Call { calls: call3s }.abi_encode().into());
+        let output =
+            provider.call(WithOtherFields::new(tx)).block(block.unwrap_or_default()).await?;
+        let results = Multicall::aggregate3Call::abi_decode_returns(&output)?;
+
+        let decoder = RevertDecoder::new();
+        if shell::is_json() {
+            let mut values = Vec::with_capacity(calls.len());
+            for ((call, target), result) in calls.iter().zip(targets).zip(results) {
+                let mut value = JsonResult {
+                    target,
+                    signature: call.sig.clone(),
+                    success: result.success,
+                    result: None,
+                    error: None,
+                };
+                match decode_result(&call.func, &result, &decoder) {
+                    Ok(Some(tokens)) => {
+                        value.result = Some(
+                            tokens
+                                .into_iter()
+                                .map(|token| serialize_value_as_json(token, None))
+                                .collect::<Result<Vec<_>>>()?
+                                .into(),
+                        );
+                    }
+                    Ok(None) => value.result = Some(result.returnData.to_string().into()),
+                    Err(err) => value.error = Some(err),
+                }
+                values.push(value);
+            }
+            sh_println!("{}", serde_json::to_string_pretty(&values)?)?;
+        } else {
+            for (call, result) in calls.iter().zip(results) {
+                let line = match decode_result(&call.func, &result, &decoder) {
+                    Ok(Some(mut tokens)) if tokens.len() == 1 => format_token(&tokens.remove(0)),
+                    Ok(Some(tokens)) => format_token(&DynSolValue::Tuple(tokens)),
+                    Ok(None) => result.returnData.to_string(),
+                    Err(err) => format!("Error: {err}"),
+                };
+                sh_println!("{line}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes the result of a call with the outputs of its signature.
+///
+/// Returns `None` if the signature has no outputs, and the revert reason if the call failed.
+fn decode_result(
+    func: &Function,
+    result: &Multicall::Result,
+    decoder: &RevertDecoder,
+) -> Result<Option<Vec<DynSolValue>>, String> {
+    if !result.success {
+        return Err(decoder.decode(&result.returnData, None));
+    }
+    if func.outputs.is_empty() {
+        return Ok(None);
+    }
+    func.abi_decode_output(&result.returnData)
+        .map(Some)
+        .map_err(|err| format!("could not decode output: {err}"))
+}
+
+/// Parses `<TO> <SIG> [ARGS]...` groups of words into calls.
+fn parse_calls(words: impl IntoIterator<Item = String>) -> Result<Vec<Call>> {
+    let mut words = words.into_iter();
+    let mut calls = Vec::new();
+    while let Some(to) = words.next() {
+        let to = to.parse::<NameOrAddress>()?;
+        let sig =
+            words.next().ok_or_eyre(format!("missing function signature for call to {to}"))?;
+        let func = get_func(&sig)?;
+        let args = words.by_ref().take(func.inputs.len()).collect::<Vec<_>>();
+        if args.len() != func.inputs.len() {
+            eyre::bail!(
+                "`{sig}` expects {} arguments, but {} were given",
+                func.inputs.len(),
+                args.len()
+            );
+        }
+        calls.push(Call { to, func, sig, args });
+    }
+    Ok(calls)
+}
+
+/// Parses the calls of a file, one `<TO> <SIG> [ARGS]...` call per line.
+///
+/// Empty lines and lines starting with `#` are skipped.
+fn parse_file_calls(contents: &str) -> Result<Vec<Call>> {
+    let mut calls = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_calls = parse_calls(split_words(line))
+            .wrap_err_with(|| format!("invalid call at line {}", i + 1))?;
+        if line_calls.len() != 1 {
+            eyre::bail!("expected one call at line {}, found {}", i + 1, line_calls.len());
+        }
+        calls.extend(line_calls);
+    }
+    Ok(calls)
+}
+
+/// Splits a line into whitespace-separated words, keeping double-quoted words together.
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut in_word = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_calls() {
+        let words = split_words(
+            r#"0x6b175474e89094c44da98b954eedeac495271d0f "transfer(address to, uint256 amount)(bool)" 0x0000000000000000000000000000000000000001 1"#,
+        );
+        assert_eq!(words.len(), 4);
+        assert_eq!(words[1], "transfer(address to, uint256 amount)(bool)");
+
+        let calls = parse_calls(words.into_iter().chain(
+            ["0x6b175474e89094c44da98b954eedeac495271d0f", "decimals()(uint8)"].map(String::from),
+        ))
+        .unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].args.len(), 2);
+        assert_eq!(calls[1].sig, "decimals()(uint8)");
+
+        let err = parse_calls(
+            ["0x6b175474e89094c44da98b954eedeac495271d0f", "balanceOf(address)"].map(String::from),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "`balanceOf(address)` expects 1 arguments, but 0 were given");
+    }
+
+    #[test]
+    fn parses_file_calls() {
+        let calls = parse_file_calls(
+            r#"
+# DAI
+0x6b175474e89094c44da98b954eedeac495271d0f "balanceOf(address)(uint256)" 0x0000000000000000000000000000000000000001
+0x6b175474e89094c44da98b954eedeac495271d0f decimals()(uint8)
+"#,
+        )
+        .unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].args, ["0x0000000000000000000000000000000000000001"]);
+
+        // Arguments don't spill over to the next line.
+        let err = parse_file_calls(
+            "0x6b175474e89094c44da98b954eedeac495271d0f balanceOf(address)\n0x0000000000000000000000000000000000000001",
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "invalid call at line 1");
+
+        let err = parse_file_calls(
+            "0x6b175474e89094c44da98b954eedeac495271d0f decimals() 0x6b175474e89094c44da98b954eedeac495271d0f decimals()",
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "expected one call at line 1, found 2");
+    }
+}
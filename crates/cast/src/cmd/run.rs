@@ -3,7 +3,7 @@
     utils::{apply_chain_and_block_specific_env_changes, configure_tx_req_env},
 };
 use alloy_consensus::{BlockHeader, Transaction};
-use alloy_network::TransactionResponse;
+use alloy_network::{Network, TransactionResponse};
 use alloy_primitives::{
     Address, Bytes, U256,
     map::{AddressSet, HashMap},
@@ -32,6 +32,7 @@
     opts::EvmOpts,
     traces::{InternalTraceMode, TraceMode, Traces},
 };
+use foundry_evm_networks::NetworkConfigs;
 use futures::TryFutureExt;
 use revm::DatabaseRef;
 use tempo_alloy::TempoNetwork;
@@ -51,10 +52,6 @@ pub struct RunArgs {
     #[arg(long)]
     decode_internal: bool,
 
-    /// Defines the depth of a trace
-    #[arg(long)]
-    trace_depth: Option<usize>,
-
     /// Print out opcode traces.
     #[arg(long, short)]
     trace_printer: bool,
@@ -65,27 +62,42 @@ pub struct RunArgs {
     #[arg(long)]
     quick: bool,
 
+    /// Use current project artifacts for trace decoding.
+    #[arg(long, visible_alias = "la")]
+    pub with_local_artifacts: bool,
+
+    #[command(flatten)]
+    pub replay: ReplayOpts,
+}
+
+/// Options shared by the commands replaying transactions of a block on a fork.
+#[derive(Clone, Debug, Parser)]
+pub struct ReplayOpts {
+    /// Defines the depth of a trace
+    #[arg(long)]
+    pub trace_depth: Option<usize>,
+
     /// Disables the labels in the traces.
     #[arg(long, default_value_t = false)]
-    disable_labels: bool,
+    pub disable_labels: bool,
 
     /// Label addresses in the trace.
     ///
     /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:vitalik.eth
     #[arg(long, short)]
-    label: Vec<String>,
+    pub label: Vec<String>,
 
     #[command(flatten)]
-    etherscan: EtherscanOpts,
+    pub etherscan: EtherscanOpts,
 
     #[command(flatten)]
-    rpc: RpcOpts,
+    pub rpc: RpcOpts,
 
     /// The EVM version to use.
     ///
     /// Overrides the version specified in the config.
     #[arg(long)]
-    evm_version: Option<EvmVersion>,
+    pub evm_version: Option<EvmVersion>,
 
     /// Sets the number of assumed available compute units per second for this provider
     ///
@@ -103,10 +115,6 @@ pub struct RunArgs {
     #[arg(long, value_name = "NO_RATE_LIMITS", visible_alias = "no-rpc-rate-limit")]
     pub no_rate_limit: bool,
 
-    /// Use current project artifacts for trace decoding.
-    #[arg(long, visible_alias = "la")]
-    pub with_local_artifacts: bool,
-
     /// Disable block gas limit check.
     #[arg(long)]
     pub disable_block_gas_limit: bool,
@@ -116,6 +124,27 @@ pub struct RunArgs {
     pub enable_tx_gas_limit: bool,
 }
 
+impl ReplayOpts {
+    /// Returns the compute units per second of the provider, unlimited if rate limiting is
+    /// disabled.
+    pub fn compute_units_per_second(&self) -> Option<u64> {
+        if self.no_rate_limit { Some(u64::MAX) } else { self.compute_units_per_second }
+    }
+
+    /// Applies the gas and code size limits of the replay to the environment of the fork.
+    pub fn configure_env(&self, env: &mut Env) {
+        env.evm_env.cfg_env.disable_block_gas_limit = self.disable_block_gas_limit;
+
+        // By default do not enforce transaction gas limits imposed by Osaka (EIP-7825).
+        // Users can opt-in to enable these limits by setting `enable_tx_gas_limit` to true.
+        if !self.enable_tx_gas_limit {
+            env.evm_env.cfg_env.tx_gas_limit_cap = Some(u64::MAX);
+        }
+
+        env.evm_env.cfg_env.limit_contract_code_size = None;
+    }
+}
+
 impl RunArgs {
     /// Executes the transaction by replaying it
     ///
@@ -123,17 +152,17 @@ impl RunArgs {
     ///
     /// Note: This executes the transaction(s) as is: Cheatcodes are disabled
     pub async fn run(self) -> Result<()> {
-        let figment = self.rpc.clone().into_figment(self.with_local_artifacts).merge(&self);
+        let figment =
+            self.replay.rpc.clone().into_figment(self.with_local_artifacts).merge(&self.replay);
         let evm_opts = figment.extract::<EvmOpts>()?;
         let mut config = Config::from_provider(figment)?.sanitized();
 
-        let label = self.label;
+        let label = self.replay.label;
         let with_local_artifacts = self.with_local_artifacts;
         let debug = self.debug;
         let decode_internal = self.decode_internal;
-        let disable_labels = self.disable_labels;
-        let compute_units_per_second =
-            if self.no_rate_limit { Some(u64::MAX) } else { self.compute_units_per_second };
+        let disable_labels = self.replay.disable_labels;
+        let compute_units_per_second = self.replay.compute_units_per_second();
 
         let provider = foundry_cli::utils::get_tempo_provider_builder(&config)?
             .compute_units_per_second_opt(compute_units_per_second)
@@ -159,32 +188,13 @@ pub async fn run(self) -> Result<()> {
             TracingExecutor::get_fork_material(&mut config, evm_opts)
         )?;
 
-        let evm_version = self.evm_version;
-
-        env.evm_env.cfg_env.disable_block_gas_limit = self.disable_block_gas_limit;
-
-        // By default do not enforce transaction gas limits imposed by Osaka (EIP-7825).
-        // Users can opt-in to enable these limits by setting `enable_tx_gas_limit` to true.
-        if !self.enable_tx_gas_limit {
-            env.evm_env.cfg_env.tx_gas_limit_cap = Some(u64::MAX);
-        }
+        let evm_version = self.replay.evm_version;
 
-        env.evm_env.cfg_env.limit_contract_code_size = None;
+        self.replay.configure_env(&mut env);
         env.evm_env.block_env.number = U256::from(tx_block_number);
 
         if let Some(block) = &block {
-            env.evm_env.block_env.timestamp = U256::from(block.header.timestamp());
-            env.evm_env.block_env.beneficiary = block.header.beneficiary();
-            env.evm_env.block_env.difficulty = block.header.difficulty();
-            env.evm_env.block_env.prevrandao = Some(block.header.mix_hash().unwrap_or_default());
-            env.evm_env.block_env.basefee = block.header.base_fee_per_gas().unwrap_or_default();
-            env.evm_env.block_env.gas_limit = block.header.gas_limit();
-
-            apply_chain_and_block_specific_env_changes::<TempoNetwork>(
-                env.as_env_mut(),
-                block,
-                config.networks,
-            );
+            configure_block_env(&mut env, block, config.networks);
         }
 
         let trace_mode = TraceMode::Call
@@ -230,22 +240,9 @@ pub async fn run(self) -> Result<()> {
                         break;
                     }
 
-                    configure_tempo_tx_req_env(&mut env, tx)?;
-
-                    // System transactions may have zero or invalid gas limits from the RPC.
-                    // Override with a reasonable value to allow execution.
-                    if is_known_system_sender(tx.from())
-                        || tx.transaction_type() == Some(SYSTEM_TRANSACTION_TYPE)
-                    {
-                        env.evm_env.cfg_env.disable_block_gas_limit = true;
-                        env.evm_env.cfg_env.disable_balance_check = true;
-                        env.evm_env.cfg_env.disable_nonce_check = true;
-                        env.evm_env.cfg_env.disable_fee_charge = true;
-                        env.evm_env.cfg_env.disable_base_fee = true;
-                        env.tx.gas_limit = u64::MAX;
-                    }
-
-                    env.evm_env.cfg_env.disable_balance_check = true;
+                    // Start from the block env, as system transactions disable checks in the env.
+                    let mut env = env.clone();
+                    configure_block_tx_env(&mut env, tx)?;
 
                     if let Some(to) = Transaction::to(tx) {
                         trace!(tx=?tx.tx_hash(),?to, "executing previous call transaction");
@@ -309,7 +306,7 @@ pub async fn run(self) -> Result<()> {
             debug,
             decode_internal,
             disable_labels,
-            self.trace_depth,
+            self.replay.trace_depth,
         )
         .await?;
 
@@ -356,9 +353,9 @@ fn gather_trace_addresses(traces: &Traces) -> impl Iterator<Item = Address> {
     addresses.into_iter()
 }
 
-impl figment::Provider for RunArgs {
+impl figment::Provider for ReplayOpts {
     fn metadata(&self) -> Metadata {
-        Metadata::named("RunArgs")
+        Metadata::named("ReplayOpts")
     }
 
     fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
@@ -376,6 +373,47 @@ fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
     }
 }
 
+/// Sets the block environment to the one of the given block.
+pub(crate) fn configure_block_env(
+    env: &mut Env,
+    block: &<TempoNetwork as Network>::BlockResponse,
+    networks: NetworkConfigs,
+) {
+    env.evm_env.block_env.timestamp = U256::from(block.header.timestamp());
+    env.evm_env.block_env.beneficiary = block.header.beneficiary();
+    env.evm_env.block_env.difficulty = block.header.difficulty();
+    env.evm_env.block_env.prevrandao = Some(block.header.mix_hash().unwrap_or_default());
+    env.evm_env.block_env.basefee = block.header.base_fee_per_gas().unwrap_or_default();
+    env.evm_env.block_env.gas_limit = block.header.gas_limit();
+
+    apply_chain_and_block_specific_env_changes::<TempoNetwork>(env.as_env_mut(), block, networks);
+}
+
+/// Configures the environment to replay a transaction of a block.
+///
+/// Balance checks are disabled, and system transactions are executed without any fee or gas
+/// checks.
+pub(crate) fn configure_block_tx_env(
+    env: &mut Env,
+    tx: &alloy_rpc_types::Transaction<TempoTxEnvelope>,
+) -> eyre::Result<()> {
+    configure_tempo_tx_req_env(env, tx)?;
+
+    // System transactions may have zero or invalid gas limits from the RPC.
+    // Override with a reasonable value to allow execution.
+    if is_known_system_sender(tx.from()) || tx.transaction_type() == Some(SYSTEM_TRANSACTION_TYPE) {
+        env.evm_env.cfg_env.disable_block_gas_limit = true;
+        env.evm_env.cfg_env.disable_balance_check = true;
+        env.evm_env.cfg_env.disable_nonce_check = true;
+        env.evm_env.cfg_env.disable_fee_charge = true;
+        env.evm_env.cfg_env.disable_base_fee = true;
+        env.tx.gas_limit = u64::MAX;
+    }
+
+    env.evm_env.cfg_env.disable_balance_check = true;
+    Ok(())
+}
+
 pub fn configure_tempo_tx_req_env(
     env: &mut Env,
     tx: &alloy_rpc_types::Transaction<TempoTxEnvelope>,
@@ -0,0 +1,396 @@
+use super::run::{ReplayOpts, configure_block_env, configure_block_tx_env};
+use crate::debug::parse_labels;
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_network::TransactionResponse;
+use alloy_primitives::{Address, Selector, TxHash, U256, hex, map::HashMap};
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockNumberOrTag, BlockTransactions};
+use clap::Parser;
+use eyre::{Result, WrapErr};
+use foundry_cli::utils::{TraceResult, init_progress, print_traces};
+use foundry_common::shell;
+use foundry_config::Config;
+use foundry_evm::{
+    Env,
+    executors::TracingExecutor,
+    opts::EvmOpts,
+    traces::{
+        CallTraceDecoder, CallTraceDecoderBuilder, TraceMode,
+        identifier::{SignaturesIdentifier, TraceIdentifiers},
+    },
+};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// CLI arguments for `cast run-block`.
+#[derive(Clone, Debug, Parser)]
+pub struct RunBlockArgs {
+    /// The number or tag of the block to replay.
+    block: BlockNumberOrTag,
+
+    /// Print the full trace of every shown transaction.
+    ///
+    /// Not supported with `--json`.
+    #[arg(long)]
+    traces: bool,
+
+    /// Only show the transactions that interact with the given address or call the given
+    /// selector.
+    ///
+    /// A transaction matches if any call in its trace is from or to one of the addresses, or
+    /// calls one of the selectors. All transactions are still executed.
+    #[arg(long, value_name = "ADDRESS|SELECTOR")]
+    filter: Vec<TxFilter>,
+
+    #[command(flatten)]
+    replay: ReplayOpts,
+}
+
+/// Filter for the transactions shown by `cast run-block`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxFilter {
+    /// Matches calls from or to an address.
+    Address(Address),
+    /// Matches calls to a function selector.
+    Selector(Selector),
+}
+
+impl FromStr for TxFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|err| format!("invalid filter `{s}`: {err}"))?;
+        match bytes.len() {
+            20 => Ok(Self::Address(Address::from_slice(&bytes))),
+            4 => Ok(Self::Selector(Selector::from_slice(&bytes))),
+            _ => Err(format!("invalid filter `{s}`: expected an address or a 4-byte selector")),
+        }
+    }
+}
+
+impl TxFilter {
+    /// Returns `true` if any call of the traces matches the filter.
+    fn matches(&self, result: &TraceResult) -> bool {
+        result.traces.iter().flatten().flat_map(|(_, trace)| trace.arena.nodes()).any(|node| {
+            match self {
+                Self::Address(address) => {
+                    node.trace.address == *address || node.trace.caller == *address
+                }
+                Self::Selector(selector) => node.trace.data.starts_with(selector.as_slice()),
+            }
+        })
+    }
+}
+
+/// The summary of a replayed transaction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TxSummary {
+    index: usize,
+    hash: TxHash,
+    from: Address,
+    to: Option<Address>,
+    success: bool,
+    gas_used: u64,
+    fee_token: Option<Address>,
+    fee_payer: Option<Address>,
+    call: String,
+}
+
+impl RunBlockArgs {
+    /// Replays every transaction of the block on top of the state of its parent block, and prints
+    /// a summary of each of them.
+    ///
+    /// Note: This executes the transactions as is: Cheatcodes are disabled
+    pub async fn run(self) -> Result<()> {
+        if self.traces && shell::is_json() {
+            eyre::bail!("`--traces` is not supported with `--json`");
+        }
+
+        let figment = self.replay.rpc.clone().into_figment(false).merge(&self.replay);
+        let evm_opts = figment.extract::<EvmOpts>()?;
+        let mut config = Config::from_provider(figment)?.sanitized();
+
+        let provider = foundry_cli::utils::get_tempo_provider_builder(&config)?
+            .compute_units_per_second_opt(self.replay.compute_units_per_second())
+            .build()?;
+
+        let block = provider
+            .get_block(self.block.into())
+            .full()
+            .await?
+            .ok_or_else(|| eyre::eyre!("block not found: {}", self.block))?;
+        let block_number = block.header.number();
+        if block_number == 0 {
+            eyre::bail!("cannot replay the genesis block");
+        }
+
+        let receipts = provider
+            .get_block_receipts(block_number.into())
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|receipt| (receipt.inner.transaction_hash, receipt))
+            .collect::<HashMap<_, _>>();
+
+        // we need to fork off the parent block
+        config.fork_block_number = Some(block_number - 1);
+
+        let create2_deployer = evm_opts.create2_deployer;
+        let (mut env, fork, chain, networks) =
+            TracingExecutor::get_fork_material(&mut config, evm_opts).await?;
+
+        self.replay.configure_env(&mut env);
+        env.evm_env.block_env.number = U256::from(block_number);
+        configure_block_env(&mut env, &block, config.networks);
+
+        let trace_mode = TraceMode::Call.with_state_changes(shell::verbosity() > 4);
+        let mut executor = TracingExecutor::new(
+            env.clone(),
+            fork,
+            self.replay.evm_version,
+            trace_mode,
+            networks,
+            create2_deployer,
+            None,
+        )?;
+        let env = Env::new_with_spec_id(
+            env.evm_env.cfg_env.clone(),
+            env.evm_env.block_env.clone(),
+            env.tx.clone(),
+            executor.spec_id(),
+        );
+
+        let mut decoder = CallTraceDecoderBuilder::new()
+            .with_labels(parse_labels(&self.replay.label).chain(config.labels.clone()))
+            .with_signature_identifier(SignaturesIdentifier::from_config(&config)?)
+            .with_label_disabled(self.replay.disable_labels)
+            .build();
+        let mut identifier = TraceIdentifiers::new().with_external(&config, Some(chain))?;
+
+        let BlockTransactions::Full(ref txs) = block.transactions else {
+            return Err(eyre::eyre!("Could not get block txs"));
+        };
+
+        let pb = (!shell::is_json()).then(|| init_progress(txs.len() as u64, "tx"));
+        let mut results = Vec::with_capacity(txs.len());
+        for (index, tx) in txs.iter().enumerate() {
+            // Start from the block env, as system transactions disable checks in the env.
+            let mut env = env.clone();
+            configure_block_tx_env(&mut env, tx)?;
+
+            let result = if let Some(to) = Transaction::to(tx) {
+                trace!(tx=?tx.tx_hash(), ?to, "executing call transaction");
+                TraceResult::try_from(executor.transact_with_env(env))
+            } else {
+                trace!(tx=?tx.tx_hash(), "executing create transaction");
+                TraceResult::try_from(executor.deploy_with_env(env, None))
+            };
+            let result = result.wrap_err_with(|| {
+                format!("Failed to execute transaction: {:?} in block {block_number}", tx.tx_hash())
+            })?;
+            results.push(result);
+
+            if let Some(pb) = &pb {
+                pb.set_position((index + 1) as u64);
+            }
+        }
+        if let Some(pb) = pb {
+            pb.finish_and_clear();
+        }
+
+        let mut summaries = Vec::new();
+        for (index, (tx, mut result)) in txs.iter().zip(results).enumerate() {
+            if !self.filter.is_empty() && !self.filter.iter().any(|filter| filter.matches(&result))
+            {
+                continue;
+            }
+
+            for (_, trace) in result.traces.as_deref().unwrap_or_default() {
+                decoder.identify(trace, &mut identifier);
+            }
+            decoder.labels.extend(result.labels.clone());
+
+            let receipt = receipts.get(&tx.tx_hash());
+            let summary = TxSummary {
+                index,
+                hash: tx.tx_hash(),
+                from: tx.from(),
+                to: Transaction::to(tx),
+                success: result.success,
+                gas_used: result.gas_used,
+                fee_token: receipt.and_then(|receipt| receipt.fee_token),
+                fee_payer: receipt.map(|receipt| receipt.fee_payer),
+                call: decode_call(&decoder, &result).await,
+            };
+
+            if shell::is_json() {
+                summaries.push(summary);
+                continue;
+            }
+
+            print_summary(&summary)?;
+            if self.traces {
+                print_traces(
+                    &mut result,
+                    &decoder,
+                    shell::verbosity() > 0,
+                    shell::verbosity() > 4,
+                    self.replay.trace_depth,
+                )
+                .await?;
+                sh_println!()?;
+            }
+        }
+
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string_pretty(&summaries)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes the top-level call of a transaction, e.g. `Token::transfer(0x..., 100)`.
+async fn decode_call(decoder: &CallTraceDecoder, result: &TraceResult) -> String {
+    let Some(node) = result
+        .traces
+        .as_deref()
+        .unwrap_or_default()
+        .first()
+        .and_then(|(_, trace)| trace.arena.nodes().first())
+    else {
+        return String::new();
+    };
+    let trace = &node.trace;
+    if trace.kind.is_any_create() {
+        return "new()".to_string();
+    }
+
+    let decoded = decoder.decode_function(trace).await;
+    let call = match decoded.call_data {
+        Some(call_data) => {
+            let name = call_data.signature.split('(').next().unwrap_or_default().to_string();
+            format!("{name}({})", call_data.args.join(", "))
+        }
+        None if trace.data.is_empty() => "receive()".to_string(),
+        None => hex::encode_prefixed(&trace.data[..trace.data.len().min(4)]),
+    };
+    match decoded.label {
+        Some(label) => format!("{label}::{call}"),
+        None => call,
+    }
+}
+
+fn print_summary(summary: &TxSummary) -> Result<()> {
+    let TxSummary { index, hash, from, to, success, gas_used, fee_token, fee_payer, call } =
+        summary;
+    let status = if *success { "success" } else { "reverted" };
+    let or_none = |address: &Option<Address>| {
+        address.map(|address| address.to_string()).unwrap_or_else(|| "-".to_string())
+    };
+
+    sh_println!("[{index}] {hash} ({status}, gas: {gas_used})")?;
+    sh_println!("    from:      {from}")?;
+    sh_println!("    to:        {}", to.map_or_else(|| "create".to_string(), |to| to.to_string()))?;
+    sh_println!("    fee token: {}", or_none(fee_token))?;
+    sh_println!("    fee payer: {}", or_none(fee_payer))?;
+    sh_println!("    call:      {call}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::{Bytes, address};
+    use foundry_evm::traces::{CallKind, CallTrace, CallTraceArena, SparsedTraceArena, TraceKind};
+
+    const FROM: Address = address!("0x0000000000000000000000000000000000000001");
+    const TOKEN: Address = address!("0x0000000000000000000000000000000000000002");
+
+    /// Returns the result of a transaction whose top-level call is the given trace.
+    fn result(trace: CallTrace) -> TraceResult {
+        let mut arena = CallTraceArena::default();
+        arena.nodes_mut()[0].trace = trace;
+        TraceResult {
+            success: true,
+            traces: Some(vec![(
+                TraceKind::Execution,
+                SparsedTraceArena { arena, ignored: Default::default() },
+            )]),
+            gas_used: 0,
+            labels: Default::default(),
+        }
+    }
+
+    fn call(data: Vec<u8>) -> CallTrace {
+        CallTrace {
+            kind: CallKind::Call,
+            caller: FROM,
+            address: TOKEN,
+            data: Bytes::from(data),
+            success: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_tx_filter() {
+        assert_eq!(
+            "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse::<TxFilter>(),
+            Ok(TxFilter::Address(address!("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045")))
+        );
+        assert_eq!(
+            "0xa9059cbb".parse::<TxFilter>(),
+            Ok(TxFilter::Selector(Selector::from([0xa9, 0x05, 0x9c, 0xbb])))
+        );
+        assert!("0x1234".parse::<TxFilter>().is_err());
+        assert!("transfer".parse::<TxFilter>().is_err());
+    }
+
+    #[test]
+    fn tx_filter_matches() {
+        let transfer = Selector::from([0xa9, 0x05, 0x9c, 0xbb]);
+        let result = result(call([transfer.as_slice(), &[0; 64]].concat()));
+
+        assert!(TxFilter::Address(FROM).matches(&result));
+        assert!(TxFilter::Address(TOKEN).matches(&result));
+        assert!(TxFilter::Selector(transfer).matches(&result));
+        assert!(!TxFilter::Address(Address::repeat_byte(0x11)).matches(&result));
+        assert!(!TxFilter::Selector(Selector::from([0x09, 0x5e, 0xa7, 0xb3])).matches(&result));
+        assert!(!TxFilter::Address(FROM).matches(&TraceResult { traces: None, ..result }));
+    }
+
+    #[tokio::test]
+    async fn decodes_top_level_call() {
+        let abi = JsonAbi::parse(["function transfer(address to, uint256 amount)"]).unwrap();
+        let decoder = CallTraceDecoderBuilder::new()
+            .with_abi(&abi)
+            .with_labels([(TOKEN, "Token".to_string())])
+            .build();
+
+        let calldata = abi.function("transfer").unwrap()[0]
+            .abi_encode_input(&[DynSolValue::Address(FROM), DynSolValue::Uint(U256::from(7), 256)])
+            .unwrap();
+        assert_eq!(
+            decode_call(&decoder, &result(call(calldata))).await,
+            format!("Token::transfer({FROM}, 7)")
+        );
+
+        // Unknown selectors are printed as is, and calls without data are transfers.
+        assert_eq!(
+            decode_call(&decoder, &result(call(vec![0x12, 0x34, 0x56, 0x78, 0x9a]))).await,
+            "Token::0x12345678"
+        );
+        assert_eq!(decode_call(&decoder, &result(call(vec![]))).await, "Token::receive()");
+
+        let create = CallTrace { kind: CallKind::Create, ..call(vec![]) };
+        assert_eq!(decode_call(&decoder, &result(create)).await, "new()");
+        assert_eq!(
+            decode_call(&decoder, &TraceResult { traces: None, ..result(call(vec![])) }).await,
+            ""
+        );
+    }
+}
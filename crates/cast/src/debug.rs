@@ -41,16 +41,7 @@ pub(crate) async fn handle_traces(
         (None, ContractSources::default())
     };
 
-    let labels = labels.iter().filter_map(|label_str| {
-        let mut iter = label_str.split(':');
-
-        if let Some(addr) = iter.next()
-            && let (Ok(address), Some(label)) = (Address::from_str(addr), iter.next())
-        {
-            return Some((address, label.to_string()));
-        }
-        None
-    });
+    let labels = parse_labels(&labels);
     let config_labels = config.labels.clone().into_iter();
 
     let mut builder = CallTraceDecoderBuilder::new()
@@ -98,3 +89,17 @@ pub(crate) async fn handle_traces(
 
     Ok(())
 }
+
+/// Parses `<address>:<label>` pairs, ignoring invalid ones.
+pub(crate) fn parse_labels(labels: &[String]) -> impl Iterator<Item = (Address, String)> + '_ {
+    labels.iter().filter_map(|label_str| {
+        let mut iter = label_str.split(':');
+
+        if let Some(addr) = iter.next()
+            && let (Ok(address), Some(label)) = (Address::from_str(addr), iter.next())
+        {
+            return Some((address, label.to_string()));
+        }
+        None
+    })
+}
@@ -13,8 +13,9 @@
     bind::BindArgs, call::CallArgs, constructor_args::ConstructorArgsArgs, create2::Create2Args,
     creation_code::CreationCodeArgs, da_estimate::DAEstimateArgs, erc20::Erc20Subcommand,
     estimate::EstimateArgs, find_block::FindBlockArgs, interface::InterfaceArgs, logs::LogsArgs,
-    mktx::MakeTxArgs, rpc::RpcArgs, run::RunArgs, send::SendTxArgs, storage::StorageArgs,
-    txpool::TxPoolSubcommands, wallet::WalletSubcommands,
+    mktx::MakeTxArgs, multicall::MulticallArgs, rpc::RpcArgs, run::RunArgs,
+    run_block::RunBlockArgs, send::SendTxArgs, storage::StorageArgs, txpool::TxPoolSubcommands,
+    wallet::WalletSubcommands,
 };
 
 /// A Swiss Army knife for interacting with Ethereum applications from the command line.
@@ -408,6 +409,10 @@ pub enum CastSubcommand {
     #[command(visible_alias = "c")]
     Call(CallArgs),
 
+    /// Aggregate many read calls into a single Multicall3 `aggregate3` call.
+    #[command(visible_alias = "mc")]
+    Multicall(MulticallArgs),
+
     /// ABI-encode a function with arguments.
     #[command(name = "calldata", visible_alias = "cd")]
     CalldataEncode {
@@ -1090,6 +1095,11 @@ pub enum CastSubcommand {
     #[command(visible_alias = "r")]
     Run(RunArgs),
 
+    /// Runs all transactions of a published block in a local environment and prints a summary of
+    /// each of them.
+    #[command(visible_alias = "rb")]
+    RunBlock(RunBlockArgs),
+
     /// Perform a raw JSON-RPC request.
     #[command(visible_alias = "rp")]
     Rpc(RpcArgs),
@@ -1369,6 +1369,34 @@
     }
 );
 
+// tests that `cast run-block` replays every transaction of a block and filters them.
+casttest!(
+    #[ignore = "tempo skip"]
+    run_block_succeeds,
+    |_prj, cmd| {
+        let rpc = next_http_archive_rpc_url();
+        cmd.args([
+            "run-block",
+            "16239315",
+            "--filter",
+            "0x91da5bf3F8Eb72724E6f50Ec6C3D199C6355c59c",
+            "--rpc-url",
+            rpc.as_str(),
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+[116] 0x44f2aaa351460c074f2cb1e5a9e28cbc7d83f33e425101d2de14331c7b7ec31e (success, gas: 21000)
+    from:      0x199D5ED7F45F4eE35960cF22EAde2076e95B253F
+    to:        0x91da5bf3F8Eb72724E6f50Ec6C3D199C6355c59c
+    fee token: [..]
+    fee payer: [..]
+    call:      receive()
+...
+
+"#]]);
+    }
+);
+
 // tests that `cast --to-base` commands are working correctly.
 casttest!(to_base, |_prj, cmd| {
     let values = [
@@ -1616,6 +1644,59 @@
     .stdout_eq(file!["../fixtures/cast_logs.stdout"]);
 });
 
+casttest!(multicall, |_prj, cmd| {
+    let rpc = next_http_archive_rpc_url();
+    cmd.args([
+        "multicall",
+        "--rpc-url",
+        rpc.as_str(),
+        "--block",
+        "20000000",
+        "--allow-failure",
+        "0xdac17f958d2ee523a2206206994597c13d831ec7",
+        "decimals()(uint8)",
+        "0xdac17f958d2ee523a2206206994597c13d831ec7",
+        "name()(string)",
+        "0x6B175474E89094C44Da98b954EedeAC495271d0F",
+        "transfer(address,uint256)(bool)",
+        "0x0000000000000000000000000000000000000001",
+        "1",
+    ])
+    .assert_success()
+    .stdout_eq(str![[r#"
+6
+"Tether USD"
+Error: Dai/insufficient-balance
+
+"#]]);
+
+    cmd.cast_fuse()
+        .args([
+            "multicall",
+            "--json",
+            "--rpc-url",
+            rpc.as_str(),
+            "--block",
+            "20000000",
+            "0xdac17f958d2ee523a2206206994597c13d831ec7",
+            "decimals()(uint8)",
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+[
+  {
+    "target": "0xdAC17F958D2ee523a2206206994597C13D831ec7",
+    "signature": "decimals()(uint8)",
+    "success": true,
+    "result": [
+      6
+    ]
+  }
+]
+
+"#]]);
+});
+
 casttest!(mktx, |_prj, cmd| {
     cmd.args([
         "mktx",
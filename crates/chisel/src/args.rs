@@ -2,12 +2,18 @@
     opts::{Chisel, ChiselSubcommand},
     prelude::{ChiselCommand, ChiselDispatcher, SolidityHelper},
 };
+use alloy_primitives::map::{HashMap, HashSet};
 use clap::Parser;
 use eyre::{Context, Result};
+use forge_script::{ScriptArgs, ScriptState};
 use foundry_cli::utils::{self, LoadConfig};
 use foundry_common::fs;
+use revm::DatabaseRef;
 use rustyline::{Editor, config::Configurer, error::ReadlineError};
-use std::{ops::ControlFlow, path::PathBuf};
+use std::{
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
 use yansi::Paint;
 
 /// Run the `chisel` command line interface.
@@ -41,6 +47,12 @@ pub async fn run_command(args: Chisel) -> Result<()> {
     // Load configuration
     let (config, evm_opts) = args.load_config_and_evm_opts()?;
 
+    // Execute the script first, so that the session starts from its end state.
+    let script = match &args.from_script {
+        Some(path) => Some(execute_script(&args, path).await?),
+        None => None,
+    };
+
     // Create a new cli dispatcher
     let mut dispatcher = ChiselDispatcher::new(crate::source::SessionSourceConfig {
         // Enable traces if any level of verbosity was passed
@@ -48,7 +60,7 @@ pub async fn run_command(args: Chisel) -> Result<()> {
         foundry_config: config,
         no_vm: args.no_vm,
         evm_opts,
-        backend: None,
+        backend: script.as_ref().map(|script| script.backend.clone()),
         calldata: None,
         ir_minimum: args.ir_minimum,
     })?;
@@ -56,6 +68,10 @@ pub async fn run_command(args: Chisel) -> Result<()> {
     // Execute prelude Solidity source files
     evaluate_prelude(&mut dispatcher, args.prelude).await?;
 
+    if let Some(script) = &script {
+        bind_script_contracts(&mut dispatcher, script).await?;
+    }
+
     if let Some(cmd) = args.cmd {
         try_cf!(handle_cli_command(&mut dispatcher, cmd).await?);
         return Ok(());
@@ -150,6 +166,92 @@ async fn load_prelude_file(
     dispatcher.dispatch(&prelude).await
 }
 
+/// Executes a script locally, returning its end state.
+async fn execute_script(args: &Chisel, path: &Path) -> Result<ScriptState> {
+    sh_println!("{} {}", "Executing script:".yellow(), path.display())?;
+    let script = ScriptArgs {
+        path: path.to_string_lossy().into_owned(),
+        sig: "run()".to_string(),
+        build: args.build.clone(),
+        evm: args.evm.clone(),
+        ..Default::default()
+    };
+    let state = script.execute_and_commit().await?;
+    if !state.result.success {
+        eyre::bail!("Failed to execute script {}", path.display());
+    }
+    Ok(state)
+}
+
+/// Binds the contracts labeled by the script to variables named after their labels. Contracts
+/// deployed by the script without a label are named after their contract.
+async fn bind_script_contracts(
+    dispatcher: &mut ChiselDispatcher,
+    script: &ScriptState,
+) -> Result<()> {
+    let root = dispatcher.source().config.foundry_config.root.clone();
+
+    let mut contracts = script
+        .result
+        .labeled_addresses
+        .iter()
+        .map(|(address, label)| (*address, label.clone()))
+        .collect::<Vec<_>>();
+    contracts.sort_by(|(_, a), (_, b)| a.cmp(b));
+    for contract in script.result.get_created_contracts(&script.known_contracts) {
+        if let Some(name) = contract.contract_name
+            && !contracts.iter().any(|(address, _)| *address == contract.address)
+        {
+            contracts
+                .push((contract.address, format!("{}{}", name[..1].to_lowercase(), &name[1..])));
+        }
+    }
+
+    let mut imports = HashMap::<String, PathBuf>::default();
+    let mut names = HashSet::<String>::default();
+    for (address, label) in contracts {
+        let Some(name) = variable_name(&label) else { continue };
+        let Some(info) = script.backend.basic_ref(address)? else { continue };
+        let code = match info.code {
+            Some(code) => code,
+            None => script.backend.code_by_hash_ref(info.code_hash)?,
+        };
+        let Some((id, _)) = script.known_contracts.find_by_deployed_code(&code.original_bytes())
+        else {
+            continue;
+        };
+
+        // Contracts are imported by name, so skip the ones shadowed by a contract of another file.
+        let path = root.join(&id.source);
+        if imports.get(&id.name).is_some_and(|imported| *imported != path) || names.contains(&name)
+        {
+            continue;
+        }
+        if !imports.contains_key(&id.name) {
+            let import = format!("import {{{}}} from \"{}\";", id.name, path.display());
+            try_cf!(dispatcher.dispatch(&import).await?);
+            imports.insert(id.name.clone(), path);
+        }
+
+        try_cf!(dispatcher.dispatch(&format!("{0} {name} = {0}({address});", id.name)).await?);
+        sh_println!("{} {name} = {}({address})", "Bound".green(), id.name)?;
+        names.insert(name);
+    }
+    Ok(())
+}
+
+/// Returns a valid Solidity identifier for a label, replacing invalid characters with underscores.
+fn variable_name(label: &str) -> Option<String> {
+    let mut name = label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect::<String>();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    (!name.is_empty()).then_some(name)
+}
+
 async fn handle_cli_command(
     d: &mut ChiselDispatcher,
     cmd: ChiselSubcommand,
@@ -182,4 +284,12 @@ mod tests {
     fn verify_cli() {
         Chisel::command().debug_assert();
     }
+
+    #[test]
+    fn label_variable_names() {
+        assert_eq!(variable_name("counter").as_deref(), Some("counter"));
+        assert_eq!(variable_name("USDC Proxy").as_deref(), Some("USDC_Proxy"));
+        assert_eq!(variable_name("1inch").as_deref(), Some("_1inch"));
+        assert_eq!(variable_name(""), None);
+    }
 }
@@ -109,6 +109,33 @@ pub enum ChiselCommand {
         /// Variable name.
         var: String,
     },
+
+    /// Watch an expression, printing its value after every executed statement. Lists the watched
+    /// expressions if no expression is provided.
+    #[command(visible_alias = "w")]
+    Watch {
+        /// Stop watching all expressions.
+        #[arg(long, conflicts_with = "expr")]
+        clear: bool,
+        /// Expression to watch.
+        #[arg(trailing_var_arg = true)]
+        expr: Vec<String>,
+    },
+
+    /// Display a storage slot of an account. Slots of contracts compiled in the session can also
+    /// be read by state variable name, and are decoded with their storage layout.
+    #[command(visible_alias = "st")]
+    Storage {
+        /// Account address.
+        addr: Address,
+        /// Slot number or state variable name (empty to display all the state variables, or the
+        /// slots changed in the session).
+        slot: Option<String>,
+    },
+
+    /// Display the state changes caused by the last executed statement.
+    #[command(visible_alias = "df")]
+    Diff,
 }
 
 impl ChiselCommand {
@@ -184,4 +211,29 @@ mod tests {
     fn print_help() {
         let _ = sh_eprintln!("{}", ChiselCommand::format_help());
     }
+
+    #[test]
+    fn parse_debug_commands() {
+        let ChiselCommand::Watch { clear, expr } = ChiselCommand::parse("watch a + b").unwrap()
+        else {
+            panic!("expected `watch`")
+        };
+        assert!(!clear);
+        assert_eq!(expr.join(" "), "a + b");
+        assert!(matches!(
+            ChiselCommand::parse("w --clear").unwrap(),
+            ChiselCommand::Watch { clear: true, .. }
+        ));
+
+        let addr = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        let ChiselCommand::Storage { addr: parsed, slot } =
+            ChiselCommand::parse(&format!("storage {addr} owner")).unwrap()
+        else {
+            panic!("expected `storage`")
+        };
+        assert_eq!(parsed, addr.parse::<Address>().unwrap());
+        assert_eq!(slot.as_deref(), Some("owner"));
+
+        assert!(matches!(ChiselCommand::parse("df").unwrap(), ChiselCommand::Diff));
+    }
 }
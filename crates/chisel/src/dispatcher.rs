@@ -6,8 +6,9 @@
 use crate::{
     prelude::{ChiselCommand, ChiselResult, ChiselSession, SessionSourceConfig, SolidityHelper},
     source::SessionSource,
+    state::{SessionState, StorageVariable},
 };
-use alloy_primitives::{Address, hex};
+use alloy_primitives::{Address, B256, U256, hex};
 use eyre::{Context, Result};
 use forge_fmt::FormatterConfig;
 use foundry_cli::utils::fetch_abi_from_etherscan;
@@ -51,6 +52,10 @@
 pub struct ChiselDispatcher {
     pub session: ChiselSession,
     pub helper: SolidityHelper,
+    /// Expressions printed after every executed statement.
+    pub watches: Vec<String>,
+    /// The session source before the last executed statement.
+    previous: Option<SessionSource>,
 }
 
 /// Helper function that formats solidity source with the given [FormatterConfig]
@@ -63,7 +68,7 @@ impl ChiselDispatcher {
     /// Associated public function to create a new Dispatcher instance
     pub fn new(config: SessionSourceConfig) -> eyre::Result<Self> {
         let session = ChiselSession::new(config)?;
-        Ok(Self { session, helper: Default::default() })
+        Ok(Self { session, helper: Default::default(), watches: Vec::new(), previous: None })
     }
 
     /// Returns the optional ID of the current session.
@@ -225,8 +230,24 @@ async fn execute_and_replace(&mut self, mut new_source: SessionSource) -> Result
         }
 
         // the code could be compiled, save it
-        *self.source_mut() = new_source;
+        self.previous = Some(std::mem::replace(self.source_mut(), new_source));
 
+        self.show_watches().await
+    }
+
+    /// Evaluates and prints the watched expressions.
+    async fn show_watches(&self) -> Result<()> {
+        if self.watches.is_empty() {
+            return Ok(());
+        }
+
+        sh_println!("{}", "Watches:".green())?;
+        for expr in &self.watches {
+            match self.source().inspect(expr).await {
+                Ok((_, Some(res))) => sh_println!("{}\n{res}", expr.yellow())?,
+                _ => sh_println!("{}\n{}", expr.yellow(), "Could not evaluate expression".red())?,
+            }
+        }
         Ok(())
     }
 }
@@ -261,6 +282,9 @@ async fn dispatch_command_impl(&mut self, cmd: ChiselCommand) -> Result<()> {
             ChiselCommand::Exec { command, args } => self.exec_command(command, args),
             ChiselCommand::Edit => self.edit_session().await,
             ChiselCommand::RawStack { var } => self.show_raw_stack(var).await,
+            ChiselCommand::Watch { clear, expr } => self.watch(clear, expr).await,
+            ChiselCommand::Storage { addr, slot } => self.show_storage(addr, slot).await,
+            ChiselCommand::Diff => self.show_diff().await,
         }
     }
 
@@ -270,6 +294,7 @@ pub(crate) fn show_help(&self) -> Result<()> {
 
     pub(crate) fn clear_source(&mut self) -> Result<()> {
         self.source_mut().clear();
+        self.previous = None;
         sh_println!("Cleared session!")
     }
 
@@ -300,6 +325,7 @@ pub(crate) fn load_session(&mut self, id: &str) -> Result<()> {
 
         new_session.source.build()?;
         self.session = new_session;
+        self.previous = None;
         sh_println!("Loaded Chisel session! (ID = {})", self.session.id.as_ref().unwrap())
     }
 
@@ -505,6 +531,154 @@ pub(crate) async fn show_raw_stack(&mut self, var: String) -> Result<()> {
 
         eyre::bail!("Variable must exist within `run()` function.")
     }
+
+    pub(crate) async fn watch(&mut self, clear: bool, expr: Vec<String>) -> Result<()> {
+        if clear {
+            self.watches.clear();
+            return sh_println!("Cleared watched expressions!");
+        }
+
+        if expr.is_empty() {
+            if self.watches.is_empty() {
+                eyre::bail!("No watched expressions. Use `!watch <expr>` to watch an expression.");
+            }
+            for (i, expr) in self.watches.iter().enumerate() {
+                sh_println!("{}: {expr}", format!("[{i}]").yellow())?;
+            }
+            return Ok(());
+        }
+
+        self.watches.push(expr.join(" "));
+        self.show_watches().await
+    }
+
+    pub(crate) async fn show_storage(
+        &mut self,
+        address: Address,
+        slot: Option<String>,
+    ) -> Result<()> {
+        let source = self.source_mut();
+        let res = source.execute().await?;
+        let source = self.source();
+        let state = SessionState::new(&res, source.config.backend.as_ref());
+        let variables = state.storage_variables(source, address)?;
+
+        let print_variable = |var: &StorageVariable, word: U256| {
+            sh_println!(
+                "{}: {}",
+                format!("{} ({})", var.label, var.ty).yellow(),
+                var.format(word).cyan()
+            )
+        };
+
+        match slot {
+            Some(slot) => {
+                if let Ok(slot) = slot.parse::<U256>() {
+                    let word = state.storage(address, slot)?;
+                    sh_println!("{}: {}", format!("{slot:#x}").yellow(), B256::from(word).cyan())?;
+                    for var in variables.iter().filter(|var| var.slot == slot) {
+                        print_variable(var, word)?;
+                    }
+                } else {
+                    let Some(var) = variables.iter().find(|var| var.label == slot) else {
+                        if variables.is_empty() {
+                            eyre::bail!(
+                                "No storage layout found for {address}; state variables can only be \
+                                 read from contracts compiled in the session."
+                            );
+                        }
+                        eyre::bail!(
+                            "No state variable named `{slot}` in the storage layout of {address}."
+                        );
+                    };
+                    print_variable(var, state.storage(address, var.slot)?)?;
+                }
+            }
+            None if !variables.is_empty() => {
+                for var in &variables {
+                    print_variable(var, state.storage(address, var.slot)?)?;
+                }
+            }
+            None => {
+                let slots = state.changed_slots(address);
+                if slots.is_empty() {
+                    eyre::bail!("No storage slot of {address} was changed in the session.");
+                }
+                for slot in slots {
+                    let word = state.storage(address, slot)?;
+                    sh_println!("{}: {}", format!("{slot:#x}").yellow(), B256::from(word).cyan())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn show_diff(&mut self) -> Result<()> {
+        let Some(mut previous) = self.previous.clone() else {
+            eyre::bail!("No statement has been executed yet.");
+        };
+
+        // Run both sources with the same configuration and backend, so that the state only differs
+        // by the changes of the last statement.
+        let res = self.source_mut().execute().await?;
+        let source = self.source();
+        previous.config = source.config.clone();
+        let previous_res = previous.execute().await?;
+
+        let backend = source.config.backend.as_ref();
+        let before = SessionState::new(&previous_res, backend);
+        let after = SessionState::new(&res, backend);
+
+        let mut addresses =
+            before.changed_accounts().chain(after.changed_accounts()).collect::<Vec<_>>();
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        let mut changed = false;
+        for address in addresses {
+            let mut changes = Vec::new();
+
+            let (old, new) = (before.account(address)?, after.account(address)?);
+            if old.balance != new.balance {
+                changes.push(format!("balance: {} → {}", old.balance, new.balance));
+            }
+            if old.nonce != new.nonce {
+                changes.push(format!("nonce: {} → {}", old.nonce, new.nonce));
+            }
+
+            let variables = after.storage_variables(source, address)?;
+            let mut slots = before.changed_slots(address);
+            slots.extend(after.changed_slots(address));
+            slots.sort_unstable();
+            slots.dedup();
+            for slot in slots {
+                let (old, new) = (before.storage(address, slot)?, after.storage(address, slot)?);
+                if old == new {
+                    continue;
+                }
+                changes.push(format!("{slot:#x}: {} → {}", B256::from(old), B256::from(new)));
+                for var in variables.iter().filter(|var| var.slot == slot) {
+                    let (old, new) = (var.format(old), var.format(new));
+                    if old != new {
+                        changes.push(format!("  {} ({}): {old} → {new}", var.label, var.ty));
+                    }
+                }
+            }
+
+            if !changes.is_empty() {
+                changed = true;
+                sh_println!("{}", address.cyan())?;
+                for change in changes {
+                    sh_println!("  {change}")?;
+                }
+            }
+        }
+
+        if !changed {
+            sh_println!("No state changes.")?;
+        }
+        Ok(())
+    }
 }
 
 /// Preprocesses addresses to ensure they are correctly checksummed and returns whether the input
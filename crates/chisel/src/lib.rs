@@ -24,9 +24,13 @@
 
 pub mod source;
 
+pub mod state;
+
 mod solidity_helper;
 pub use solidity_helper::SolidityHelper;
 
 pub mod prelude {
-    pub use crate::{cmd::*, dispatcher::*, runner::*, session::*, solidity_helper::*, source::*};
+    pub use crate::{
+        cmd::*, dispatcher::*, runner::*, session::*, solidity_helper::*, source::*, state::*,
+    };
 }
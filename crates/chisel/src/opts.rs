@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueHint};
 use foundry_cli::opts::{BuildOpts, EvmArgs, GlobalArgs};
 use foundry_common::version::{LONG_VERSION, SHORT_VERSION};
 use std::path::PathBuf;
@@ -23,6 +23,13 @@ pub struct Chisel {
     #[arg(long, help_heading = "REPL options")]
     pub prelude: Option<PathBuf>,
 
+    /// Path to a script to execute before starting the session.
+    ///
+    /// The session starts from the end state of the script's `run()` function, with the contracts
+    /// labeled or deployed by the script bound to variables.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "PATH", help_heading = "REPL options")]
+    pub from_script: Option<PathBuf>,
+
     /// Disable the default `Vm` import.
     #[arg(long, help_heading = "REPL options", long_help = format!(
         "Disable the default `Vm` import.\n\n\
@@ -6,6 +6,7 @@
 use alloy_primitives::{Address, Bytes, Log, U256, map::AddressHashMap};
 use eyre::Result;
 use foundry_evm::{
+    core::utils::StateChangeset,
     executors::{DeployResult, Executor, RawCallResult},
     traces::{TraceKind, Traces},
 };
@@ -48,6 +49,8 @@ pub struct ChiselResult {
     pub address: Address,
     /// EVM State at the final instruction of the `run()` function
     pub state: Option<(Vec<U256>, Vec<u8>)>,
+    /// State changes of the deployment of the REPL contract and of the `run()` function
+    pub state_changeset: StateChangeset,
 }
 
 /// ChiselRunner implementation
@@ -77,7 +80,7 @@ pub fn run(&mut self, bytecode: Bytes) -> Result<ChiselResult> {
 
         // Deploy an instance of the REPL contract
         // We don't care about deployment traces / logs here
-        let DeployResult { address, .. } = self
+        let DeployResult { address, raw } = self
             .executor
             .deploy(self.sender, bytecode, U256::ZERO, None)
             .map_err(|err| eyre::eyre!("Failed to deploy REPL contract:\n{}", err))?;
@@ -94,9 +97,28 @@ pub fn run(&mut self, bytecode: Bytes) -> Result<ChiselResult> {
         let res = self.executor.transact_raw(self.sender, address, calldata.into(), U256::ZERO)?;
 
         let RawCallResult {
-            result, reverted, logs, traces, labels, chisel_state, gas_used, ..
+            result,
+            reverted,
+            logs,
+            traces,
+            labels,
+            chisel_state,
+            gas_used,
+            state_changeset: run_changeset,
+            ..
         } = res;
 
+        // Apply the state changes of the call on top of the ones of the deployment.
+        let mut state_changeset = raw.state_changeset;
+        for (address, account) in run_changeset {
+            if let Some(existing) = state_changeset.get_mut(&address) {
+                existing.info = account.info;
+                existing.storage.extend(account.storage);
+            } else {
+                state_changeset.insert(address, account);
+            }
+        }
+
         Ok(ChiselResult {
             returned: result,
             success: !reverted,
@@ -106,6 +128,7 @@ pub fn run(&mut self, bytecode: Bytes) -> Result<ChiselResult> {
             labeled_addresses: labels,
             address,
             state: chisel_state,
+            state_changeset,
         })
     }
 }
@@ -6,10 +6,10 @@
 
 use eyre::Result;
 use forge_doc::solang_ext::{CodeLocationExt, SafeUnwrap};
-use foundry_common::fs;
+use foundry_common::{ContractsByArtifactBuilder, fs};
 use foundry_compilers::{
     Artifact, ProjectCompileOutput,
-    artifacts::{ConfigurableContractArtifact, Source, Sources},
+    artifacts::{ConfigurableContractArtifact, Source, Sources, StorageLayout},
     project::ProjectCompiler,
     solc::Solc,
 };
@@ -19,7 +19,7 @@
 use serde::{Deserialize, Serialize};
 use solang_parser::pt;
 use solar::interface::diagnostics::EmittedDiagnostics;
-use std::{cell::OnceCell, collections::HashMap, fmt, path::PathBuf};
+use std::{cell::OnceCell, collections::HashMap, fmt, path::PathBuf, sync::Arc};
 use walkdir::WalkDir;
 
 /// The minimum Solidity version of the `Vm` interface.
@@ -88,6 +88,24 @@ impl GeneratedOutputRef<'_> {
     pub fn repl_contract(&self) -> Option<&ConfigurableContractArtifact> {
         self.output.find_first("REPL")
     }
+
+    /// Returns the storage layout of the contract compiled in the session whose runtime code is
+    /// `code`, if any.
+    ///
+    /// Immutables and metadata are ignored when matching the code.
+    pub fn storage_layout(&self, code: &[u8]) -> Option<Arc<StorageLayout>> {
+        let contracts = ContractsByArtifactBuilder::new(
+            self.output.artifact_ids().map(|(id, artifact)| (id, artifact.get_contract_bytecode())),
+        )
+        .with_storage_layouts(
+            self.output
+                .artifact_ids()
+                .filter_map(|(id, artifact)| Some((id, artifact.storage_layout.clone()?))),
+        )
+        .build();
+        let (_, contract) = contracts.find_by_deployed_code_exact(code)?;
+        contract.storage_layout.clone()
+    }
 }
 
 impl std::ops::Deref for GeneratedOutput {
@@ -611,6 +629,15 @@ fn compile(&self) -> Result<ProjectCompileOutput> {
 
         let mut project = self.config.foundry_config.ephemeral_project()?;
         self.config.foundry_config.disable_optimizations(&mut project, self.config.ir_minimum);
+        // Request the storage layouts to decode the storage of the session contracts.
+        project.artifacts.additional_values.storage_layout = true;
+        project.update_output_selection(|selection| {
+            selection.0.values_mut().for_each(|contract_selection| {
+                contract_selection
+                    .values_mut()
+                    .for_each(|selection| selection.push("storageLayout".to_string()))
+            });
+        });
         let mut output = ProjectCompiler::with_sources(&project, sources)?.compile()?;
 
         if output.has_compiler_errors() {
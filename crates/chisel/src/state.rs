@@ -0,0 +1,190 @@
+//! Session State
+//!
+//! This module contains helpers to read and decode the EVM state resulting from a run of the REPL
+//! contract.
+
+use crate::prelude::{ChiselResult, SessionSource};
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{Address, B256, Bytes, U256};
+use eyre::Result;
+use foundry_common::{
+    fmt::format_token,
+    slot_identifier::{SlotInfo, StorageTypeInfo},
+};
+use foundry_compilers::artifacts::StorageLayout;
+use foundry_evm::backend::Backend;
+use revm::{DatabaseRef, state::AccountInfo};
+
+/// The state after a run of the REPL contract: the state changes of the run on top of the session
+/// backend.
+#[derive(Clone, Copy, Debug)]
+pub struct SessionState<'a> {
+    result: &'a ChiselResult,
+    backend: Option<&'a Backend>,
+}
+
+impl<'a> SessionState<'a> {
+    /// Creates a new [SessionState] from the result of a run and the backend it was executed on.
+    pub fn new(result: &'a ChiselResult, backend: Option<&'a Backend>) -> Self {
+        Self { result, backend }
+    }
+
+    /// Returns the value of a storage slot of an account.
+    pub fn storage(&self, address: Address, slot: U256) -> Result<U256> {
+        if let Some(value) =
+            self.result.state_changeset.get(&address).and_then(|account| account.storage.get(&slot))
+        {
+            return Ok(value.present_value);
+        }
+        match self.backend {
+            Some(backend) => Ok(backend.storage_ref(address, slot)?),
+            None => Ok(U256::ZERO),
+        }
+    }
+
+    /// Returns the storage slots of an account changed by the run.
+    pub fn changed_slots(&self, address: Address) -> Vec<U256> {
+        let mut slots = self
+            .result
+            .state_changeset
+            .get(&address)
+            .map(|account| account.storage.keys().copied().collect::<Vec<_>>())
+            .unwrap_or_default();
+        slots.sort_unstable();
+        slots
+    }
+
+    /// Returns the addresses of the accounts changed by the run.
+    pub fn changed_accounts(&self) -> impl Iterator<Item = Address> + '_ {
+        self.result.state_changeset.keys().copied()
+    }
+
+    /// Returns the info of an account.
+    pub fn account(&self, address: Address) -> Result<AccountInfo> {
+        if let Some(account) = self.result.state_changeset.get(&address) {
+            return Ok(account.info.clone());
+        }
+        match self.backend {
+            Some(backend) => Ok(backend.basic_ref(address)?.unwrap_or_default()),
+            None => Ok(AccountInfo::default()),
+        }
+    }
+
+    /// Returns the state variables of an account, if its runtime code is the one of a contract
+    /// compiled in the session.
+    pub fn storage_variables(
+        &self,
+        source: &SessionSource,
+        address: Address,
+    ) -> Result<Vec<StorageVariable>> {
+        let code = self.code(address)?;
+        if code.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(source
+            .build()?
+            .enter(|output| {
+                output.storage_layout(&code).map(|layout| StorageVariable::from_layout(&layout))
+            })
+            .unwrap_or_default())
+    }
+
+    /// Returns the runtime code of an account.
+    pub fn code(&self, address: Address) -> Result<Bytes> {
+        let info = self.account(address)?;
+        let code = match (info.code, self.backend) {
+            (Some(code), _) => code,
+            (None, Some(backend)) => backend.code_by_hash_ref(info.code_hash)?,
+            (None, None) => return Ok(Bytes::new()),
+        };
+        Ok(code.original_bytes())
+    }
+}
+
+/// A state variable, from the storage layout of a contract.
+#[derive(Debug)]
+pub struct StorageVariable {
+    /// The variable name.
+    pub label: String,
+    /// The variable type, as labeled in the storage layout.
+    pub ty: String,
+    /// The slot the variable starts at.
+    pub slot: U256,
+    /// The slot info decoding the variable, if its type can be parsed.
+    info: Option<SlotInfo>,
+}
+
+impl StorageVariable {
+    /// Returns the state variables of a storage layout.
+    pub fn from_layout(layout: &StorageLayout) -> Vec<Self> {
+        layout
+            .storage
+            .iter()
+            .filter_map(|storage| {
+                let ty = layout.types.get(&storage.storage_type)?;
+                let info = DynSolType::parse(&ty.label).ok().map(|dyn_sol_type| SlotInfo {
+                    label: storage.label.clone(),
+                    slot_type: StorageTypeInfo { label: ty.label.clone(), dyn_sol_type },
+                    offset: storage.offset,
+                    slot: storage.slot.clone(),
+                    members: None,
+                    decoded: None,
+                    keys: None,
+                });
+                Some(Self {
+                    label: storage.label.clone(),
+                    ty: ty.label.clone(),
+                    slot: storage.slot.parse().ok()?,
+                    info,
+                })
+            })
+            .collect()
+    }
+
+    /// Decodes the variable from the word of its slot, if it is a value type or a short string.
+    pub fn decode(&self, word: U256) -> Option<DynSolValue> {
+        self.info.as_ref()?.decode_packed(B256::from(word))
+    }
+
+    /// Formats the variable from the word of its slot, falling back to the raw word.
+    pub fn format(&self, word: U256) -> String {
+        match self.decode(word) {
+            Some(value) => format_token(&value),
+            None => format!("{word:#x}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variable(ty: &str, offset: usize) -> StorageVariable {
+        let layout = serde_json::from_value(serde_json::json!({
+            "storage": [{
+                "astId": 1,
+                "contract": "REPL",
+                "label": "x",
+                "offset": offset,
+                "slot": "0",
+                "type": "t"
+            }],
+            "types": {
+                "t": { "encoding": "inplace", "label": ty, "numberOfBytes": "32" }
+            }
+        }))
+        .unwrap();
+        StorageVariable::from_layout(&layout).pop().unwrap()
+    }
+
+    #[test]
+    fn decodes_packed_variables() {
+        // `uint8 a = 0xff; int16 b = -2; bool c = true; bytes2 d = 0x1234;` packed in slot 0.
+        let word = U256::from(0x1234_01_fffe_ffu64);
+        assert_eq!(variable("uint8", 0).format(word), "255");
+        assert_eq!(variable("int16", 1).format(word), "-2");
+        assert_eq!(variable("bool", 3).format(word), "true");
+        assert_eq!(variable("bytes2", 4).format(word), "0x1234");
+        assert_eq!(variable("mapping(address => uint256)", 0).format(word), "0x123401fffeff");
+    }
+}
@@ -17,6 +17,20 @@ fn $name() {
             return (); // Fix "go to definition" due to `tokio::test`.
         }
     };
+    (
+        $name:ident,
+        $flags:expr,init = $init:expr,setup = | $prj:ident | $setup:expr,
+        | $cmd:ident | $test:expr
+    ) => {
+        #[test]
+        #[allow(unused_mut)]
+        fn $name() {
+            let mut $cmd =
+                ChiselSession::new_with_setup(stringify!($name), $flags, $init, |$prj| $setup);
+            $test;
+            return (); // Fix "go to definition" due to `tokio::test`.
+        }
+    };
 }
 
 repl_test!(test_repl_help, |repl| {
@@ -133,6 +147,41 @@ fn $name() {
     repl.expect("Decimal: 255");
 });
 
+// Test that `--from-script` binds the contracts labeled and deployed by the script.
+repl_test!(
+    test_from_script_binds_contracts,
+    "--from-script script/Bind.s.sol eval my_counter.number()+counter.number()",
+    init = true,
+    setup = |prj| {
+        prj.add_raw_script(
+            "Bind.s.sol",
+            r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Script} from "forge-std/Script.sol";
+import {Counter} from "../src/Counter.sol";
+
+contract BindScript is Script {
+    function run() public {
+        Counter labeled = new Counter();
+        labeled.setNumber(40);
+        vm.label(address(labeled), "my counter");
+
+        Counter unlabeled = new Counter();
+        unlabeled.setNumber(2);
+    }
+}
+"#,
+        );
+    },
+    |repl| {
+        repl.expect("Bound my_counter = Counter(");
+        repl.expect("Bound counter = Counter(");
+        repl.expect("Decimal: 42");
+    }
+);
+
 // Issue #4938: Test memory/stack dumps with assembly.
 repl_test!(test_assembly_memory_dump, |repl| {
     let input = r#"
@@ -252,3 +301,17 @@ fn $name() {
         repl.expect(&format!("Hex: 0x{}e", "f".repeat(size_minus_1)));
     }
 });
+
+// Test watched expressions and state diffs.
+repl_test!(test_watch_and_diff, "", init = true, |repl| {
+    repl.sendln("address alice = address(0x1)");
+    repl.sendln("!watch alice.balance");
+    repl.expect("Decimal: 0");
+
+    repl.sendln("vm.deal(alice, 1 ether);");
+    repl.expect("Watches:");
+    repl.expect("Decimal: 1000000000000000000");
+
+    repl.sendln("!diff");
+    repl.expect("balance: 0 → 1000000000000000000");
+});
@@ -3,6 +3,8 @@
 use rexpect::{reader::Options, session::PtySession, spawn_with_options};
 
 const TIMEOUT_SECS: u64 = 3;
+/// Sessions with a setup may compile and run project sources before their first output.
+const SETUP_TIMEOUT_SECS: u64 = 60;
 const PROMPT: &str = "➜ ";
 
 /// Testing session for Chisel.
@@ -22,11 +24,32 @@ fn is_repl(args: &[String]) -> bool {
 #[allow(dead_code)]
 impl ChiselSession {
     pub fn new(name: &str, flags: &str, init: bool) -> Self {
+        Self::spawn(name, flags, init, TIMEOUT_SECS, |_| {})
+    }
+
+    /// Creates a new session, running `setup` on the project before spawning Chisel.
+    pub fn new_with_setup(
+        name: &str,
+        flags: &str,
+        init: bool,
+        setup: impl FnOnce(&TestProject),
+    ) -> Self {
+        Self::spawn(name, flags, init, SETUP_TIMEOUT_SECS, setup)
+    }
+
+    fn spawn(
+        name: &str,
+        flags: &str,
+        init: bool,
+        timeout_secs: u64,
+        setup: impl FnOnce(&TestProject),
+    ) -> Self {
         let project = foundry_test_utils::TestProject::new(name, PathStyle::Dapptools);
         if init {
             foundry_test_utils::util::initialize(project.root());
             project.initialize_default_contracts();
         }
+        setup(&project);
 
         let bin = env!("CARGO_BIN_EXE_chisel");
         let mut command = std::process::Command::new(bin);
@@ -46,7 +69,7 @@ pub fn new(name: &str, flags: &str, init: bool) -> Self {
         let session = spawn_with_options(
             command,
             Options {
-                timeout_ms: Some(TIMEOUT_SECS * 1000),
+                timeout_ms: Some(timeout_secs * 1000),
                 strip_ansi_escape_codes: false,
                 encoding: rexpect::Encoding::UTF8,
             },
@@ -12,6 +12,7 @@
 use regex::Regex;
 use serde::de::DeserializeOwned;
 use std::{
+    collections::{BTreeMap, BTreeSet},
     ffi::OsStr,
     path::{Path, PathBuf},
     process::{Command, Output, Stdio},
@@ -564,6 +565,50 @@ pub fn tag(self) -> Result<String> {
         self.cmd().arg("tag").get_stdout_lossy()
     }
 
+    /// Returns the lines added or modified since `rev` in the working tree, by file.
+    ///
+    /// Paths are relative to the root, and only files matching `pathspecs` are considered.
+    /// Untracked files that are not ignored are entirely changed.
+    pub fn changed_lines<I, S>(
+        self,
+        rev: &str,
+        pathspecs: I,
+    ) -> Result<BTreeMap<PathBuf, BTreeSet<u32>>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let pathspecs = pathspecs.into_iter().collect::<Vec<_>>();
+        let diff = self
+            .cmd()
+            .args([
+                "diff",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--no-prefix",
+                "--relative",
+            ])
+            .arg(rev)
+            .arg("--")
+            .args(&pathspecs)
+            .get_stdout_lossy()?;
+        let mut changed = parse_changed_lines(&diff);
+
+        let untracked = self
+            .cmd()
+            .args(["ls-files", "--others", "--exclude-standard", "--"])
+            .args(&pathspecs)
+            .get_stdout_lossy()?;
+        for path in untracked.lines().map(PathBuf::from) {
+            let lines = std::fs::read_to_string(self.root.join(&path))?.lines().count() as u32;
+            if lines > 0 {
+                changed.insert(path, (1..=lines).collect());
+            }
+        }
+        Ok(changed)
+    }
+
     /// Returns the tag the commit first appeared in.
     ///
     /// E.g Take rev = `abc1234`. This commit can be found in multiple releases (tags).
@@ -753,6 +798,36 @@ fn stderr(self) -> Stdio {
     }
 }
 
+/// Parses the added lines of each file out of a `git diff --unified=0 --no-prefix` output.
+fn parse_changed_lines(diff: &str) -> BTreeMap<PathBuf, BTreeSet<u32>> {
+    let mut changed = BTreeMap::<PathBuf, BTreeSet<u32>>::new();
+    let mut file = None;
+    // Whether we are in the header of a file diff, as added lines may also start with `+++`.
+    let mut in_header = false;
+    for line in diff.lines() {
+        if line.starts_with("diff ") {
+            in_header = true;
+        } else if in_header && let Some(path) = line.strip_prefix("+++ ") {
+            file = (path != "/dev/null").then(|| PathBuf::from(path));
+            in_header = false;
+        } else if let Some(hunk) = line.strip_prefix("@@ ")
+            && let Some(file) = &file
+        {
+            // `@@ -a[,b] +c[,d] @@`, where `d` defaults to 1.
+            let Some(added) = hunk.split_whitespace().find_map(|s| s.strip_prefix('+')) else {
+                continue;
+            };
+            let (start, count) = added.split_once(',').unwrap_or((added, "1"));
+            let (Ok(start), Ok(count)) = (start.parse::<u32>(), count.parse::<u32>()) else {
+                continue;
+            };
+            changed.entry(file.clone()).or_default().extend(start..start + count);
+        }
+    }
+    changed.retain(|_, lines| !lines.is_empty());
+    changed
+}
+
 /// Deserialized `git submodule status lib/dep` output.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct Submodule {
@@ -829,6 +904,38 @@ mod tests {
     use std::{env, fs::File, io::Write};
     use tempfile::tempdir;
 
+    #[test]
+    fn parse_git_changed_lines() {
+        let diff = r#"diff --git src/A.sol src/A.sol
+index 1111111..2222222 100644
+--- src/A.sol
++++ src/A.sol
+@@ -3 +3 @@ contract A {
+-    uint a;
++    uint b;
+@@ -10,0 +11,3 @@ contract A {
++    function f() public {}
++
++    function g() public {}
+@@ -20,2 +23,0 @@ contract A {
+-    uint c;
+-    uint d;
+diff --git src/B.sol src/B.sol
+deleted file mode 100644
+index 3333333..0000000
+--- src/B.sol
++++ /dev/null
+@@ -1 +0,0 @@
+-contract B {}
+"#;
+        let changed = parse_changed_lines(diff);
+        assert_eq!(changed.len(), 1);
+        assert_eq!(
+            changed[Path::new("src/A.sol")].iter().copied().collect::<Vec<_>>(),
+            [3, 11, 12, 13]
+        );
+    }
+
     #[test]
     fn parse_submodule_status() {
         let s = "+8829465a08cac423dcf59852f21e448449c1a1a8 lib/openzeppelin-contracts (v4.8.0-791-g8829465a)";
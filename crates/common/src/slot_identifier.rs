@@ -123,6 +123,15 @@ pub fn decode(&self, value: B256) -> Option<DynSolValue> {
         }
     }
 
+    /// Decodes the value of the variable packed at [`offset`](Self::offset) within a storage
+    /// word.
+    ///
+    /// Unlike [`SlotInfo::decode`], which expects the value to fill the word, this decodes
+    /// variables sharing their slot with others.
+    pub fn decode_packed(&self, word: B256) -> Option<DynSolValue> {
+        self.decode(packed_value(&self.slot_type.dyn_sol_type, self.offset as usize, word))
+    }
+
     /// Slot is of type [`DynSolType::Bytes`] or [`DynSolType::String`]
     pub fn is_bytes_or_string(&self) -> bool {
         matches!(self.slot_type.dyn_sol_type, DynSolType::Bytes | DynSolType::String)
@@ -270,37 +279,15 @@ pub fn decode_values(&mut self, previous_value: B256, new_value: B256) {
         // If this is a struct with members, decode each member individually
         if let Some(members) = &mut self.members {
             for member in members.iter_mut() {
+                let ty = &member.slot_type.dyn_sol_type;
                 let offset = member.offset as usize;
-                let size = match &member.slot_type.dyn_sol_type {
-                    DynSolType::Uint(bits) | DynSolType::Int(bits) => bits / 8,
-                    DynSolType::Address => 20,
-                    DynSolType::Bool => 1,
-                    DynSolType::FixedBytes(size) => *size,
-                    _ => 32, // Default to full word
-                };
-
-                // Extract and decode member values
-                let mut prev_bytes = [0u8; 32];
-                let mut new_bytes = [0u8; 32];
-
-                if offset + size <= 32 {
-                    // In Solidity storage, values are right-aligned
-                    // For offset 0, we want the rightmost bytes
-                    // For offset 16 (for a uint128), we want bytes 0-16
-                    // For packed storage: offset 0 is at the rightmost position
-                    // offset 0, size 16 -> read bytes 16-32 (rightmost)
-                    // offset 16, size 16 -> read bytes 0-16 (leftmost)
-                    let byte_start = 32 - offset - size;
-                    prev_bytes[32 - size..]
-                        .copy_from_slice(&previous_value.0[byte_start..byte_start + size]);
-                    new_bytes[32 - size..]
-                        .copy_from_slice(&new_value.0[byte_start..byte_start + size]);
-                }
+                let prev_bytes = packed_value(ty, offset, previous_value);
+                let new_bytes = packed_value(ty, offset, new_value);
 
                 // Decode the member values
                 if let (Ok(prev_val), Ok(new_val)) = (
-                    member.slot_type.dyn_sol_type.abi_decode(&prev_bytes),
-                    member.slot_type.dyn_sol_type.abi_decode(&new_bytes),
+                    member.slot_type.dyn_sol_type.abi_decode(prev_bytes.as_slice()),
+                    member.slot_type.dyn_sol_type.abi_decode(new_bytes.as_slice()),
                 ) {
                     member.decoded =
                         Some(DecodedSlotValues { previous_value: prev_val, new_value: new_val });
@@ -318,6 +305,40 @@ pub fn decode_values(&mut self, previous_value: B256, new_value: B256) {
     }
 }
 
+/// Extracts the value of a variable of type `ty` packed at `offset` within a storage word, as an
+/// ABI-encoded word.
+///
+/// In storage, values are right-aligned from their offset: offset 0 is the rightmost byte.
+fn packed_value(ty: &DynSolType, offset: usize, word: B256) -> B256 {
+    let size = match ty {
+        DynSolType::Uint(bits) | DynSolType::Int(bits) => bits / 8,
+        DynSolType::Address => 20,
+        DynSolType::Bool => 1,
+        DynSolType::FixedBytes(size) => *size,
+        _ => 32,
+    };
+    if size >= 32 || offset + size > 32 {
+        return word;
+    }
+
+    let start = 32 - offset - size;
+    let bytes = &word.0[start..start + size];
+    let mut value = B256::ZERO;
+    match ty {
+        // Fixed bytes are left-aligned in ABI encoding.
+        DynSolType::FixedBytes(_) => value.0[..size].copy_from_slice(bytes),
+        // Signed integers are sign-extended.
+        DynSolType::Int(_) => {
+            if bytes[0] & 0x80 != 0 {
+                value = B256::repeat_byte(0xff);
+            }
+            value.0[32 - size..].copy_from_slice(bytes);
+        }
+        _ => value.0[32 - size..].copy_from_slice(bytes),
+    }
+    value
+}
+
 /// Custom serializer for StorageTypeInfo that only outputs the label
 fn serialize_slot_type<S>(info: &StorageTypeInfo, serializer: S) -> Result<S::Ok, S::Error>
 where
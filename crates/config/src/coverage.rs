@@ -0,0 +1,68 @@
+//! Configuration specific to the `forge coverage` command.
+
+use crate::filter::GlobMatcher;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Contains the config for `forge coverage`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageConfig {
+    /// Minimum coverage of the whole project, in percent.
+    #[serde(flatten)]
+    pub thresholds: CoverageThresholds,
+    /// Globs of files that are not subject to any threshold.
+    pub exclude: Vec<GlobMatcher>,
+    /// Minimum coverage of individual files matching a glob, in percent.
+    pub overrides: Vec<CoverageOverride>,
+}
+
+impl CoverageConfig {
+    /// Returns `true` if at least one threshold is configured.
+    pub fn has_thresholds(&self) -> bool {
+        !self.thresholds.is_empty() || self.overrides.iter().any(|o| !o.thresholds.is_empty())
+    }
+
+    /// Returns `true` if the file at `path` is excluded from the thresholds.
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        self.exclude.iter().any(|glob| glob.is_match(path))
+    }
+
+    /// Returns the thresholds that apply to the individual file at `path`.
+    pub fn overrides_for<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> impl Iterator<Item = &'a CoverageOverride> + 'a {
+        self.overrides.iter().filter(move |o| o.path.is_match(path))
+    }
+}
+
+/// Minimum coverage percentages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageThresholds {
+    /// Minimum line coverage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_lines: Option<f64>,
+    /// Minimum branch coverage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_branches: Option<f64>,
+    /// Minimum function coverage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_functions: Option<f64>,
+}
+
+impl CoverageThresholds {
+    /// Returns `true` if no threshold is set.
+    pub fn is_empty(&self) -> bool {
+        self.min_lines.is_none() && self.min_branches.is_none() && self.min_functions.is_none()
+    }
+}
+
+/// Thresholds for the files matching a glob.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CoverageOverride {
+    /// Glob of the files the thresholds apply to.
+    pub path: GlobMatcher,
+    /// Minimum coverage of each matching file.
+    #[serde(flatten)]
+    pub thresholds: CoverageThresholds,
+}
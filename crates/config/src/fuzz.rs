@@ -116,6 +116,10 @@ pub struct FuzzCorpusConfig {
     pub corpus_min_size: usize,
     /// Whether to collect and display edge coverage metrics.
     pub show_edge_coverage: bool,
+    /// Whether to remove the persisted corpus entries that don't contribute edge coverage when
+    /// loading the corpus. Set by `forge fuzz minimize`.
+    #[serde(skip)]
+    pub corpus_minimize: bool,
 }
 
 impl FuzzCorpusConfig {
@@ -144,6 +148,7 @@ fn default() -> Self {
             corpus_min_mutations: 5,
             corpus_min_size: 0,
             show_edge_coverage: false,
+            corpus_minimize: false,
         }
     }
 }
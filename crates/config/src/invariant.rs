@@ -41,6 +41,9 @@ pub struct InvariantConfig {
     pub max_time_delay: Option<u32>,
     /// Maximum number of blocks elapsed between generated txs.
     pub max_block_delay: Option<u32>,
+    /// Number of parallel workers running each invariant test. Workers periodically sync their
+    /// fuzz dictionary and corpus. Set to 0 to use all available cores.
+    pub threads: u32,
 }
 
 impl Default for InvariantConfig {
@@ -61,6 +64,7 @@ fn default() -> Self {
             show_solidity: false,
             max_time_delay: None,
             max_block_delay: None,
+            threads: 1,
         }
     }
 }
@@ -75,7 +75,7 @@
 pub use fmt::FormatterConfig;
 
 pub mod lint;
-pub use lint::{LinterConfig, Severity as LintSeverity};
+pub use lint::{CustomLintPattern, CustomLintRule, LinterConfig, Severity as LintSeverity};
 
 pub mod fs_permissions;
 pub use fs_permissions::FsPermissions;
@@ -88,6 +88,9 @@
 pub mod doc;
 pub use doc::DocConfig;
 
+pub mod coverage;
+pub use coverage::{CoverageConfig, CoverageOverride, CoverageThresholds};
+
 pub mod filter;
 pub use filter::SkipBuildFilters;
 
@@ -481,6 +484,8 @@ pub struct Config {
     pub lint: LinterConfig,
     /// Configuration for `forge doc`
     pub doc: DocConfig,
+    /// Configuration for `forge coverage`
+    pub coverage: CoverageConfig,
     /// Configuration for `forge bind-json`
     pub bind_json: BindJsonConfig,
     /// Configures the permissions of cheat codes that touch the file system.
@@ -692,6 +697,7 @@ impl Config {
         "fmt",
         "lint",
         "doc",
+        "coverage",
         "fuzz",
         "invariant",
         "labels",
@@ -2568,6 +2574,7 @@ fn default() -> Self {
             fmt: Default::default(),
             lint: Default::default(),
             doc: Default::default(),
+            coverage: Default::default(),
             bind_json: Default::default(),
             labels: Default::default(),
             unchecked_cheatcode_artifacts: false,
@@ -4696,6 +4703,120 @@ fn test_lint_config() {
         });
     }
 
+    #[test]
+    fn test_lint_custom_rules() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [[lint.custom]]
+                id = "no-delegatecall"
+                severity = "high"
+                description = "delegatecall is forbidden"
+                kind = "forbidden-call"
+                functions = ["*.delegatecall"]
+                "#,
+            )?;
+            jail.create_dir("lints")?;
+            jail.create_file(
+                "lints/docs.toml",
+                r#"
+                [[rule]]
+                id = "external-natspec"
+                severity = "info"
+                description = "external functions must be documented"
+                kind = "require-natspec"
+
+                [[rule]]
+                id = "no-old-pragma"
+                severity = "med"
+                description = "compiler version is not allowed"
+                kind = "forbidden-pragma"
+                versions = ["0.8.19"]
+                "#,
+            )?;
+
+            let loaded = Config::load().unwrap();
+            let rules = loaded.lint.load_custom_rules(&loaded.root).unwrap();
+            assert_eq!(
+                rules,
+                vec![
+                    CustomLintRule {
+                        id: "no-delegatecall".into(),
+                        severity: LintSeverity::High,
+                        description: "delegatecall is forbidden".into(),
+                        help: None,
+                        pattern: CustomLintPattern::ForbiddenCall {
+                            functions: vec!["*.delegatecall".into()]
+                        },
+                    },
+                    CustomLintRule {
+                        id: "external-natspec".into(),
+                        severity: LintSeverity::Info,
+                        description: "external functions must be documented".into(),
+                        help: None,
+                        pattern: CustomLintPattern::RequireNatspec {
+                            visibility: vec!["external".into()],
+                            tags: vec!["notice".into()],
+                        },
+                    },
+                    CustomLintRule {
+                        id: "no-old-pragma".into(),
+                        severity: LintSeverity::Med,
+                        description: "compiler version is not allowed".into(),
+                        help: None,
+                        pattern: CustomLintPattern::ForbiddenPragma {
+                            versions: vec![semver::Version::new(0, 8, 19)]
+                        },
+                    },
+                ]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_coverage_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [coverage]
+                min_lines = 90
+                min_branches = 75.5
+                exclude = ["src/mocks/**"]
+
+                [[coverage.overrides]]
+                path = "src/core/**"
+                min_lines = 100
+                "#,
+            )?;
+
+            let loaded = Config::load().unwrap();
+            let coverage = loaded.coverage;
+            assert_eq!(
+                coverage.thresholds,
+                CoverageThresholds {
+                    min_lines: Some(90.),
+                    min_branches: Some(75.5),
+                    min_functions: None
+                }
+            );
+            assert!(coverage.has_thresholds());
+            assert!(coverage.is_excluded(Path::new("src/mocks/Token.sol")));
+            assert!(!coverage.is_excluded(Path::new("src/core/Vault.sol")));
+
+            let overrides =
+                coverage.overrides_for(Path::new("src/core/Vault.sol")).collect::<Vec<_>>();
+            assert_eq!(overrides.len(), 1);
+            assert_eq!(overrides[0].thresholds.min_lines, Some(100.));
+            assert_eq!(coverage.overrides_for(Path::new("src/Other.sol")).count(), 0);
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_invariant_config() {
         figment::Jail::expect_with(|jail| {
@@ -2,11 +2,17 @@
 
 use clap::ValueEnum;
 use core::fmt;
+use eyre::WrapErr;
+use semver::Version;
 use serde::{Deserialize, Deserializer, Serialize};
 use solar::interface::diagnostics::Level;
-use std::str::FromStr;
+use std::{path::Path, str::FromStr};
 use yansi::Paint;
 
+/// Directory, relative to the project root, from which additional user-defined lint rules are
+/// loaded.
+pub const CUSTOM_LINTS_DIR: &str = "lints";
+
 /// Contains the config and rule set.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LinterConfig {
@@ -31,6 +37,12 @@ pub struct LinterConfig {
     /// Default's to ["ERC", "URI"] to allow common names like `rescueERC20`, `ERC721TokenReceiver`
     /// or `tokenURI`.
     pub mixed_case_exceptions: Vec<String>,
+
+    /// User-defined lint rules.
+    ///
+    /// Rules declared in the `*.toml` files of the [`CUSTOM_LINTS_DIR`] directory are appended to
+    /// these, see [`LinterConfig::load_custom_rules`].
+    pub custom: Vec<CustomLintRule>,
 }
 
 impl Default for LinterConfig {
@@ -41,10 +53,119 @@ fn default() -> Self {
             exclude_lints: Vec::new(),
             ignore: Vec::new(),
             mixed_case_exceptions: vec!["ERC".to_string(), "URI".to_string()],
+            custom: Vec::new(),
+        }
+    }
+}
+
+impl LinterConfig {
+    /// Returns all the user-defined lint rules of the project.
+    ///
+    /// These are the rules declared under `[[lint.custom]]`, followed by the `[[rule]]` entries of
+    /// every `*.toml` file in the `lints/` directory under `root`, in file name order.
+    pub fn load_custom_rules(&self, root: &Path) -> eyre::Result<Vec<CustomLintRule>> {
+        let mut rules = self.custom.clone();
+
+        let dir = root.join(CUSTOM_LINTS_DIR);
+        if !dir.is_dir() {
+            return Ok(rules);
         }
+
+        let mut files = std::fs::read_dir(&dir)
+            .wrap_err_with(|| format!("failed to read {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+            .collect::<Vec<_>>();
+        files.sort();
+
+        for file in files {
+            let content = std::fs::read_to_string(&file)
+                .wrap_err_with(|| format!("failed to read {}", file.display()))?;
+            let parsed: CustomLintRules = toml::from_str(&content)
+                .wrap_err_with(|| format!("failed to parse lint rules in {}", file.display()))?;
+            rules.extend(parsed.rule);
+        }
+
+        Ok(rules)
     }
 }
 
+/// Contents of a lint rules file in the [`CUSTOM_LINTS_DIR`] directory.
+#[derive(Deserialize)]
+struct CustomLintRules {
+    #[serde(default)]
+    rule: Vec<CustomLintRule>,
+}
+
+/// A user-defined lint rule, reported like the built-in lints.
+///
+/// ```toml
+/// [[lint.custom]]
+/// id = "no-delegatecall"
+/// severity = "high"
+/// description = "delegatecall is forbidden outside of the proxy contracts"
+/// kind = "forbidden-call"
+/// functions = ["*.delegatecall"]
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomLintRule {
+    /// Unique ID of the rule (e.g. "no-delegatecall").
+    ///
+    /// Can be used like the ID of any built-in lint: in `exclude_lints`, `--only-lint` and inline
+    /// `forge-lint` comments.
+    pub id: String,
+
+    /// Severity of the findings.
+    pub severity: Severity,
+
+    /// Short description of the rule, displayed with every finding.
+    pub description: String,
+
+    /// Help message or link displayed with every finding.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+
+    /// What the rule matches.
+    #[serde(flatten)]
+    pub pattern: CustomLintPattern,
+}
+
+/// The pattern matched by a [`CustomLintRule`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum CustomLintPattern {
+    /// Forbids calls to the given functions.
+    ///
+    /// Each entry is a `.`-separated path matched against the end of the callee, where `*`
+    /// matches any segment: `selfdestruct`, `abi.encodePacked`, `*.delegatecall`.
+    ForbiddenCall { functions: Vec<String> },
+
+    /// Forbids imports whose path matches any of the given globs.
+    ForbiddenImport { paths: Vec<String> },
+
+    /// Forbids `pragma solidity` directives that allow any of the given compiler versions.
+    ForbiddenPragma { versions: Vec<Version> },
+
+    /// Requires NatSpec tags on the functions with the given visibilities.
+    ///
+    /// `param` and `return` tags are required once per named parameter and return value.
+    /// Functions documented with `@inheritdoc` are not checked.
+    RequireNatspec {
+        #[serde(default = "default_natspec_visibility")]
+        visibility: Vec<String>,
+        #[serde(default = "default_natspec_tags")]
+        tags: Vec<String>,
+    },
+}
+
+fn default_natspec_visibility() -> Vec<String> {
+    vec!["external".to_string()]
+}
+
+fn default_natspec_tags() -> Vec<String> {
+    vec!["notice".to_string()]
+}
+
 /// Severity of a lint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
 pub enum Severity {
@@ -3,8 +3,10 @@
 use crate::{DebugNode, Debugger, node::flatten_call_trace};
 use alloy_primitives::{Address, map::AddressHashMap};
 use foundry_common::get_contract_name;
+use foundry_compilers::artifacts::StorageLayout;
 use foundry_evm_core::Breakpoints;
 use foundry_evm_traces::{CallTraceArena, CallTraceDecoder, Traces, debug::ContractSources};
+use std::{collections::HashMap, sync::Arc};
 
 /// Debugger builder.
 #[derive(Debug, Default)]
@@ -16,6 +18,8 @@ pub struct DebuggerBuilder {
     identified_contracts: AddressHashMap<String>,
     /// Map of source files.
     sources: ContractSources,
+    /// Storage layouts of the contracts, by contract name.
+    storage_layouts: HashMap<String, Arc<StorageLayout>>,
     /// Map of the debugger breakpoints.
     breakpoints: Breakpoints,
 }
@@ -76,6 +80,16 @@ pub fn sources(mut self, sources: ContractSources) -> Self {
         self
     }
 
+    /// Extends the storage layouts, by contract name.
+    #[inline]
+    pub fn storage_layouts(
+        mut self,
+        storage_layouts: impl IntoIterator<Item = (String, Arc<StorageLayout>)>,
+    ) -> Self {
+        self.storage_layouts.extend(storage_layouts);
+        self
+    }
+
     /// Sets the breakpoints for the debugger.
     #[inline]
     pub fn breakpoints(mut self, breakpoints: Breakpoints) -> Self {
@@ -86,7 +100,8 @@ pub fn breakpoints(mut self, breakpoints: Breakpoints) -> Self {
     /// Builds the debugger.
     #[inline]
     pub fn build(self) -> Debugger {
-        let Self { debug_arena, identified_contracts, sources, breakpoints } = self;
-        Debugger::new(debug_arena, identified_contracts, sources, breakpoints)
+        let Self { debug_arena, identified_contracts, sources, storage_layouts, breakpoints } =
+            self;
+        Debugger::new(debug_arena, identified_contracts, sources, storage_layouts, breakpoints)
     }
 }
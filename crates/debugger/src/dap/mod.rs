@@ -0,0 +1,145 @@
+//! Debug Adapter Protocol server.
+//!
+//! Serves the recorded execution to any DAP-capable editor. See
+//! <https://microsoft.github.io/debug-adapter-protocol/specification>.
+
+use crate::debugger::DebuggerContext;
+use eyre::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    fmt,
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener},
+    path::Path,
+    str::FromStr,
+};
+
+mod session;
+use session::Session;
+
+/// Transport of the Debug Adapter Protocol messages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DapTransport {
+    /// Standard input and output.
+    #[default]
+    Stdio,
+    /// A TCP socket, listening for a single connection at the given address.
+    Tcp(SocketAddr),
+}
+
+impl FromStr for DapTransport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "stdio" {
+            return Ok(Self::Stdio);
+        }
+        s.parse()
+            .map(Self::Tcp)
+            .map_err(|_| format!("invalid DAP transport `{s}`, expected `stdio` or an address"))
+    }
+}
+
+impl fmt::Display for DapTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdio => f.write_str("stdio"),
+            Self::Tcp(addr) => addr.fmt(f),
+        }
+    }
+}
+
+/// Serves the Debug Adapter Protocol over the given transport until the client disconnects.
+pub(crate) fn serve(context: &DebuggerContext, transport: DapTransport, root: &Path) -> Result<()> {
+    match transport {
+        DapTransport::Stdio => {
+            let stdin = std::io::stdin();
+            Session::new(context, root, stdin.lock(), std::io::stdout().lock()).run()
+        }
+        DapTransport::Tcp(addr) => {
+            let listener = TcpListener::bind(addr)
+                .wrap_err_with(|| format!("failed to listen for DAP connections on {addr}"))?;
+            sh_eprintln!("Listening for DAP connections on {}", listener.local_addr()?)?;
+            let (stream, _) = listener.accept()?;
+            let reader = BufReader::new(stream.try_clone()?);
+            Session::new(context, root, reader, stream).run()
+        }
+    }
+}
+
+/// A request sent by the client.
+#[derive(Debug, Deserialize)]
+struct Request {
+    seq: i64,
+    command: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+/// Reads the next message, returning `None` at the end of the stream.
+fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Request>> {
+    let mut content_length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            content_length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| eyre::eyre!("missing Content-Length"))?;
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Writes a message with its header.
+fn write_message<W: Write>(writer: &mut W, message: &Value) -> Result<()> {
+    let content = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{content}", content.len())?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_transport() {
+        assert_eq!("stdio".parse::<DapTransport>(), Ok(DapTransport::Stdio));
+        assert_eq!(
+            "127.0.0.1:4711".parse::<DapTransport>(),
+            Ok(DapTransport::Tcp("127.0.0.1:4711".parse().unwrap()))
+        );
+        assert!("localhost".parse::<DapTransport>().is_err());
+    }
+
+    #[test]
+    fn message_roundtrip() {
+        let mut buf = Vec::new();
+        let message = json!({ "seq": 1, "type": "request", "command": "initialize" });
+        write_message(&mut buf, &message).unwrap();
+        write_message(&mut buf, &message).unwrap();
+
+        let mut reader = buf.as_slice();
+        for _ in 0..2 {
+            let request = read_message(&mut reader).unwrap().unwrap();
+            assert_eq!(request.seq, 1);
+            assert_eq!(request.command, "initialize");
+            assert!(request.arguments.is_null());
+        }
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+}
@@ -0,0 +1,397 @@
+//! Debug Adapter Protocol session over the recorded execution.
+
+use super::{Request, read_message, write_message};
+use crate::{
+    debugger::DebuggerContext,
+    source::{Position, Statement},
+};
+use alloy_primitives::hex;
+use eyre::Result;
+use serde_json::{Value, json};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+/// The single thread of the execution.
+const THREAD_ID: i64 = 1;
+
+/// The kinds of variables of a stack frame, encoded in variable references.
+const SCOPES: [&str; 4] = ["Locals", "Stack", "Memory", "Storage"];
+
+/// Why the execution stopped.
+enum StopReason {
+    Entry,
+    Step,
+    Breakpoint(Option<char>),
+}
+
+pub(super) struct Session<'a, R, W> {
+    context: &'a DebuggerContext,
+    root: &'a Path,
+    reader: R,
+    writer: W,
+    /// Sequence number of the next message sent.
+    seq: i64,
+    /// Position of the current step, `None` before the execution starts.
+    position: Option<Position>,
+    /// Line breakpoints, by source path relative to the root.
+    breakpoints: HashMap<PathBuf, BTreeSet<usize>>,
+    stop_on_entry: bool,
+    /// Call stack of the current step, indexed by stack frame ID.
+    frames: Vec<Position>,
+}
+
+impl<'a, R: BufRead, W: Write> Session<'a, R, W> {
+    pub(super) fn new(context: &'a DebuggerContext, root: &'a Path, reader: R, writer: W) -> Self {
+        Self {
+            context,
+            root,
+            reader,
+            writer,
+            seq: 1,
+            position: None,
+            breakpoints: HashMap::new(),
+            stop_on_entry: false,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Handles requests until the client disconnects.
+    pub(super) fn run(mut self) -> Result<()> {
+        while let Some(request) = read_message(&mut self.reader)? {
+            let disconnect = request.command == "disconnect";
+            match self.handle(&request) {
+                Ok(body) => self.respond(&request, Ok(body))?,
+                Err(e) => self.respond(&request, Err(e.to_string()))?,
+            }
+            self.after(&request)?;
+            if disconnect {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles a request, returning the body of the response.
+    fn handle(&mut self, request: &Request) -> Result<Value> {
+        let args = &request.arguments;
+        Ok(match request.command.as_str() {
+            "initialize" => json!({
+                "supportsConfigurationDoneRequest": true,
+                "supportsTerminateRequest": true,
+            }),
+            "launch" | "attach" => {
+                self.stop_on_entry = args["stopOnEntry"].as_bool().unwrap_or(false);
+                Value::Null
+            }
+            "setBreakpoints" => self.set_breakpoints(args),
+            "setExceptionBreakpoints" => json!({ "breakpoints": [] }),
+            "threads" => json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+            "stackTrace" => self.stack_trace(),
+            "scopes" => self.scopes(args),
+            "variables" => self.variables(args),
+            "continue" => json!({ "allThreadsContinued": true }),
+            "configurationDone" | "next" | "stepIn" | "stepOut" | "pause" | "terminate"
+            | "disconnect" => Value::Null,
+            command => eyre::bail!("unsupported request `{command}`"),
+        })
+    }
+
+    /// Runs the execution commands, after responding to their request.
+    fn after(&mut self, request: &Request) -> Result<()> {
+        match request.command.as_str() {
+            "initialize" => self.event("initialized", Value::Null),
+            "configurationDone" => {
+                if self.stop_on_entry {
+                    let start = Position::default();
+                    let entry = if self.context.statement(start).is_some() {
+                        Some(start)
+                    } else {
+                        self.context.step_into(start)
+                    };
+                    self.stop(entry, StopReason::Entry)
+                } else {
+                    self.resume()
+                }
+            }
+            "continue" => self.resume(),
+            "next" => self.step(DebuggerContext::step_over),
+            "stepIn" => self.step(DebuggerContext::step_into),
+            "stepOut" => self.step(DebuggerContext::step_out),
+            "pause" => self.stop(self.position, StopReason::Step),
+            "terminate" => self.event("terminated", Value::Null),
+            _ => Ok(()),
+        }
+    }
+
+    fn step(&mut self, f: fn(&DebuggerContext, Position) -> Option<Position>) -> Result<()> {
+        let next = match self.position {
+            Some(pos) => f(self.context, pos),
+            None => Some(Position::default()),
+        };
+        self.stop(next, StopReason::Step)
+    }
+
+    /// Continues the execution until the next breakpoint.
+    fn resume(&mut self) -> Result<()> {
+        let mut pos = match self.position {
+            Some(pos) => self.context.next_position(pos),
+            None => Some(Position::default()),
+        };
+        while let Some(current) = pos {
+            if let Some(reason) = self.breakpoint_at(current) {
+                return self.stop(Some(current), reason);
+            }
+            pos = self.context.next_position(current);
+        }
+        self.stop(None, StopReason::Step)
+    }
+
+    /// Returns whether the step at given position hits a breakpoint: either a line breakpoint when
+    /// entering its statement, or a `vm.breakpoint` cheatcode.
+    fn breakpoint_at(&self, pos: Position) -> Option<StopReason> {
+        let node = &self.context.debug_arena[pos.node];
+        let pc = node.steps[pos.step].pc;
+        if let Some((c, _)) = self
+            .context
+            .breakpoints
+            .iter()
+            .find(|(_, (address, bp_pc))| *address == node.address && *bp_pc == pc)
+        {
+            return Some(StopReason::Breakpoint(Some(*c)));
+        }
+
+        let statement = self.context.statement(pos)?;
+        let lines = self.breakpoints.get(&statement.source.path)?;
+        if !lines.contains(&statement.line()) {
+            return None;
+        }
+        let entered = pos.step == 0
+            || self
+                .context
+                .statement(Position::new(pos.node, pos.step - 1))
+                .is_none_or(|prev| prev.range != statement.range);
+        entered.then_some(StopReason::Breakpoint(None))
+    }
+
+    /// Moves to the given position and notifies the client, or terminates the session at the end
+    /// of the execution.
+    fn stop(&mut self, pos: Option<Position>, reason: StopReason) -> Result<()> {
+        let Some(pos) = pos else {
+            self.position = Some(self.context.last_position());
+            return self.event("terminated", Value::Null);
+        };
+        self.position = Some(pos);
+        self.frames = self.context.call_stack(pos);
+
+        let (reason, description) = match reason {
+            StopReason::Entry => ("entry", None),
+            StopReason::Step => ("step", None),
+            StopReason::Breakpoint(Some(c)) => ("breakpoint", Some(format!("Breakpoint '{c}'"))),
+            StopReason::Breakpoint(None) => ("breakpoint", None),
+        };
+        self.event(
+            "stopped",
+            json!({
+                "reason": reason,
+                "description": description,
+                "threadId": THREAD_ID,
+                "allThreadsStopped": true,
+            }),
+        )
+    }
+
+    fn set_breakpoints(&mut self, args: &Value) -> Value {
+        let path = args["source"]["path"].as_str().map(|path| self.relative_path(path));
+        let lines = args["breakpoints"]
+            .as_array()
+            .map(|breakpoints| {
+                breakpoints.iter().filter_map(|bp| bp["line"].as_u64()).map(|line| line as usize)
+            })
+            .into_iter()
+            .flatten()
+            .collect::<BTreeSet<_>>();
+
+        // Breakpoints are verified if a statement starts on their line.
+        let statement_lines = match &path {
+            Some(path) => self.statement_lines(path),
+            None => BTreeSet::new(),
+        };
+        let breakpoints = lines
+            .iter()
+            .map(|line| json!({ "verified": statement_lines.contains(line), "line": line }))
+            .collect::<Vec<_>>();
+        if let Some(path) = path {
+            self.breakpoints.insert(path, lines);
+        }
+        json!({ "breakpoints": breakpoints })
+    }
+
+    /// Returns the lines of the statements executed in the given source.
+    fn statement_lines(&self, path: &Path) -> BTreeSet<usize> {
+        let mut lines = BTreeSet::new();
+        let mut pos = Some(Position::default());
+        while let Some(current) = pos {
+            if let Some(statement) = self.context.statement(current)
+                && statement.source.path == path
+            {
+                lines.insert(statement.line());
+            }
+            pos = self.context.next_position(current);
+        }
+        lines
+    }
+
+    fn stack_trace(&self) -> Value {
+        let frames = self
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(id, &pos)| {
+                let name = self.context.function_name(pos);
+                match self.context.statement(pos) {
+                    Some(statement) => json!({
+                        "id": id,
+                        "name": name,
+                        "source": self.source(&statement),
+                        "line": statement.line(),
+                        "column": column(&statement),
+                    }),
+                    None => json!({
+                        "id": id,
+                        "name": name,
+                        "line": 0,
+                        "column": 0,
+                        "presentationHint": "subtle",
+                    }),
+                }
+            })
+            .collect::<Vec<_>>();
+        json!({ "stackFrames": frames, "totalFrames": frames.len() })
+    }
+
+    fn scopes(&self, args: &Value) -> Value {
+        let frame = args["frameId"].as_u64().unwrap_or_default() as usize;
+        let scopes = SCOPES
+            .iter()
+            .enumerate()
+            .map(|(kind, name)| {
+                json!({
+                    "name": name,
+                    "variablesReference": frame * SCOPES.len() + kind + 1,
+                    "expensive": false,
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({ "scopes": scopes })
+    }
+
+    fn variables(&self, args: &Value) -> Value {
+        let reference = args["variablesReference"].as_u64().unwrap_or_default() as usize;
+        let Some(index) = reference.checked_sub(1) else { return json!({ "variables": [] }) };
+        let (frame, kind) = (index / SCOPES.len(), index % SCOPES.len());
+        let Some(&pos) = self.frames.get(frame) else { return json!({ "variables": [] }) };
+        let step = &self.context.debug_arena[pos.node].steps[pos.step];
+
+        let variables = match SCOPES[kind] {
+            "Locals" => self
+                .context
+                .function_variables(pos)
+                .map(|vars| {
+                    vars.parameters
+                        .into_iter()
+                        .chain(vars.locals)
+                        .filter(|var| !var.info.name.is_empty())
+                        .map(|var| {
+                            let value = var.value.unwrap_or_else(|| "<not on stack>".to_string());
+                            variable(&var.info.name, value, Some(&var.info.ty))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            "Stack" => step
+                .stack
+                .iter()
+                .flat_map(|stack| stack.iter().rev().enumerate())
+                .map(|(i, value)| variable(&i.to_string(), format!("{value:#x}"), None))
+                .collect(),
+            "Memory" => step
+                .memory
+                .iter()
+                .flat_map(|memory| memory.as_bytes().chunks(32).enumerate())
+                .map(|(i, word)| {
+                    variable(&format!("{:#06x}", i * 32), hex::encode_prefixed(word), None)
+                })
+                .collect(),
+            _ => self
+                .context
+                .frame_storage(pos)
+                .into_iter()
+                .map(|slot| {
+                    let ty = slot.info.as_ref().map(|info| info.slot_type.label.as_str());
+                    variable(&slot.name(), slot.format_value(slot.value), ty)
+                })
+                .collect(),
+        };
+        json!({ "variables": variables })
+    }
+
+    /// Returns the DAP source of a statement.
+    fn source(&self, statement: &Statement<'_>) -> Value {
+        let path = &statement.source.path;
+        json!({
+            "name": path.file_name().map(|name| name.to_string_lossy()),
+            "path": self.root.join(path),
+        })
+    }
+
+    /// Returns the path relative to the root of a path sent by the client.
+    fn relative_path(&self, path: &str) -> PathBuf {
+        let path = Path::new(path);
+        if let Ok(relative) = path.strip_prefix(self.root) {
+            return relative.to_path_buf();
+        }
+        let canonical =
+            |path: &Path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let (path, root) = (canonical(path), canonical(self.root));
+        path.strip_prefix(&root).map(Path::to_path_buf).unwrap_or(path)
+    }
+
+    fn respond(&mut self, request: &Request, result: Result<Value, String>) -> Result<()> {
+        let mut response = json!({
+            "seq": self.seq,
+            "type": "response",
+            "request_seq": request.seq,
+            "command": request.command,
+            "success": result.is_ok(),
+        });
+        match result {
+            Ok(body) if !body.is_null() => response["body"] = body,
+            Ok(_) => {}
+            Err(message) => response["message"] = message.into(),
+        }
+        self.seq += 1;
+        write_message(&mut self.writer, &response)
+    }
+
+    fn event(&mut self, event: &str, body: Value) -> Result<()> {
+        let mut message = json!({ "seq": self.seq, "type": "event", "event": event });
+        if !body.is_null() {
+            message["body"] = body;
+        }
+        self.seq += 1;
+        write_message(&mut self.writer, &message)
+    }
+}
+
+fn variable(name: &str, value: String, ty: Option<&str>) -> Value {
+    json!({ "name": name, "value": value, "type": ty, "variablesReference": 0 })
+}
+
+/// Returns the 1-based column of the start of a statement.
+fn column(statement: &Statement<'_>) -> usize {
+    let before = &statement.source.source.as_bytes()[..statement.range.start];
+    before.iter().rev().take_while(|&&b| b != b'\n').count() + 1
+}
@@ -1,17 +1,20 @@
 //! Debugger implementation.
 
-use crate::{DebugNode, DebuggerBuilder, ExitReason, tui::TUI};
+use crate::{DapTransport, DebugNode, DebuggerBuilder, ExitReason, tui::TUI};
 use alloy_primitives::map::AddressHashMap;
 use eyre::Result;
+use foundry_compilers::artifacts::StorageLayout;
 use foundry_evm_core::Breakpoints;
 use foundry_evm_traces::debug::ContractSources;
-use std::path::Path;
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 pub struct DebuggerContext {
     pub debug_arena: Vec<DebugNode>,
     pub identified_contracts: AddressHashMap<String>,
     /// Source map of contract sources
     pub contracts_sources: ContractSources,
+    /// Storage layouts of the contracts, by contract name.
+    pub storage_layouts: HashMap<String, Arc<StorageLayout>>,
     pub breakpoints: Breakpoints,
 }
 
@@ -31,6 +34,7 @@ pub fn new(
         debug_arena: Vec<DebugNode>,
         identified_contracts: AddressHashMap<String>,
         contracts_sources: ContractSources,
+        storage_layouts: HashMap<String, Arc<StorageLayout>>,
         breakpoints: Breakpoints,
     ) -> Self {
         Self {
@@ -38,6 +42,7 @@ pub fn new(
                 debug_arena,
                 identified_contracts,
                 contracts_sources,
+                storage_layouts,
                 breakpoints,
             },
         }
@@ -63,6 +68,14 @@ pub fn try_run_tui(&mut self) -> Result<ExitReason> {
         tui.try_run()
     }
 
+    /// Serves the Debug Adapter Protocol over the given transport until the client disconnects.
+    ///
+    /// Source paths are resolved relative to `root`.
+    pub fn run_dap(&mut self, transport: DapTransport, root: &Path) -> Result<()> {
+        eyre::ensure!(!self.context.debug_arena.is_empty(), "debug arena is empty");
+        crate::dap::serve(&self.context, transport, root)
+    }
+
     /// Dumps debugger data to file.
     pub fn dump_to_file(&mut self, path: &Path) -> Result<()> {
         eyre::ensure!(!self.context.debug_arena.is_empty(), "debug arena is empty");
@@ -14,8 +14,10 @@
 mod op;
 
 mod builder;
+mod dap;
 mod debugger;
 mod dump;
+mod source;
 mod tui;
 
 mod node;
@@ -23,5 +25,6 @@
 pub use node::DebugNode;
 
 pub use builder::DebuggerBuilder;
+pub use dap::DapTransport;
 pub use debugger::Debugger;
 pub use tui::{ExitReason, TUI};
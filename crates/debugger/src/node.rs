@@ -14,6 +14,8 @@ pub struct DebugNode {
     pub kind: CallKind,
     /// Calldata of the call.
     pub calldata: Bytes,
+    /// Depth of the call.
+    pub depth: usize,
     /// The debug steps.
     pub steps: Vec<CallTraceStep>,
 }
@@ -25,8 +27,9 @@ pub fn new(
         kind: CallKind,
         steps: Vec<CallTraceStep>,
         calldata: Bytes,
+        depth: usize,
     ) -> Self {
-        Self { address, kind, steps, calldata }
+        Self { address, kind, steps, calldata, depth }
     }
 }
 
@@ -78,7 +81,7 @@ fn inner(arena: &CallTraceArena, node_idx: usize, out: &mut Vec<PendingNode>) {
 
         let call = &arena_nodes[pending.node_idx].trace;
         let calldata = if call.kind.is_any_create() { Bytes::new() } else { call.data.clone() };
-        let node = DebugNode::new(call.address, call.kind, steps, calldata);
+        let node = DebugNode::new(call.address, call.kind, steps, calldata, call.depth);
 
         out.push(node);
     }
@@ -0,0 +1,774 @@
+//! Source-level navigation of the debug arena.
+
+use crate::debugger::DebuggerContext;
+use alloy_primitives::{B256, U256, keccak256};
+use foundry_common::{
+    fmt::format_token,
+    mapping_slots::MappingSlots,
+    slot_identifier::{SlotIdentifier, SlotInfo},
+};
+use foundry_compilers::artifacts::sourcemap::{Jump, SourceElement};
+use foundry_evm_traces::debug::{
+    FunctionDebugInfo, SourceData, VariableDebugInfo, format_stack_variable,
+};
+use revm::bytecode::opcode::OpCode;
+use std::ops::Range;
+
+/// A position in the debug arena.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Position {
+    /// Index of the node in the debug arena.
+    pub(crate) node: usize,
+    /// Index of the step in the node.
+    pub(crate) step: usize,
+}
+
+impl Position {
+    pub(crate) fn new(node: usize, step: usize) -> Self {
+        Self { node, step }
+    }
+}
+
+/// The Solidity statement executed by a step.
+#[derive(Clone, Debug)]
+pub(crate) struct Statement<'a> {
+    pub(crate) source: &'a SourceData,
+    /// Range of the statement in the source code.
+    pub(crate) range: Range<usize>,
+}
+
+impl Statement<'_> {
+    fn is_same(&self, other: &Self) -> bool {
+        std::ptr::eq(self.source, other.source) && self.range == other.range
+    }
+
+    /// Returns the 1-based line number of the start of the statement.
+    pub(crate) fn line(&self) -> usize {
+        self.source.line_number(self.range.start)
+    }
+}
+
+/// A variable of the function executed by a step, with its decoded value.
+#[derive(Clone, Debug)]
+pub(crate) struct Variable<'a> {
+    pub(crate) info: &'a VariableDebugInfo,
+    /// The formatted value of the stack slot guessed for the variable, or `None` if the slot is
+    /// not on the stack.
+    pub(crate) value: Option<String>,
+}
+
+impl Variable<'_> {
+    /// Formats the value for display, marked as a guess: the compiler does not guarantee the
+    /// stack layout the value is read from.
+    pub(crate) fn display_value(&self) -> String {
+        match &self.value {
+            Some(value) => format!("≈ {value}"),
+            None => "<not on stack>".to_string(),
+        }
+    }
+}
+
+/// The variables of the function executed by a step.
+#[derive(Clone, Debug)]
+pub(crate) struct FunctionVariables<'a> {
+    pub(crate) function: &'a FunctionDebugInfo,
+    /// Parameters and return variables.
+    pub(crate) parameters: Vec<Variable<'a>>,
+    /// Local variables declared before the step and still in scope.
+    pub(crate) locals: Vec<Variable<'a>>,
+}
+
+impl FunctionVariables<'_> {
+    /// Returns the title of the variables panel, labeling the values as best guesses.
+    pub(crate) fn title(&self) -> String {
+        format!("Variables: {} (best guess from the stack layout)", self.function.name)
+    }
+}
+
+/// A storage slot accessed by a call frame.
+#[derive(Debug)]
+pub(crate) struct StorageSlot {
+    pub(crate) slot: U256,
+    /// The value before the last store, if known: the last loaded or stored value, or the
+    /// original value of the slot if the frame stored to it without loading it first.
+    pub(crate) previous: Option<U256>,
+    /// The last loaded or stored value.
+    pub(crate) value: U256,
+    /// Whether the slot was stored to.
+    pub(crate) written: bool,
+    /// The variable stored in the slot, identified with the storage layout of the contract.
+    pub(crate) info: Option<SlotInfo>,
+}
+
+impl StorageSlot {
+    /// Returns the name of the variable stored in the slot, falling back to the slot number.
+    pub(crate) fn name(&self) -> String {
+        match &self.info {
+            Some(info) => info.label.clone(),
+            None => format!("{:#x}", self.slot),
+        }
+    }
+
+    /// Formats a value of the slot, decoding it with the type of the variable if known.
+    pub(crate) fn format_value(&self, value: U256) -> String {
+        self.info
+            .as_ref()
+            .and_then(|info| info.decode(B256::from(value)))
+            .map(|value| format_token(&value))
+            .unwrap_or_else(|| format!("{value:#x}"))
+    }
+}
+
+/// Depth of a step relative to the step a navigation started from.
+#[derive(Clone, Copy, Debug, Default)]
+struct RelativeDepth {
+    /// Difference in call depth.
+    call: isize,
+    /// Difference in internal function depth, tracked while in the starting call frame.
+    internal: isize,
+}
+
+impl RelativeDepth {
+    fn is_deeper(&self) -> bool {
+        self.call > 0 || (self.call == 0 && self.internal > 0)
+    }
+
+    fn is_shallower(&self) -> bool {
+        self.call < 0 || (self.call == 0 && self.internal < 0)
+    }
+}
+
+impl DebuggerContext {
+    /// Returns the source element and source of the step at given position.
+    pub(crate) fn source_mapping(&self, pos: Position) -> Option<(SourceElement, &SourceData)> {
+        let node = self.debug_arena.get(pos.node)?;
+        let step = node.steps.get(pos.step)?;
+        let contract_name = self.identified_contracts.get(&node.address)?;
+        self.contracts_sources.find_source_mapping(
+            contract_name,
+            step.pc as u32,
+            node.kind.is_any_create(),
+        )
+    }
+
+    /// Returns the Solidity statement executed by the step at given position.
+    ///
+    /// Steps that are not part of a statement, such as function dispatching, have no statement.
+    pub(crate) fn statement(&self, pos: Position) -> Option<Statement<'_>> {
+        let (element, source) = self.source_mapping(pos)?;
+        if element.index().is_none() || element.length() == 0 {
+            return None;
+        }
+        let offset = element.offset() as usize;
+        let range = source.find_function(offset)?.find_statement(offset)?.clone();
+        Some(Statement { source, range })
+    }
+
+    /// Returns the position of the step following given position.
+    pub(crate) fn next_position(&self, pos: Position) -> Option<Position> {
+        if pos.step + 1 < self.debug_arena[pos.node].steps.len() {
+            Some(Position::new(pos.node, pos.step + 1))
+        } else if pos.node + 1 < self.debug_arena.len() {
+            Some(Position::new(pos.node + 1, 0))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the position of the last step of the debug arena.
+    pub(crate) fn last_position(&self) -> Position {
+        let node = self.debug_arena.len() - 1;
+        Position::new(node, self.debug_arena[node].steps.len() - 1)
+    }
+
+    /// Returns the position of the next statement, entering function calls.
+    pub(crate) fn step_into(&self, pos: Position) -> Option<Position> {
+        let start = self.statement(pos);
+        self.find_next(pos, |depth, statement| {
+            depth.is_deeper()
+                || depth.is_shallower()
+                || start.as_ref().is_none_or(|start| !start.is_same(statement))
+        })
+    }
+
+    /// Returns the position of the next statement in the current function, stepping over
+    /// function calls.
+    pub(crate) fn step_over(&self, pos: Position) -> Option<Position> {
+        let start = self.statement(pos);
+        self.find_next(pos, |depth, statement| {
+            !depth.is_deeper()
+                && (depth.is_shallower()
+                    || start.as_ref().is_none_or(|start| !start.is_same(statement)))
+        })
+    }
+
+    /// Returns the position of the next statement after returning from the current function.
+    pub(crate) fn step_out(&self, pos: Position) -> Option<Position> {
+        let mut returned = false;
+        self.find_next(pos, |depth, _| {
+            returned |= depth.is_shallower();
+            returned
+        })
+    }
+
+    /// Walks the steps following given position, returning the position of the first step
+    /// executing a statement that matches the predicate.
+    fn find_next(
+        &self,
+        mut pos: Position,
+        mut predicate: impl FnMut(RelativeDepth, &Statement<'_>) -> bool,
+    ) -> Option<Position> {
+        let start_depth = self.debug_arena[pos.node].depth as isize;
+        let mut depth = RelativeDepth::default();
+        loop {
+            let next = self.next_position(pos)?;
+            depth.call = self.debug_arena[next.node].depth as isize - start_depth;
+            if depth.call == 0 {
+                depth.internal += self.internal_depth_change(next);
+            }
+            pos = next;
+
+            // Leaving the frame returns from all of its internal functions.
+            if depth.call < 0 {
+                depth.internal = 0;
+            }
+            if let Some(statement) = self.statement(pos)
+                && predicate(depth, &statement)
+            {
+                return Some(pos);
+            }
+        }
+    }
+
+    /// Returns the change of internal function depth when executing the step at given position:
+    /// `1` when jumping into a function, `-1` when jumping out of one.
+    fn internal_depth_change(&self, pos: Position) -> isize {
+        if pos.step == 0 {
+            return 0;
+        }
+        let prev = Position::new(pos.node, pos.step - 1);
+        if self.debug_arena[pos.node].steps[prev.step].op != OpCode::JUMP {
+            return 0;
+        }
+        match self.source_mapping(prev).map(|(element, _)| element.jump()) {
+            Some(Jump::In) => 1,
+            Some(Jump::Out) => -1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the indices of the nodes of the call frame executing the step at given position,
+    /// from the node of the step back to the start of the frame.
+    fn frame_nodes(&self, pos: Position) -> impl Iterator<Item = usize> + '_ {
+        let depth = self.debug_arena[pos.node].depth;
+        (0..=pos.node)
+            .rev()
+            // Stop at the start of the frame and skip the calls it makes.
+            .take_while(move |&i| self.debug_arena[i].depth >= depth)
+            .filter(move |&i| self.debug_arena[i].depth == depth)
+    }
+
+    /// Returns the position of the first step of the function executing the step at given
+    /// position, i.e. the destination of the jump into the function.
+    fn function_entry(&self, pos: Position) -> Option<Position> {
+        let mut unmatched_returns = 0usize;
+        for node_idx in self.frame_nodes(pos) {
+            let node = &self.debug_arena[node_idx];
+            let last = if node_idx == pos.node { pos.step } else { node.steps.len() - 1 };
+            for step in (1..=last).rev() {
+                match self.internal_depth_change(Position::new(node_idx, step)) {
+                    1 if unmatched_returns == 0 => return Some(Position::new(node_idx, step)),
+                    1 => unmatched_returns -= 1,
+                    -1 => unmatched_returns += 1,
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the call stack of the step at given position, from the step itself to the
+    /// outermost call.
+    ///
+    /// Each caller is represented by the position of its jump into the called function, or of
+    /// its call to the called contract.
+    pub(crate) fn call_stack(&self, pos: Position) -> Vec<Position> {
+        let mut stack = vec![pos];
+        let mut current = pos;
+        loop {
+            if let Some(entry) = self.function_entry(current) {
+                current = Position::new(entry.node, entry.step - 1);
+            } else {
+                let Some(first) = self.frame_nodes(current).last() else { break };
+                let Some(caller) = first.checked_sub(1) else { break };
+                current = Position::new(caller, self.debug_arena[caller].steps.len() - 1);
+            }
+            // Skip the calls made by code without a source, such as the function dispatcher.
+            if self.statement(current).is_some() {
+                stack.push(current);
+            }
+        }
+        stack
+    }
+
+    /// Returns the name of the function executing the step at given position, in the form
+    /// `Contract::function`, falling back to the contract name or address.
+    pub(crate) fn function_name(&self, pos: Position) -> String {
+        let node = &self.debug_arena[pos.node];
+        let contract = self.identified_contracts.get(&node.address);
+        if let Some((element, source)) = self.source_mapping(pos)
+            && let Some(function) = source.find_function(element.offset() as usize)
+        {
+            let contract = source
+                .find_contract_name(function.range.start, function.range.end)
+                .or(contract.map(String::as_str));
+            return match contract {
+                Some(contract) => format!("{contract}::{}", function.name),
+                None => function.name.clone(),
+            };
+        }
+        contract.cloned().unwrap_or_else(|| node.address.to_string())
+    }
+
+    /// Returns the storage slots loaded and stored by the call frame executing the step at given
+    /// position, before the step.
+    ///
+    /// Slots are identified with the storage layout of the contract, if available. Mapping entries
+    /// are resolved from the `KECCAK256` steps of the frame that computed their slots.
+    pub(crate) fn frame_storage(&self, pos: Position) -> Vec<StorageSlot> {
+        let mut slots = Vec::<StorageSlot>::new();
+        let mut mapping_slots = MappingSlots::default();
+        let nodes = self.frame_nodes(pos).collect::<Vec<_>>();
+        for &node_idx in nodes.iter().rev() {
+            let steps = &self.debug_arena[node_idx].steps;
+            let end = if node_idx == pos.node { pos.step } else { steps.len() };
+            for (i, step) in steps[..end].iter().enumerate() {
+                let Some(stack) = step.stack.as_deref() else { continue };
+                let (slot, value, stored) = match step.op {
+                    // Same as `mapping_slots::step`, from the recorded memory.
+                    OpCode::KECCAK256 => {
+                        if let [.., size, offset] = stack
+                            && *size == U256::from(0x40)
+                            && let Some(memory) = &step.memory
+                            && let Some(data) = usize::try_from(*offset)
+                                .ok()
+                                .and_then(|offset| memory.as_bytes().get(offset..)?.get(..0x40))
+                        {
+                            let low = B256::from_slice(&data[..0x20]);
+                            let high = B256::from_slice(&data[0x20..]);
+                            mapping_slots.seen_sha3.insert(keccak256(data), (low, high));
+                        }
+                        continue;
+                    }
+                    // The loaded value is on top of the stack after the load.
+                    OpCode::SLOAD => {
+                        let next = steps.get(i + 1).and_then(|next| next.stack.as_deref());
+                        let (Some(slot), Some(value)) = (stack.last(), next.and_then(<[_]>::last))
+                        else {
+                            continue;
+                        };
+                        (*slot, *value, false)
+                    }
+                    OpCode::SSTORE if stack.len() >= 2 => {
+                        (stack[stack.len() - 1], stack[stack.len() - 2], true)
+                    }
+                    _ => continue,
+                };
+
+                mapping_slots.insert(slot.into());
+                // The value before the first access of a slot is only known from the recorded
+                // storage change, if state diffs were recorded.
+                let original = step.storage_change.as_ref().and_then(|change| change.had_value);
+                match slots.iter_mut().find(|s| s.slot == slot) {
+                    Some(s) => {
+                        if stored {
+                            s.previous = Some(s.value);
+                            s.written = true;
+                        }
+                        s.value = value;
+                    }
+                    None => slots.push(StorageSlot {
+                        slot,
+                        previous: if stored { original } else { None },
+                        value,
+                        written: stored,
+                        info: None,
+                    }),
+                }
+            }
+        }
+
+        let address = self.debug_arena[pos.node].address;
+        if let Some(layout) =
+            self.identified_contracts.get(&address).and_then(|name| self.storage_layouts.get(name))
+        {
+            let identifier = SlotIdentifier::new(layout.clone());
+            for slot in &mut slots {
+                slot.info = identifier.identify(&slot.slot.into(), Some(&mapping_slots));
+            }
+        }
+        slots
+    }
+
+    /// Decodes the parameters and local variables of the function executing the step at given
+    /// position from the stack.
+    ///
+    /// Variables are assumed to be laid out on the stack in declaration order from the stack
+    /// height at the function entry: parameters, return variables, then local variables. The
+    /// values are best guesses, as the compiler does not emit the stack layout: the optimizer and
+    /// the IR pipeline may move variables around, and temporaries may sit between them.
+    pub(crate) fn function_variables(&self, pos: Position) -> Option<FunctionVariables<'_>> {
+        let entry = self.function_entry(pos)?;
+        let (element, source) = self.source_mapping(entry)?;
+        let function = source.find_function(element.offset() as usize)?;
+
+        let (current, _) = self.source_mapping(pos)?;
+        let offset = current.offset() as usize;
+        let step = &self.debug_arena[pos.node].steps[pos.step];
+        let stack = step.stack.as_deref().unwrap_or_default();
+        let memory = step.memory.as_ref().map(|memory| &memory.as_bytes()[..]).unwrap_or_default();
+
+        let entry_height = self.debug_arena[entry.node].steps[entry.step].stack.as_ref()?.len();
+        let parameter_slots =
+            function.parameters.iter().map(VariableDebugInfo::stack_slots).sum::<usize>();
+        let mut slot = entry_height.checked_sub(parameter_slots)?;
+        let mut parameters = Vec::new();
+        for info in function
+            .parameters
+            .iter()
+            .chain(&function.returns)
+            .chain(function.locals.iter().filter(|local| local.is_visible(offset)))
+        {
+            let value = stack.get(slot).map(|word| format_variable(info, *word, memory));
+            slot += info.stack_slots();
+            parameters.push(Variable { info, value });
+        }
+        let locals = parameters.split_off(function.parameters.len() + function.returns.len());
+        Some(FunctionVariables { function, parameters, locals })
+    }
+}
+
+/// Formats the value of a variable, falling back to the raw stack word.
+fn format_variable(info: &VariableDebugInfo, word: U256, memory: &[u8]) -> String {
+    format_stack_variable(info, word, memory).unwrap_or_else(|| format!("{word:#x}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DebugNode;
+    use alloy_primitives::{Address, map::AddressHashMap};
+    use foundry_compilers::{
+        artifacts::sourcemap, multi::MultiCompilerLanguage, solc::SolcLanguage,
+    };
+    use foundry_evm_core::ic::PcIcMap;
+    use foundry_evm_traces::{
+        CallKind,
+        debug::{ArtifactData, ContractSources},
+    };
+    use revm_inspectors::tracing::types::{CallTraceStep, StorageChange, StorageChangeReason};
+    use std::{collections::HashMap, sync::Arc};
+
+    const SOURCE: &str = "contract C {
+    function f() public {
+        uint256 a = 1;
+        g(a);
+        b.h();
+        a = 2;
+    }
+
+    function g(uint256 x) internal {
+        x = x + 1;
+    }
+}
+";
+
+    fn range(needle: &str) -> Range<usize> {
+        let start = SOURCE.find(needle).unwrap();
+        start..start + needle.len()
+    }
+
+    fn variable(name: &str, declared_at: usize, scope: Range<usize>) -> VariableDebugInfo {
+        VariableDebugInfo {
+            name: name.to_string(),
+            ty: "uint256".to_string(),
+            data_location: None,
+            declared_at,
+            scope,
+        }
+    }
+
+    fn step(pc: usize, op: OpCode, stack: &[u64]) -> CallTraceStep {
+        CallTraceStep {
+            depth: 0,
+            pc,
+            op,
+            contract: Address::ZERO,
+            stack: Some(stack.iter().map(|&word| U256::from(word)).collect()),
+            push_stack: None,
+            memory: None,
+            returndata: Default::default(),
+            gas_remaining: 0,
+            gas_refund_counter: 0,
+            gas_used: 0,
+            gas_cost: 0,
+            storage_change: None,
+            status: None,
+            immediate_bytes: None,
+            decoded: None,
+        }
+    }
+
+    /// Builds the debug arena of a call to `C.f()`, which calls the internal function `g` and
+    /// makes an external call to a contract without sources.
+    fn context() -> DebuggerContext {
+        let f = range(
+            "function f() public {\n        uint256 a = 1;\n        g(a);\n        b.h();\n        a = 2;\n    }",
+        );
+        let g = range("function g(uint256 x) internal {\n        x = x + 1;\n    }");
+        let f_body = SOURCE[f.clone()].find('{').unwrap() + f.start..f.end;
+        let functions = vec![
+            FunctionDebugInfo {
+                name: "f".to_string(),
+                range: f,
+                parameters: vec![],
+                returns: vec![],
+                locals: vec![variable("a", range("uint256 a = 1;").end, f_body)],
+                statements: ["uint256 a = 1;", "g(a);", "b.h();", "a = 2;"].map(range).to_vec(),
+            },
+            FunctionDebugInfo {
+                name: "g".to_string(),
+                range: g.clone(),
+                parameters: vec![variable("x", g.start, g)],
+                returns: vec![],
+                locals: vec![],
+                statements: vec![range("x = x + 1;")],
+            },
+        ];
+        let source = SourceData {
+            source: Arc::new(SOURCE.to_string()),
+            language: MultiCompilerLanguage::Solc(SolcLanguage::Solidity),
+            path: "C.sol".into(),
+            contract_definitions: vec![("C".to_string(), 0..SOURCE.len())],
+            functions,
+        };
+
+        // One element per instruction, all instructions being one byte long.
+        let element = |needle: &str, jump: char| {
+            let range = range(needle);
+            format!("{}:{}:0:{jump}", range.start, range.len())
+        };
+        let source_map = [
+            format!("0:{}:-1:i", SOURCE.len()),
+            element("uint256 a = 1", '-'),
+            element("g(a)", 'i'),
+            element("x = x + 1", '-'),
+            element("x = x + 1", 'o'),
+            element("g(a)", '-'),
+            element("b.h()", '-'),
+            element("b.h()", '-'),
+            element("a = 2", '-'),
+            format!("0:{}:-1:-", SOURCE.len()),
+        ]
+        .join(";");
+        let artifact = ArtifactData {
+            source_map: None,
+            source_map_runtime: Some(sourcemap::parse(&source_map).unwrap()),
+            pc_ic_map: None,
+            pc_ic_map_runtime: Some(PcIcMap::new(&[0x5b; 10])),
+            build_id: "build".to_string(),
+            file_id: 0,
+        };
+        let mut contracts_sources = ContractSources::default();
+        contracts_sources.artifacts_by_name.insert("C".to_string(), vec![artifact]);
+        contracts_sources
+            .sources_by_id
+            .insert("build".to_string(), HashMap::from([(0, Arc::new(source))]));
+
+        let (c, other) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let debug_arena = vec![
+            DebugNode::new(
+                c,
+                CallKind::Call,
+                vec![
+                    // Dispatcher jumping into `f`.
+                    step(0, OpCode::JUMP, &[100]),
+                    step(1, OpCode::PUSH1, &[100]),
+                    // `g(a)` jumping into `g` with `x = 1`.
+                    step(2, OpCode::JUMP, &[100, 1, 200, 1]),
+                    step(3, OpCode::JUMPDEST, &[100, 1, 200, 1]),
+                    step(4, OpCode::JUMP, &[100, 1, 200, 2]),
+                    step(5, OpCode::SLOAD, &[100, 1, 0]),
+                    step(6, OpCode::CALL, &[100, 1, 7]),
+                ],
+                Default::default(),
+                1,
+            ),
+            DebugNode::new(
+                other,
+                CallKind::Call,
+                vec![step(0, OpCode::STOP, &[])],
+                Default::default(),
+                2,
+            ),
+            DebugNode::new(
+                c,
+                CallKind::Call,
+                vec![
+                    step(7, OpCode::JUMPDEST, &[100, 1]),
+                    step(8, OpCode::SSTORE, &[100, 1, 2, 0]),
+                    step(9, OpCode::STOP, &[100, 1]),
+                ],
+                Default::default(),
+                1,
+            ),
+        ];
+
+        DebuggerContext {
+            debug_arena,
+            identified_contracts: AddressHashMap::from_iter([(c, "C".to_string())]),
+            contracts_sources,
+            storage_layouts: Default::default(),
+            breakpoints: Default::default(),
+        }
+    }
+
+    fn pos(node: usize, step: usize) -> Position {
+        Position::new(node, step)
+    }
+
+    #[test]
+    fn statements() {
+        let context = context();
+        assert!(context.statement(pos(0, 0)).is_none());
+        let statement = context.statement(pos(0, 1)).unwrap();
+        assert_eq!(&SOURCE[statement.range.clone()], "uint256 a = 1;");
+        assert_eq!(statement.line(), 3);
+        assert!(context.statement(pos(1, 0)).is_none());
+        assert!(context.statement(pos(2, 2)).is_none());
+    }
+
+    #[test]
+    fn step_into_enters_functions() {
+        let context = context();
+        assert_eq!(context.step_into(pos(0, 1)), Some(pos(0, 2)));
+        assert_eq!(context.step_into(pos(0, 2)), Some(pos(0, 3)));
+        assert_eq!(context.step_into(pos(0, 4)), Some(pos(0, 5)));
+        // Steps without a source are skipped.
+        assert_eq!(context.step_into(pos(0, 6)), Some(pos(2, 1)));
+        assert_eq!(context.step_into(pos(2, 1)), None);
+    }
+
+    #[test]
+    fn step_over_skips_calls() {
+        let context = context();
+        assert_eq!(context.step_over(pos(0, 2)), Some(pos(0, 6)));
+        assert_eq!(context.step_over(pos(0, 6)), Some(pos(2, 1)));
+        assert_eq!(context.step_over(pos(2, 1)), None);
+    }
+
+    #[test]
+    fn step_out_returns_to_caller() {
+        let context = context();
+        assert_eq!(context.step_out(pos(0, 3)), Some(pos(0, 5)));
+        assert_eq!(context.step_out(pos(0, 4)), Some(pos(0, 5)));
+        assert_eq!(context.step_out(pos(2, 1)), None);
+    }
+
+    #[test]
+    fn function_entries_and_call_stack() {
+        let context = context();
+        assert_eq!(context.function_entry(pos(0, 4)), Some(pos(0, 3)));
+        // Returned functions are skipped, including across external calls.
+        assert_eq!(context.function_entry(pos(2, 1)), Some(pos(0, 1)));
+        assert_eq!(context.function_entry(pos(0, 0)), None);
+
+        assert_eq!(context.call_stack(pos(0, 4)), vec![pos(0, 4), pos(0, 2)]);
+        assert_eq!(context.function_name(pos(0, 4)), "C::g");
+        assert_eq!(context.function_name(pos(2, 1)), "C::f");
+        assert_eq!(context.function_name(pos(1, 0)), Address::with_last_byte(2).to_string());
+    }
+
+    #[test]
+    fn function_variables() {
+        let context = context();
+
+        let variables = context.function_variables(pos(0, 4)).unwrap();
+        assert_eq!(variables.function.name, "g");
+        assert_eq!(variables.parameters.len(), 1);
+        assert_eq!(variables.parameters[0].info.name, "x");
+        assert_eq!(variables.parameters[0].value.as_deref(), Some("2"));
+        assert!(variables.locals.is_empty());
+
+        let variables = context.function_variables(pos(2, 1)).unwrap();
+        assert_eq!(variables.function.name, "f");
+        assert!(variables.parameters.is_empty());
+        assert_eq!(variables.locals.len(), 1);
+        assert_eq!(variables.locals[0].info.name, "a");
+        assert_eq!(variables.locals[0].value.as_deref(), Some("1"));
+
+        // `a` is not declared yet.
+        let variables = context.function_variables(pos(0, 1)).unwrap();
+        assert!(variables.locals.is_empty());
+        assert!(context.function_variables(pos(0, 0)).is_none());
+    }
+
+    #[test]
+    fn function_variables_are_guessed() {
+        let mut context = context();
+        let variables = context.function_variables(pos(2, 1)).unwrap();
+        assert_eq!(variables.title(), "Variables: f (best guess from the stack layout)");
+        assert_eq!(variables.locals[0].display_value(), "≈ 1");
+
+        // A temporary kept below `a` shifts the actual layout: the value shown is the one of the
+        // guessed slot, not the one of `a`.
+        context.debug_arena[2].steps[1].stack = Some([100, 9, 1].map(U256::from).to_vec());
+        let variables = context.function_variables(pos(2, 1)).unwrap();
+        assert_eq!(variables.locals[0].info.name, "a");
+        assert_eq!(variables.locals[0].display_value(), "≈ 9");
+
+        // The guessed slot may be past the top of the stack.
+        context.debug_arena[2].steps[1].stack = Some([100].map(U256::from).to_vec());
+        let variables = context.function_variables(pos(2, 1)).unwrap();
+        assert_eq!(variables.locals[0].display_value(), "<not on stack>");
+    }
+
+    #[test]
+    fn frame_storage() {
+        let context = context();
+
+        let slots = context.frame_storage(pos(0, 6));
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].value, U256::from(7));
+        assert!(!slots[0].written);
+
+        // Storage accessed before the external call is part of the frame.
+        let slots = context.frame_storage(pos(2, 2));
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].slot, U256::ZERO);
+        assert_eq!(slots[0].previous, Some(U256::from(7)));
+        assert_eq!(slots[0].value, U256::from(2));
+        assert!(slots[0].written);
+        assert_eq!(slots[0].name(), "0x0");
+
+        // Store to slot 1 instead, which was not loaded before.
+        let mut context = context;
+        context.debug_arena[2].steps[1].stack = Some([100, 1, 3, 1].map(U256::from).to_vec());
+        let slots = context.frame_storage(pos(2, 2));
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[1].slot, U256::from(1));
+        assert_eq!(slots[1].previous, None);
+        assert!(slots[1].written);
+
+        // The original value is known if state diffs were recorded.
+        context.debug_arena[2].steps[1].storage_change = Some(StorageChange {
+            key: U256::from(1),
+            value: U256::from(3),
+            had_value: Some(U256::from(5)),
+            reason: StorageChangeReason::SSTORE,
+        });
+        let slots = context.frame_storage(pos(2, 2));
+        assert_eq!(slots[1].previous, Some(U256::from(5)));
+        assert_eq!(slots[1].value, U256::from(3));
+    }
+}
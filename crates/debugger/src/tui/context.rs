@@ -1,6 +1,10 @@
 //! Debugger context and event handler implementation.
 
-use crate::{DebugNode, ExitReason, debugger::DebuggerContext};
+use crate::{
+    DebugNode, ExitReason,
+    debugger::DebuggerContext,
+    source::{FunctionVariables, Position, StorageSlot},
+};
 use alloy_primitives::{Address, hex};
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use foundry_evm_core::buffer::BufferKind;
@@ -26,6 +30,8 @@ pub(crate) struct TUIContext<'a> {
     pub(crate) draw_memory: DrawMemory,
     pub(crate) opcode_list: Vec<String>,
     pub(crate) last_index: usize,
+    /// Storage slots accessed by the current call frame, and the position they were computed at.
+    pub(crate) storage: (Position, Vec<StorageSlot>),
 
     pub(crate) stack_labels: bool,
     /// Whether to decode active buffer as utf8 or not.
@@ -45,6 +51,7 @@ pub(crate) fn new(debugger_context: &'a mut DebuggerContext) -> Self {
             draw_memory: DrawMemory::default(),
             opcode_list: Vec::new(),
             last_index: 0,
+            storage: Default::default(),
 
             stack_labels: false,
             buf_utf: false,
@@ -55,6 +62,7 @@ pub(crate) fn new(debugger_context: &'a mut DebuggerContext) -> Self {
 
     pub(crate) fn init(&mut self) {
         self.gen_opcode_list();
+        self.gen_storage();
     }
 
     pub(crate) fn debug_arena(&self) -> &[DebugNode] {
@@ -85,6 +93,16 @@ pub(crate) fn current_step(&self) -> &CallTraceStep {
         &self.debug_steps()[self.current_step]
     }
 
+    /// Returns the position of the current debug step in the debug arena.
+    pub(crate) fn position(&self) -> Position {
+        Position::new(self.draw_memory.inner_call_index, self.current_step)
+    }
+
+    /// Returns the variables of the function executing the current debug step.
+    pub(crate) fn function_variables(&self) -> Option<FunctionVariables<'_>> {
+        self.debugger_context.function_variables(self.position())
+    }
+
     fn gen_opcode_list(&mut self) {
         self.opcode_list.clear();
         let debug_steps =
@@ -101,6 +119,17 @@ fn gen_opcode_list_if_necessary(&mut self) {
         }
     }
 
+    fn gen_storage(&mut self) {
+        let pos = self.position();
+        self.storage = (pos, self.debugger_context.frame_storage(pos));
+    }
+
+    fn gen_storage_if_necessary(&mut self) {
+        if self.storage.0 != self.position() {
+            self.gen_storage();
+        }
+    }
+
     fn active_buffer(&self) -> &[u8] {
         match self.active_buffer {
             BufferKind::Memory => self.current_step().memory.as_ref().unwrap().as_bytes(),
@@ -117,8 +146,9 @@ pub(crate) fn handle_event(&mut self, event: Event) -> ControlFlow<ExitReason> {
             Event::Mouse(event) => self.handle_mouse_event(event),
             _ => ControlFlow::Continue(()),
         };
-        // Generate the list after the event has been handled.
+        // Generate the list and the storage after the event has been handled.
         self.gen_opcode_list_if_necessary();
+        self.gen_storage_if_necessary();
         ret
     }
 
@@ -232,6 +262,15 @@ fn handle_key_event(&mut self, event: KeyEvent) -> ControlFlow<ExitReason> {
                     .unwrap_or_default();
             }),
 
+            // Step over the current statement
+            KeyCode::Char('n') => self.repeat(|this| this.step_source(DebuggerContext::step_over)),
+
+            // Step into the current statement
+            KeyCode::Char('i') => self.repeat(|this| this.step_source(DebuggerContext::step_into)),
+
+            // Step out of the current function
+            KeyCode::Char('o') => self.repeat(|this| this.step_source(DebuggerContext::step_out)),
+
             // Toggle stack labels
             KeyCode::Char('t') => self.stack_labels = !self.stack_labels,
 
@@ -303,6 +342,15 @@ fn step(&mut self) {
         }
     }
 
+    /// Moves to the position returned by the source-level step `f`, or to the end of the
+    /// execution if there is none.
+    fn step_source(&mut self, f: fn(&DebuggerContext, Position) -> Option<Position>) {
+        let context = &*self.debugger_context;
+        let pos = f(context, self.position()).unwrap_or_else(|| context.last_position());
+        self.draw_memory.inner_call_index = pos.node;
+        self.current_step = pos.step;
+    }
+
     /// Calls a closure `f` the number of times specified in the key buffer, and at least once.
     fn repeat(&mut self, mut f: impl FnMut(&mut Self)) {
         for _ in 0..buffer_as_number(&self.key_buffer) {
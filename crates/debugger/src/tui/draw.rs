@@ -80,7 +80,9 @@ fn size_too_small(&self, f: &mut Frame<'_>, min_width: u16, min_height: u16) {
     /// |                             |
     /// |             src             |
     /// |                             |
-    /// |-----------------------------|
+    /// |--------------|--------------|
+    /// |  variables   |   storage    |
+    /// |--------------|--------------|
     /// ```
     fn vertical_layout(&self, f: &mut Frame<'_>) {
         let area = f.area();
@@ -98,20 +100,29 @@ fn vertical_layout(&self, f: &mut Frame<'_>) {
             unreachable!()
         };
 
-        // Split the app in 4 vertically to construct all the panes.
-        let [op_pane, stack_pane, memory_pane, src_pane] = Layout::new(
+        // Split the app in 5 vertically to construct all the panes.
+        let [op_pane, stack_pane, memory_pane, src_pane, variables_pane] = Layout::new(
             Direction::Vertical,
             [
                 Constraint::Ratio(1, 6),
                 Constraint::Ratio(1, 6),
                 Constraint::Ratio(1, 6),
-                Constraint::Ratio(3, 6),
+                Constraint::Ratio(2, 6),
+                Constraint::Ratio(1, 6),
             ],
         )
         .split(app)[..] else {
             unreachable!()
         };
 
+        // Split the bottom pane in 2 horizontally to variables and storage.
+        let [variables_pane, storage_pane] =
+            Layout::new(Direction::Horizontal, [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                .split(variables_pane)[..]
+        else {
+            unreachable!()
+        };
+
         if self.show_shortcuts {
             self.draw_footer(f, footer);
         }
@@ -119,6 +130,8 @@ fn vertical_layout(&self, f: &mut Frame<'_>) {
         self.draw_op_list(f, op_pane);
         self.draw_stack(f, stack_pane);
         self.draw_buffer(f, memory_pane);
+        self.draw_variables(f, variables_pane);
+        self.draw_storage(f, storage_pane);
     }
 
     /// Draws the layout in horizontal mode.
@@ -127,8 +140,12 @@ fn vertical_layout(&self, f: &mut Frame<'_>) {
     /// |-----------------|-----------|
     /// |        op       |   stack   |
     /// |-----------------|-----------|
-    /// |                 |           |
-    /// |       src       |    buf    |
+    /// |                 | variables |
+    /// |                 |-----------|
+    /// |                 |  storage  |
+    /// |                 |-----------|
+    /// |       src       |           |
+    /// |                 |    buf    |
     /// |                 |           |
     /// |-----------------|-----------|
     /// ```
@@ -161,11 +178,17 @@ fn horizontal_layout(&self, f: &mut Frame<'_>) {
             unreachable!()
         };
 
-        // Split right pane horizontally to construct stack and memory.
-        let [stack_pane, memory_pane] =
-            Layout::new(Direction::Vertical, [Constraint::Ratio(1, 4), Constraint::Ratio(3, 4)])
-                .split(app_right)[..]
-        else {
+        // Split right pane horizontally to construct stack, variables, storage and memory.
+        let [stack_pane, variables_pane, storage_pane, memory_pane] = Layout::new(
+            Direction::Vertical,
+            [
+                Constraint::Ratio(1, 4),
+                Constraint::Ratio(1, 4),
+                Constraint::Ratio(1, 4),
+                Constraint::Ratio(1, 4),
+            ],
+        )
+        .split(app_right)[..] else {
             unreachable!()
         };
 
@@ -176,10 +199,12 @@ fn horizontal_layout(&self, f: &mut Frame<'_>) {
         self.draw_op_list(f, op_pane);
         self.draw_stack(f, stack_pane);
         self.draw_buffer(f, memory_pane);
+        self.draw_variables(f, variables_pane);
+        self.draw_storage(f, storage_pane);
     }
 
     fn draw_footer(&self, f: &mut Frame<'_>, area: Rect) {
-        let l1 = "[q]: quit | [k/j]: prev/next op | [a/s]: prev/next jump | [c/C]: prev/next call | [g/G]: start/end | [b]: cycle memory/calldata/returndata buffers";
+        let l1 = "[q]: quit | [k/j]: prev/next op | [a/s]: prev/next jump | [c/C]: prev/next call | [n/i/o]: step over/into/out | [g/G]: start/end | [b]: cycle memory/calldata/returndata buffers";
         let l2 = "[t]: stack labels | [m]: buffer decoding | [shift + j/k]: scroll stack | [ctrl + j/k]: scroll buffer | ['<char>]: goto breakpoint | [h] toggle help";
         let dimmed = Style::new().add_modifier(Modifier::DIM);
         let lines =
@@ -575,6 +600,83 @@ fn draw_buffer(&self, f: &mut Frame<'_>, area: Rect) {
         let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
         f.render_widget(paragraph, area);
     }
+
+    fn draw_variables(&self, f: &mut Frame<'_>, area: Rect) {
+        let Some(variables) = self.function_variables() else {
+            let block = Block::default().title("Variables").borders(Borders::ALL);
+            let paragraph = Paragraph::new("No variable info for the current step").block(block);
+            f.render_widget(paragraph, area);
+            return;
+        };
+
+        let parameter_style = Style::new().fg(Color::Cyan);
+        let local_style = Style::new().fg(Color::Green);
+        let text: Vec<Line<'_>> = variables
+            .parameters
+            .iter()
+            .map(|var| (var, parameter_style))
+            .chain(variables.locals.iter().map(|var| (var, local_style)))
+            .filter(|(var, _)| !var.info.name.is_empty())
+            .map(|(var, style)| {
+                Line::from(vec![
+                    Span::styled(var.info.name.as_str(), style),
+                    Span::styled(
+                        format!(" ({})", var.info.ty),
+                        Style::new().add_modifier(Modifier::DIM),
+                    ),
+                    Span::raw(": "),
+                    Span::raw(var.display_value()),
+                ])
+            })
+            .collect();
+
+        let block = Block::default().title(variables.title()).borders(Borders::ALL);
+        let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+        f.render_widget(paragraph, area);
+    }
+
+    fn draw_storage(&self, f: &mut Frame<'_>, area: Rect) {
+        let slots = &self.storage.1;
+        let block = Block::default().title("Storage").borders(Borders::ALL);
+        if slots.is_empty() {
+            let paragraph = Paragraph::new("No storage accessed in the current call").block(block);
+            f.render_widget(paragraph, area);
+            return;
+        }
+
+        let dimmed = Style::new().add_modifier(Modifier::DIM);
+        let text: Vec<Line<'_>> = slots
+            .iter()
+            .map(|slot| {
+                let mut spans =
+                    vec![Span::styled(slot.name(), Style::new().fg(Color::Cyan)), Span::raw(": ")];
+                match slot.previous {
+                    Some(previous) if slot.written => {
+                        spans.push(Span::styled(slot.format_value(previous), dimmed));
+                        spans.push(Span::raw(" → "));
+                        spans.push(Span::styled(
+                            slot.format_value(slot.value),
+                            Style::new().fg(Color::Yellow),
+                        ));
+                    }
+                    // The slot was stored to before being loaded, without recorded state diffs.
+                    None if slot.written => {
+                        spans.push(Span::styled("unknown", dimmed));
+                        spans.push(Span::raw(" → "));
+                        spans.push(Span::styled(
+                            slot.format_value(slot.value),
+                            Style::new().fg(Color::Yellow),
+                        ));
+                    }
+                    _ => spans.push(Span::raw(slot.format_value(slot.value))),
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+        f.render_widget(paragraph, area);
+    }
 }
 
 /// Wrapper around a list of [`Line`]s that prepends the line number on each new line.
@@ -1,6 +1,6 @@
 use crate::{
-    AsDoc, BufWriter, Document, ParseItem, ParseSource, Parser, Preprocessor,
-    document::DocumentContent, helpers::merge_toml_table, solang_ext::Visitable,
+    AsDoc, BufWriter, DocFormat, Document, Html, JsonDocument, ParseItem, ParseSource, Parser,
+    Preprocessor, document::DocumentContent, helpers::merge_toml_table, solang_ext::Visitable,
 };
 use alloy_primitives::map::HashMap;
 use eyre::{Context, Result};
@@ -37,6 +37,8 @@ pub struct DocBuilder {
     fmt: FormatterConfig,
     /// Whether to include libraries to the output.
     include_libraries: bool,
+    /// The output format.
+    format: DocFormat,
 }
 
 impl DocBuilder {
@@ -44,6 +46,8 @@ impl DocBuilder {
     const SOL_EXT: &'static str = "sol";
     const README: &'static str = "README.md";
     const SUMMARY: &'static str = "SUMMARY.md";
+    const INDEX_JSON: &'static str = "index.json";
+    const INDEX_HTML: &'static str = "index.html";
 
     /// Create new instance of builder.
     pub fn new(
@@ -61,6 +65,7 @@ pub fn new(
             config: DocConfig::default(),
             preprocessors: Default::default(),
             fmt: Default::default(),
+            format: Default::default(),
         }
     }
 
@@ -82,6 +87,12 @@ pub fn with_fmt(mut self, fmt: FormatterConfig) -> Self {
         self
     }
 
+    /// Set the output format on the builder.
+    pub fn with_format(mut self, format: DocFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     /// Set preprocessors on the builder.
     pub fn with_preprocessor<P: Preprocessor + 'static>(mut self, preprocessor: P) -> Self {
         self.preprocessors.push(Box::new(preprocessor) as Box<dyn Preprocessor>);
@@ -98,6 +109,31 @@ pub fn build(self, compiler: &mut solar::sema::Compiler) -> eyre::Result<()> {
         fs::create_dir_all(self.root.join(&self.config.out))
             .wrap_err("failed to create output directory")?;
 
+        let documents = self.documents(compiler)?;
+        if documents.is_empty() {
+            return Ok(());
+        }
+
+        match self.format {
+            DocFormat::Markdown => self.write_mdbook(documents)?,
+            DocFormat::Json => return self.write_json(&documents),
+            DocFormat::Html => return self.write_html(&documents),
+        }
+
+        // Build the book if requested
+        if self.should_build {
+            MDBook::load(self.out_dir().wrap_err("failed to construct output directory")?)
+                .and_then(|book| book.build())
+                .map_err(|err| eyre::eyre!("failed to build book: {err:?}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Parse the sources and return the preprocessed documents, without writing them.
+    ///
+    /// Library documents are only returned if libraries are included.
+    pub fn documents(&self, compiler: &mut solar::sema::Compiler) -> eyre::Result<Vec<Document>> {
         // Expand ignore globs
         let ignored = expand_globs(&self.root, self.config.ignore.iter())?;
 
@@ -108,7 +144,7 @@ pub fn build(self, compiler: &mut solar::sema::Compiler) -> eyre::Result<()> {
 
         if sources.is_empty() {
             sh_println!("No sources detected at {}", self.sources.display())?;
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         let library_sources = self
@@ -123,7 +159,8 @@ pub fn build(self, compiler: &mut solar::sema::Compiler) -> eyre::Result<()> {
             .chain(library_sources.iter().map(|path| (path, true)))
             .collect::<Vec<_>>();
 
-        let out_dir = self.out_dir()?;
+        // The output directory only exists if the documentation is written.
+        let out_dir = self.out_dir().unwrap_or_else(|_| self.root.join(&self.config.out));
         let out_target_dir = out_dir.clone();
         let documents = compiler.enter_mut(|compiler| -> eyre::Result<Vec<Vec<Document>>> {
             let gcx = compiler.gcx();
@@ -286,17 +323,7 @@ pub fn build(self, compiler: &mut solar::sema::Compiler) -> eyre::Result<()> {
             .filter(|d| !d.from_library || self.include_libraries)
             .collect_vec();
 
-        // Write mdbook related files
-        self.write_mdbook(documents)?;
-
-        // Build the book if requested
-        if self.should_build {
-            MDBook::load(self.out_dir().wrap_err("failed to construct output directory")?)
-                .and_then(|book| book.build())
-                .map_err(|err| eyre::eyre!("failed to build book: {err:?}"))?;
-        }
-
-        Ok(())
+        Ok(documents)
     }
 
     fn write_mdbook(&self, documents: Vec<Document>) -> eyre::Result<()> {
@@ -365,6 +392,45 @@ fn write_mdbook(&self, documents: Vec<Document>) -> eyre::Result<()> {
         Ok(())
     }
 
+    fn write_json(&self, documents: &[Document]) -> eyre::Result<()> {
+        let out_dir = self.out_dir().wrap_err("failed to construct output directory")?;
+        let out_dir_src = out_dir.join(Self::SRC);
+
+        let documents =
+            documents.iter().map(|document| JsonDocument::new(document, &self.root)).collect_vec();
+        for document in &documents {
+            let path = out_dir_src.join(&document.path);
+            fs::create_dir_all(
+                path.parent().ok_or_else(|| eyre::format_err!("empty target path; noop"))?,
+            )?;
+            fs::write(path, serde_json::to_string_pretty(document)?)?;
+        }
+
+        // Write the index of the documents
+        let index = documents
+            .iter()
+            .map(|document| {
+                serde_json::json!({
+                    "identity": document.identity,
+                    "path": document.path,
+                    "source": document.source,
+                })
+            })
+            .collect_vec();
+        fs::write(out_dir.join(Self::INDEX_JSON), serde_json::to_string_pretty(&index)?)?;
+
+        Ok(())
+    }
+
+    fn write_html(&self, documents: &[Document]) -> eyre::Result<()> {
+        let out_dir = self.out_dir().wrap_err("failed to construct output directory")?;
+        let documents =
+            documents.iter().map(|document| JsonDocument::new(document, &self.root)).collect_vec();
+        let title = if self.config.title.is_empty() { "Documentation" } else { &self.config.title };
+        fs::write(out_dir.join(Self::INDEX_HTML), Html { title, documents: &documents }.as_doc()?)?;
+        Ok(())
+    }
+
     fn book_config(&self) -> eyre::Result<String> {
         // Read the default book first
         let mut book: value::Table = toml::from_str(include_str!("../static/book.toml"))?;
@@ -0,0 +1,301 @@
+//! NatSpec coverage of the documented contracts.
+
+use crate::{
+    CommentTag, Comments, Document, INHERITDOC_ID, ParseItem, ParseSource, PreprocessorOutput,
+    document::{DocumentContent, read_context},
+};
+use serde::Serialize;
+use solang_parser::pt::{FunctionAttribute, FunctionTy, Identifier, VariableAttribute, Visibility};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// The NatSpec coverage of the public API of a project.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct NatSpecCoverage {
+    /// The coverage of each contract, interface and library with public items.
+    pub contracts: Vec<ContractCoverage>,
+}
+
+impl NatSpecCoverage {
+    /// Computes the coverage of the contracts in the given documents.
+    ///
+    /// Source paths are made relative to `root`.
+    pub fn new(documents: &[Document], root: &Path) -> Self {
+        let contracts = documents
+            .iter()
+            .filter_map(|document| match document.content {
+                DocumentContent::Single(ref item) if item.as_contract().is_some() => {
+                    Some(ContractCoverage::new(document, item, root))
+                }
+                _ => None,
+            })
+            .filter(|coverage| coverage.total > 0)
+            .collect();
+        Self { contracts }
+    }
+
+    /// Returns the number of fully documented items.
+    pub fn documented(&self) -> usize {
+        self.contracts.iter().map(|contract| contract.documented).sum()
+    }
+
+    /// Returns the number of public items.
+    pub fn total(&self) -> usize {
+        self.contracts.iter().map(|contract| contract.total).sum()
+    }
+
+    /// Returns the percentage of fully documented items.
+    pub fn percentage(&self) -> f64 {
+        percentage(self.documented(), self.total())
+    }
+}
+
+/// The NatSpec coverage of a single contract.
+#[derive(Clone, Debug, Serialize)]
+pub struct ContractCoverage {
+    /// The contract name.
+    pub name: String,
+    /// The source file declaring the contract.
+    pub path: PathBuf,
+    /// The number of fully documented items.
+    pub documented: usize,
+    /// The number of public items.
+    pub total: usize,
+    /// The items lacking NatSpec tags.
+    pub missing: Vec<MissingNatSpec>,
+}
+
+impl ContractCoverage {
+    fn new(document: &Document, contract: &ParseItem, root: &Path) -> Self {
+        let inheritdocs = read_context!(document, INHERITDOC_ID, Inheritdoc);
+
+        let mut total = 0;
+        let mut missing = Vec::new();
+        for child in &contract.children {
+            let Some(kind) = ItemKind::of(&child.source) else { continue };
+            total += 1;
+
+            let comments =
+                child.comments.merge_inheritdoc(&child.source.signature(), inheritdocs.clone());
+            let tags = missing_tags(&child.source, &comments);
+            if !tags.is_empty() {
+                missing.push(MissingNatSpec { kind, name: child.source.signature(), tags });
+            }
+        }
+
+        Self {
+            name: contract.source.ident(),
+            path: document.item_path.strip_prefix(root).unwrap_or(&document.item_path).to_owned(),
+            documented: total - missing.len(),
+            total,
+            missing,
+        }
+    }
+
+    /// Returns the percentage of fully documented items.
+    pub fn percentage(&self) -> f64 {
+        percentage(self.documented, self.total)
+    }
+}
+
+/// A public item lacking NatSpec tags.
+#[derive(Clone, Debug, Serialize)]
+pub struct MissingNatSpec {
+    /// The kind of the item.
+    pub kind: ItemKind,
+    /// The item name, or signature for functions.
+    pub name: String,
+    /// The missing tags, e.g. `@notice` or `@param amount`.
+    pub tags: Vec<String>,
+}
+
+/// The kind of items whose NatSpec is covered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    Function,
+    Variable,
+    Event,
+    Error,
+}
+
+impl ItemKind {
+    /// Returns the kind of the item if it is part of the public API of a contract.
+    fn of(source: &ParseSource) -> Option<Self> {
+        match source {
+            ParseSource::Function(func)
+                if func.ty == FunctionTy::Function
+                    && func.attributes.iter().any(|attr| {
+                        matches!(
+                            attr,
+                            FunctionAttribute::Visibility(
+                                Visibility::Public(_) | Visibility::External(_)
+                            )
+                        )
+                    }) =>
+            {
+                Some(Self::Function)
+            }
+            ParseSource::Variable(var)
+                if var.attrs.iter().any(|attr| {
+                    matches!(attr, VariableAttribute::Visibility(Visibility::Public(_)))
+                }) =>
+            {
+                Some(Self::Variable)
+            }
+            ParseSource::Event(_) => Some(Self::Event),
+            ParseSource::Error(_) => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Function => "function",
+            Self::Variable => "variable",
+            Self::Event => "event",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Returns the tags missing from the comments of an item.
+///
+/// Every item requires a `@notice`, and a `@param` for each named parameter. Functions also
+/// require a `@return` for each return value, named after it if the return value is named.
+fn missing_tags(source: &ParseSource, comments: &Comments) -> Vec<String> {
+    let mut missing = Vec::new();
+    if comments.include_tag(CommentTag::Notice).is_empty() {
+        missing.push("@notice".to_string());
+    }
+
+    let params: Vec<&Identifier> = match source {
+        ParseSource::Function(func) => {
+            func.params.iter().filter_map(|(_, param)| param.as_ref()?.name.as_ref()).collect()
+        }
+        ParseSource::Event(event) => {
+            event.fields.iter().filter_map(|field| field.name.as_ref()).collect()
+        }
+        ParseSource::Error(error) => {
+            error.fields.iter().filter_map(|field| field.name.as_ref()).collect()
+        }
+        _ => Vec::new(),
+    };
+    let documented = comments.include_tag(CommentTag::Param);
+    for param in params {
+        if !documented.iter().any(|c| c.match_first_word(&param.name).is_some()) {
+            missing.push(format!("@param {}", param.name));
+        }
+    }
+
+    if let ParseSource::Function(func) = source {
+        let documented = comments.include_tag(CommentTag::Return);
+        for (i, (_, ret)) in func.returns.iter().enumerate() {
+            match ret.as_ref().and_then(|ret| ret.name.as_ref()) {
+                Some(name) => {
+                    if !documented.iter().any(|c| c.match_first_word(&name.name).is_some()) {
+                        missing.push(format!("@return {}", name.name));
+                    }
+                }
+                None if documented.len() <= i => missing.push("@return".to_string()),
+                None => {}
+            }
+        }
+    }
+
+    missing
+}
+
+fn percentage(documented: usize, total: usize) -> f64 {
+    if total == 0 { 100.0 } else { documented as f64 * 100.0 / total as f64 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Parser, solang_ext::Visitable};
+
+    fn coverage(src: &str) -> NatSpecCoverage {
+        let (mut source, comments) = solang_parser::parse(src, 0).expect("failed to parse source");
+        let mut parser = Parser::new(comments, src.to_owned(), 4);
+        source.visit(&mut parser).expect("failed to visit source");
+        let documents = parser
+            .items()
+            .into_iter()
+            .map(|item| {
+                let ident = item.source.ident();
+                Document::new("src/A.sol".into(), PathBuf::new(), false, PathBuf::new())
+                    .with_content(DocumentContent::Single(item), ident)
+            })
+            .collect::<Vec<_>>();
+        NatSpecCoverage::new(&documents, Path::new(""))
+    }
+
+    #[test]
+    fn reports_missing_tags() {
+        let coverage = coverage(
+            r"
+            contract A {
+                /// @notice The owner.
+                address public owner;
+                uint256 internal secret;
+
+                /// @notice Emitted on transfers.
+                /// @param from The sender.
+                event Transfer(address indexed from, address indexed to);
+
+                error Unauthorized();
+
+                /// @notice Transfers tokens.
+                /// @param to The recipient.
+                /// @param amount The amount.
+                /// @return success Whether the transfer succeeded.
+                function transfer(address to, uint256 amount) external returns (bool success) {}
+
+                /// @param amount The amount.
+                function burn(uint256 amount) public returns (uint256) {}
+
+                function _mint(uint256 amount) internal {}
+            }
+        ",
+        );
+
+        assert_eq!(coverage.contracts.len(), 1);
+        let contract = &coverage.contracts[0];
+        assert_eq!(contract.name, "A");
+        assert_eq!(contract.total, 5);
+        assert_eq!(contract.documented, 2);
+
+        let missing = contract
+            .missing
+            .iter()
+            .map(|item| format!("{} {}: {}", item.kind, item.name, item.tags.join(", ")))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            missing,
+            [
+                "event Transfer: @param to",
+                "error Unauthorized: @notice",
+                "function burn(uint256): @notice, @return",
+            ]
+        );
+        assert_eq!(coverage.percentage(), 40.0);
+    }
+
+    #[test]
+    fn skips_contracts_without_public_items() {
+        let coverage = coverage(
+            r"
+            library L {
+                function f() internal {}
+            }
+        ",
+        );
+        assert!(coverage.contracts.is_empty());
+        assert_eq!(coverage.percentage(), 100.0);
+    }
+}
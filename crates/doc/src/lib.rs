@@ -14,6 +14,9 @@
 mod builder;
 pub use builder::DocBuilder;
 
+mod coverage;
+pub use coverage::{ContractCoverage, ItemKind, MissingNatSpec, NatSpecCoverage};
+
 mod document;
 pub use document::Document;
 
@@ -28,7 +31,10 @@
 pub use preprocessor::*;
 
 mod writer;
-pub use writer::{AsDoc, AsDocResult, BufWriter, Markdown};
+pub use writer::{
+    AsDoc, AsDocResult, BufWriter, DocFormat, Html, JsonBase, JsonComment, JsonDocument, JsonItem,
+    Markdown,
+};
 
 pub use mdbook;
 
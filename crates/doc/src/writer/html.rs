@@ -0,0 +1,266 @@
+use crate::{
+    AsDoc, AsDocResult,
+    writer::json::{JsonComment, JsonDocument, JsonItem},
+};
+use alloy_primitives::map::{HashMap, HashSet};
+use itertools::Itertools;
+use std::fmt::Write;
+
+/// The kinds of contract children, with their section titles.
+const CHILDREN: &[(&str, &str)] = &[
+    ("variable", "State Variables"),
+    ("function", "Functions"),
+    ("event", "Events"),
+    ("error", "Errors"),
+    ("struct", "Structs"),
+    ("enum", "Enums"),
+    ("type", "Types"),
+];
+
+/// A self-contained HTML page documenting all the [JsonDocument]s, with client-side search.
+#[derive(Debug)]
+pub struct Html<'a> {
+    /// The page title.
+    pub title: &'a str,
+    /// The documents to render.
+    pub documents: &'a [JsonDocument],
+}
+
+impl AsDoc for Html<'_> {
+    fn as_doc(&self) -> AsDocResult {
+        // Assign a unique anchor to every document, and link contract names to their document.
+        let mut used = HashSet::default();
+        let anchors = self
+            .documents
+            .iter()
+            .map(|document| {
+                let slug = slug(&document.identity);
+                let mut anchor = slug.clone();
+                let mut i = 1;
+                while !used.insert(anchor.clone()) {
+                    anchor = format!("{slug}-{i}");
+                    i += 1;
+                }
+                anchor
+            })
+            .collect::<Vec<_>>();
+        let mut contracts: HashMap<&str, &str> = HashMap::default();
+        for (document, anchor) in self.documents.iter().zip(&anchors) {
+            for item in document.items.iter().filter(|item| !item.items.is_empty()) {
+                contracts.entry(item.name.as_str()).or_insert(anchor.as_str());
+            }
+        }
+
+        let title = escape(self.title);
+        let mut out = String::new();
+        writeln!(out, "<!DOCTYPE html>")?;
+        writeln!(out, "<html lang=\"en\">")?;
+        writeln!(out, "<head>")?;
+        writeln!(out, "<meta charset=\"utf-8\">")?;
+        writeln!(out, "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">")?;
+        writeln!(out, "<title>{title}</title>")?;
+        writeln!(out, "<style>\n{}</style>", include_str!("../../static/html/style.css"))?;
+        writeln!(out, "</head>")?;
+        writeln!(out, "<body>")?;
+
+        writeln!(out, "<nav>")?;
+        writeln!(out, "<h1>{title}</h1>")?;
+        writeln!(
+            out,
+            "<input id=\"search\" type=\"search\" placeholder=\"Search\" autocomplete=\"off\">"
+        )?;
+        writeln!(out, "<ul>")?;
+        for (document, anchor) in self.documents.iter().zip(&anchors) {
+            writeln!(
+                out,
+                "<li data-search=\"{}\"><a href=\"#{anchor}\">{}</a></li>",
+                escape(&search_text(document)),
+                escape(&document.identity)
+            )?;
+        }
+        writeln!(out, "</ul>")?;
+        writeln!(out, "</nav>")?;
+
+        writeln!(out, "<main>")?;
+        for (document, anchor) in self.documents.iter().zip(&anchors) {
+            writeln!(
+                out,
+                "<section id=\"{anchor}\" data-search=\"{}\">",
+                escape(&search_text(document))
+            )?;
+            writeln!(out, "<h2>{}</h2>", escape(&document.identity))?;
+            write!(out, "<p class=\"source\">{}", escape(&document.source.display().to_string()))?;
+            if let Some(git_source) = &document.git_source {
+                write!(out, " &middot; <a href=\"{}\">Git Source</a>", escape(git_source))?;
+            }
+            writeln!(out, "</p>")?;
+
+            if !document.inheritance.is_empty() {
+                let bases = document
+                    .inheritance
+                    .iter()
+                    .map(|base| match contracts.get(base.name.as_str()) {
+                        Some(anchor) => format!("<a href=\"#{anchor}\">{}</a>", escape(&base.name)),
+                        None => escape(&base.name),
+                    })
+                    .join(", ");
+                writeln!(out, "<p><strong>Inherits:</strong> {bases}</p>")?;
+            }
+
+            if !document.deployments.is_empty() {
+                writeln!(out, "<table>")?;
+                writeln!(out, "<tr><th>Network</th><th>Address</th></tr>")?;
+                for deployment in &document.deployments {
+                    writeln!(
+                        out,
+                        "<tr><td>{}</td><td><code>{}</code></td></tr>",
+                        escape(deployment.network.as_deref().unwrap_or_default()),
+                        deployment.address
+                    )?;
+                }
+                writeln!(out, "</table>")?;
+            }
+
+            for item in &document.items {
+                write_item(&mut out, item, anchor, item.items.is_empty())?;
+            }
+            writeln!(out, "</section>")?;
+        }
+        writeln!(out, "</main>")?;
+
+        writeln!(out, "<script>\n{}</script>", include_str!("../../static/html/search.js"))?;
+        writeln!(out, "</body>")?;
+        writeln!(out, "</html>")?;
+        Ok(out)
+    }
+}
+
+/// Writes an item and its children. Contracts are already named by their section, so they are
+/// written without a heading.
+fn write_item(out: &mut String, item: &JsonItem, anchor: &str, heading: bool) -> std::fmt::Result {
+    if heading {
+        writeln!(
+            out,
+            "<h3 id=\"{anchor}-{}\">{}</h3>",
+            slug(&item.signature),
+            escape(&item.signature.replace(',', ", "))
+        )?;
+    }
+    write_natspec(out, &item.natspec)?;
+    writeln!(out, "<pre><code>{}</code></pre>", escape(&item.code))?;
+
+    for (kind, title) in CHILDREN {
+        let children = item.items.iter().filter(|child| child.kind == *kind).collect::<Vec<_>>();
+        if !children.is_empty() {
+            writeln!(out, "<h4>{title}</h4>")?;
+            for child in children {
+                write_item(out, child, anchor, true)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_natspec(out: &mut String, natspec: &[JsonComment]) -> std::fmt::Result {
+    let (described, tagged): (Vec<_>, Vec<_>) = natspec
+        .iter()
+        .filter(|comment| comment.tag != "inheritdoc")
+        .partition(|comment| comment.tag == "notice" || comment.tag == "dev");
+    for comment in described {
+        writeln!(out, "<p class=\"{}\">{}</p>", comment.tag, escape(&comment.value))?;
+    }
+    if !tagged.is_empty() {
+        writeln!(out, "<dl>")?;
+        for comment in tagged {
+            writeln!(out, "<dt>@{}</dt><dd>{}</dd>", escape(&comment.tag), escape(&comment.value))?;
+        }
+        writeln!(out, "</dl>")?;
+    }
+    Ok(())
+}
+
+/// Returns the lowercase text matched by the search: the names of the document items and their
+/// NatSpec.
+fn search_text(document: &JsonDocument) -> String {
+    fn collect(item: &JsonItem, text: &mut Vec<String>) {
+        text.push(item.name.to_lowercase());
+        text.extend(item.natspec.iter().map(|comment| comment.value.to_lowercase()));
+        item.items.iter().for_each(|child| collect(child, text));
+    }
+
+    let mut text = vec![document.identity.to_lowercase()];
+    document.items.iter().for_each(|item| collect(item, &mut text));
+    text.join(" ")
+}
+
+/// Returns an anchor-safe version of the given string.
+fn slug(s: &str) -> String {
+    s.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect()
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn item(kind: &str, name: &str, items: Vec<JsonItem>) -> JsonItem {
+        JsonItem {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            signature: name.to_string(),
+            natspec: vec![JsonComment { tag: "notice".to_string(), value: format!("<{name}>") }],
+            code: format!("{kind} {name}"),
+            items,
+        }
+    }
+
+    #[test]
+    fn renders_page() {
+        let documents = [
+            JsonDocument {
+                identity: "Token".to_string(),
+                path: PathBuf::from("src/Token.sol/contract.Token.json"),
+                source: PathBuf::from("src/Token.sol"),
+                git_source: None,
+                inheritance: Vec::new(),
+                deployments: Vec::new(),
+                items: vec![item("contract", "Token", vec![item("function", "transfer", vec![])])],
+            },
+            JsonDocument {
+                identity: "Token".to_string(),
+                path: PathBuf::from("src/Other.sol/contract.Token.json"),
+                source: PathBuf::from("src/Other.sol"),
+                git_source: None,
+                inheritance: Vec::new(),
+                deployments: Vec::new(),
+                items: vec![item("function", "helper", vec![])],
+            },
+        ];
+        let html = Html { title: "Docs & Co", documents: &documents }.as_doc().unwrap();
+
+        assert!(html.contains("<title>Docs &amp; Co</title>"), "{html}");
+        assert!(html.contains("<section id=\"Token\" data-search=\"token token &lt;token&gt; transfer &lt;transfer&gt;\">"), "{html}");
+        assert!(html.contains("<section id=\"Token-1\""), "{html}");
+        assert!(
+            html.contains("<h4>Functions</h4>\n<h3 id=\"Token-transfer\">transfer</h3>"),
+            "{html}"
+        );
+        assert!(html.contains("<p class=\"notice\">&lt;transfer&gt;</p>"), "{html}");
+        assert!(html.contains("<pre><code>function helper</code></pre>"), "{html}");
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    CONTRACT_INHERITANCE_ID, Comment, CommentTag, Comments, DEPLOYMENTS_ID, Deployment, Document,
+    GIT_SOURCE_ID, INHERITDOC_ID, ParseItem, ParseSource, PreprocessorOutput,
+    document::read_context,
+};
+use alloy_primitives::map::HashMap;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// The machine-readable model of a [Document].
+#[derive(Clone, Debug, Serialize)]
+pub struct JsonDocument {
+    /// The document display identity.
+    pub identity: String,
+    /// The path of the JSON document, relative to the `src` output directory.
+    pub path: PathBuf,
+    /// The source file declaring the items, relative to the project root.
+    pub source: PathBuf,
+    /// The git url of the source file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_source: Option<String>,
+    /// The bases of the contract.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub inheritance: Vec<JsonBase>,
+    /// The deployments of the contract.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deployments: Vec<Deployment>,
+    /// The documented items.
+    pub items: Vec<JsonItem>,
+}
+
+impl JsonDocument {
+    /// Creates the JSON model of a document.
+    ///
+    /// Source paths are made relative to `root`.
+    pub fn new(document: &Document, root: &Path) -> Self {
+        let inheritdocs = read_context!(document, INHERITDOC_ID, Inheritdoc);
+
+        let mut inheritance = Vec::new();
+        for item in document.content.iter_items() {
+            if let ParseSource::Contract(ref contract) = item.source {
+                let linked = read_context!(document, CONTRACT_INHERITANCE_ID, ContractInheritance);
+                for base in &contract.base {
+                    let name = base.name.identifiers.last().unwrap().name.clone();
+                    let path = linked
+                        .as_ref()
+                        .and_then(|linked| linked.get(&name))
+                        .map(|path| path.with_extension("json"));
+                    inheritance.push(JsonBase { name, path });
+                }
+            }
+        }
+
+        Self {
+            identity: document.identity.clone(),
+            path: document.relative_output_path().with_extension("json"),
+            source: document.item_path.strip_prefix(root).unwrap_or(&document.item_path).to_owned(),
+            git_source: read_context!(document, GIT_SOURCE_ID, GitSource),
+            inheritance,
+            deployments: read_context!(document, DEPLOYMENTS_ID, Deployments).unwrap_or_default(),
+            items: document
+                .content
+                .iter_items()
+                .map(|item| JsonItem::new(item, &inheritdocs))
+                .collect(),
+        }
+    }
+}
+
+/// A base of a contract.
+#[derive(Clone, Debug, Serialize)]
+pub struct JsonBase {
+    /// The base name.
+    pub name: String,
+    /// The path of the base JSON document, if it is documented.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+/// A documented item, e.g. a contract or a function.
+#[derive(Clone, Debug, Serialize)]
+pub struct JsonItem {
+    /// The item kind, e.g. `contract`, `interface` or `function`.
+    pub kind: String,
+    /// The item name.
+    pub name: String,
+    /// The item signature, which includes the parameter types for functions.
+    pub signature: String,
+    /// The NatSpec comments, including the ones inherited with `@inheritdoc`.
+    pub natspec: Vec<JsonComment>,
+    /// The formatted code of the item.
+    pub code: String,
+    /// The children items.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<Self>,
+}
+
+impl JsonItem {
+    fn new(item: &ParseItem, inheritdocs: &Option<HashMap<String, Comments>>) -> Self {
+        let signature = item.source.signature();
+        let comments = item.comments.merge_inheritdoc(&signature, inheritdocs.clone());
+        Self {
+            kind: match item.source {
+                ParseSource::Contract(ref contract) => contract.ty.to_string(),
+                ParseSource::Function(_) => "function".to_string(),
+                ParseSource::Variable(_) => "variable".to_string(),
+                ParseSource::Event(_) => "event".to_string(),
+                ParseSource::Error(_) => "error".to_string(),
+                ParseSource::Struct(_) => "struct".to_string(),
+                ParseSource::Enum(_) => "enum".to_string(),
+                ParseSource::Type(_) => "type".to_string(),
+            },
+            name: item.source.ident(),
+            signature,
+            natspec: comments.iter().map(JsonComment::from).collect(),
+            code: item.code.clone(),
+            items: item.children.iter().map(|child| Self::new(child, inheritdocs)).collect(),
+        }
+    }
+}
+
+/// A NatSpec comment.
+#[derive(Clone, Debug, Serialize)]
+pub struct JsonComment {
+    /// The tag, e.g. `notice` or `custom:security`.
+    pub tag: String,
+    /// The comment value.
+    pub value: String,
+}
+
+impl From<&Comment> for JsonComment {
+    fn from(comment: &Comment) -> Self {
+        let tag = match &comment.tag {
+            CommentTag::Title => "title".to_string(),
+            CommentTag::Author => "author".to_string(),
+            CommentTag::Notice => "notice".to_string(),
+            CommentTag::Dev => "dev".to_string(),
+            CommentTag::Param => "param".to_string(),
+            CommentTag::Return => "return".to_string(),
+            CommentTag::Inheritdoc => "inheritdoc".to_string(),
+            CommentTag::Custom(tag) => format!("custom:{tag}"),
+        };
+        Self { tag, value: comment.value.clone() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Parser, document::DocumentContent, solang_ext::Visitable};
+
+    #[test]
+    fn models_contract() {
+        let src = r"
+            /// @title Token
+            contract Token is Base {
+                /// @notice Transfers tokens.
+                /// @param to The recipient.
+                /// @custom:security Reentrancy safe.
+                function transfer(address to) external {}
+            }
+        ";
+        let (mut source, comments) = solang_parser::parse(src, 0).unwrap();
+        let mut parser = Parser::new(comments, src.to_owned(), 4);
+        source.visit(&mut parser).unwrap();
+        let item = parser.items().pop().unwrap();
+        let document = Document::new(
+            "/root/src/Token.sol".into(),
+            "/root/docs/src/src/Token.sol/contract.Token.md".into(),
+            false,
+            "/root/docs".into(),
+        )
+        .with_content(DocumentContent::Single(item), "Token".to_string());
+
+        let json = serde_json::to_value(JsonDocument::new(&document, Path::new("/root"))).unwrap();
+        assert_eq!(json["identity"], "Token");
+        assert_eq!(json["path"], "src/Token.sol/contract.Token.json");
+        assert_eq!(json["source"], "src/Token.sol");
+        assert_eq!(json["inheritance"], serde_json::json!([{ "name": "Base" }]));
+
+        let contract = &json["items"][0];
+        assert_eq!(contract["kind"], "contract");
+        assert_eq!(contract["natspec"], serde_json::json!([{ "tag": "title", "value": "Token" }]));
+
+        let function = &contract["items"][0];
+        assert_eq!(function["kind"], "function");
+        assert_eq!(function["signature"], "transfer(address)");
+        assert_eq!(
+            function["natspec"],
+            serde_json::json!([
+                { "tag": "notice", "value": "Transfers tokens." },
+                { "tag": "param", "value": "to The recipient." },
+                { "tag": "custom:security", "value": "Reentrancy safe." },
+            ])
+        );
+    }
+}
@@ -2,10 +2,26 @@
 
 mod as_doc;
 mod buf_writer;
+mod html;
+mod json;
 mod markdown;
 
 pub use as_doc::{AsDoc, AsDocResult};
 pub use buf_writer::BufWriter;
+pub use html::Html;
+pub use json::{JsonBase, JsonComment, JsonDocument, JsonItem};
 pub use markdown::Markdown;
 
 mod traits;
+
+/// The documentation output format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DocFormat {
+    /// An mdbook Markdown tree.
+    #[default]
+    Markdown,
+    /// A JSON document per contract, and an `index.json` listing them.
+    Json,
+    /// A self-contained `index.html` page with client-side search.
+    Html,
+}
@@ -0,0 +1,7 @@
+const search = document.getElementById("search");
+search.addEventListener("input", () => {
+  const query = search.value.trim().toLowerCase();
+  for (const element of document.querySelectorAll("[data-search]")) {
+    element.hidden = query !== "" && !element.dataset.search.includes(query);
+  }
+});
@@ -9,6 +9,7 @@
     invariant::FuzzRunIdentifiedContracts,
     strategies::{EvmFuzzState, mutate_param_value},
 };
+use parking_lot::Mutex;
 use proptest::{
     prelude::{Just, Rng, Strategy},
     prop_oneof,
@@ -85,7 +86,7 @@ pub fn from_tx_seq(tx_seq: &[BasicTxDetails]) -> Self {
     }
 }
 
-#[derive(Serialize, Default)]
+#[derive(Clone, Serialize, Default)]
 pub(crate) struct CorpusMetrics {
     // Number of edges seen during the invariant run.
     cumulative_edges_seen: usize,
@@ -109,6 +110,11 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 impl CorpusMetrics {
+    /// Returns the number of edges seen during the campaign.
+    pub fn edges_seen(&self) -> usize {
+        self.cumulative_edges_seen
+    }
+
     /// Records number of new edges or features explored during the campaign.
     pub fn update_seen(&mut self, is_edge: bool) {
         if is_edge {
@@ -128,44 +134,22 @@ pub fn update_favored(&mut self, is_favored: bool, corpus_favored: bool) {
     }
 }
 
-/// Fuzz corpus manager, used in coverage guided fuzzing mode by both stateless and stateful tests.
-pub(crate) struct CorpusManager {
-    // Fuzzed calls generator.
-    tx_generator: BoxedStrategy<BasicTxDetails>,
-    // Call sequence mutation strategy type generator.
-    mutation_generator: BoxedStrategy<MutationType>,
-    // Corpus configuration.
-    config: FuzzCorpusConfig,
-    // In-memory corpus, populated from persisted files and current runs.
-    // Mutation is performed on these.
-    in_memory_corpus: Vec<CorpusEntry>,
-    // Identifier of current mutated entry.
-    current_mutated: Option<Uuid>,
-    // Number of failed replays from persisted corpus.
-    failed_replays: usize,
-    // History of binned hitcount of edges seen during fuzzing.
+/// Corpus loaded from the corpus directory, replayed to warm up the coverage history.
+struct LoadedCorpus {
+    entries: Vec<CorpusEntry>,
     history_map: Vec<u8>,
-    // Corpus metrics.
-    pub(crate) metrics: CorpusMetrics,
+    metrics: CorpusMetrics,
+    failed_replays: usize,
 }
 
-impl CorpusManager {
-    pub fn new(
-        config: FuzzCorpusConfig,
-        tx_generator: BoxedStrategy<BasicTxDetails>,
+impl LoadedCorpus {
+    /// Loads and replays the persisted corpus, if a corpus directory is configured.
+    fn load(
+        config: &FuzzCorpusConfig,
         executor: &Executor,
         fuzzed_function: Option<&Function>,
         fuzzed_contracts: Option<&FuzzRunIdentifiedContracts>,
     ) -> eyre::Result<Self> {
-        let mutation_generator = prop_oneof![
-            Just(MutationType::Splice),
-            Just(MutationType::Repeat),
-            Just(MutationType::Interleave),
-            Just(MutationType::Prefix),
-            Just(MutationType::Suffix),
-            Just(MutationType::Abi),
-        ]
-        .boxed();
         let mut history_map = vec![0u8; COVERAGE_MAP_SIZE];
         let mut metrics = CorpusMetrics::default();
         let mut in_memory_corpus = vec![];
@@ -173,16 +157,7 @@ pub fn new(
 
         // Early return if corpus dir / coverage guided fuzzing not configured.
         let Some(corpus_dir) = &config.corpus_dir else {
-            return Ok(Self {
-                tx_generator,
-                mutation_generator,
-                config,
-                in_memory_corpus,
-                current_mutated: None,
-                failed_replays,
-                history_map,
-                metrics,
-            });
+            return Ok(Self { entries: in_memory_corpus, history_map, metrics, failed_replays });
         };
 
         // Ensure corpus dir for current test is created.
@@ -200,7 +175,8 @@ pub fn new(
                 })
         };
 
-        'corpus_replay: for entry in std::fs::read_dir(corpus_dir)? {
+        let mut entries = vec![];
+        for entry in std::fs::read_dir(corpus_dir)? {
             let path = entry?.path();
             if path.is_file()
                 && let Some(name) = path.file_name().and_then(|s| s.to_str())
@@ -221,66 +197,221 @@ pub fn new(
             };
 
             if !tx_seq.is_empty() {
-                // Warm up history map from loaded sequences.
-                let mut executor = executor.clone();
-                for tx in &tx_seq {
-                    if can_replay_tx(tx) {
-                        let mut call_result = execute_tx(&mut executor, tx)?;
-                        let (new_coverage, is_edge) =
-                            call_result.merge_edge_coverage(&mut history_map);
-                        if new_coverage {
-                            metrics.update_seen(is_edge);
-                        }
+                entries.push((path, tx_seq));
+            }
+        }
 
-                        // Commit only when running invariant / stateful tests.
-                        if fuzzed_contracts.is_some() {
-                            executor.commit(&mut call_result);
-                        }
-                    } else {
-                        failed_replays += 1;
+        // When minimizing, shorter sequences are replayed first so that longer sequences are only
+        // kept if they reach coverage the shorter ones don't.
+        if config.corpus_minimize {
+            entries.sort_by_key(|(_, tx_seq)| tx_seq.len());
+        }
 
-                        // If the only input for fuzzed function cannot be replied, then move to
-                        // next one without adding it in memory.
-                        if fuzzed_function.is_some() {
-                            continue 'corpus_replay;
-                        }
+        'corpus_replay: for (path, tx_seq) in entries {
+            // Warm up history map from loaded sequences.
+            let mut executor = executor.clone();
+            let mut new_sequence_coverage = false;
+            for tx in &tx_seq {
+                if can_replay_tx(tx) {
+                    let mut call_result = execute_tx(&mut executor, tx)?;
+                    let (new_coverage, is_edge) = call_result.merge_edge_coverage(&mut history_map);
+                    if new_coverage {
+                        metrics.update_seen(is_edge);
+                        new_sequence_coverage = true;
                     }
-                }
 
-                metrics.corpus_count += 1;
+                    // Commit only when running invariant / stateful tests.
+                    if fuzzed_contracts.is_some() {
+                        executor.commit(&mut call_result);
+                    }
+                } else {
+                    failed_replays += 1;
 
-                trace!(
-                    target: "corpus",
-                    "load sequence with len {} from corpus file {}",
-                    tx_seq.len(),
-                    path.display()
-                );
+                    // If the only input for fuzzed function cannot be replied, then move to
+                    // next one without adding it in memory.
+                    if fuzzed_function.is_some() {
+                        continue 'corpus_replay;
+                    }
+                }
+            }
 
-                // Populate in memory corpus with the sequence from corpus file.
-                in_memory_corpus.push(CorpusEntry::new(tx_seq, path)?);
+            if config.corpus_minimize && !new_sequence_coverage {
+                debug!(target: "corpus", "remove redundant corpus file {}", path.display());
+                std::fs::remove_file(&path)?;
+                continue;
             }
+
+            metrics.corpus_count += 1;
+
+            trace!(
+                target: "corpus",
+                "load sequence with len {} from corpus file {}",
+                tx_seq.len(),
+                path.display()
+            );
+
+            // Populate in memory corpus with the sequence from corpus file.
+            in_memory_corpus.push(CorpusEntry::new(tx_seq, path)?);
         }
 
+        Ok(Self { entries: in_memory_corpus, history_map, metrics, failed_replays })
+    }
+}
+
+/// Corpus shared by the parallel workers of an invariant campaign.
+///
+/// The persisted corpus is loaded once for all workers, and call sequences shared by workers are
+/// persisted when synced.
+pub(crate) struct SharedCorpus {
+    // Corpus configuration.
+    config: FuzzCorpusConfig,
+    // Call sequences loaded from disk or that produced new coverage on any worker, in the order
+    // they were synced.
+    entries: Mutex<Vec<Vec<BasicTxDetails>>>,
+    // History of binned hitcount of edges seen by all workers.
+    history_map: Mutex<Vec<u8>>,
+    // Metrics of the corpus loaded from disk.
+    metrics: CorpusMetrics,
+    // Number of failed replays from persisted corpus.
+    failed_replays: usize,
+}
+
+impl SharedCorpus {
+    /// Loads and replays the persisted corpus once for all workers.
+    pub fn load(
+        config: FuzzCorpusConfig,
+        executor: &Executor,
+        fuzzed_contracts: &FuzzRunIdentifiedContracts,
+    ) -> eyre::Result<Self> {
+        let LoadedCorpus { entries, history_map, metrics, failed_replays } =
+            LoadedCorpus::load(&config, executor, None, Some(fuzzed_contracts))?;
+        Ok(Self {
+            config,
+            entries: Mutex::new(entries.into_iter().map(|entry| entry.tx_seq).collect()),
+            history_map: Mutex::new(history_map),
+            metrics,
+            failed_replays,
+        })
+    }
+
+    /// Returns the number of failed replays from persisted corpus.
+    pub fn failed_replays(&self) -> usize {
+        self.failed_replays
+    }
+}
+
+/// Fuzz corpus manager, used in coverage guided fuzzing mode by both stateless and stateful tests.
+pub(crate) struct CorpusManager {
+    // Fuzzed calls generator.
+    tx_generator: BoxedStrategy<BasicTxDetails>,
+    // Call sequence mutation strategy type generator.
+    mutation_generator: BoxedStrategy<MutationType>,
+    // Corpus configuration.
+    config: FuzzCorpusConfig,
+    // In-memory corpus, populated from persisted files and current runs.
+    // Mutation is performed on these.
+    in_memory_corpus: Vec<CorpusEntry>,
+    // Identifier of current mutated entry.
+    current_mutated: Option<Uuid>,
+    // Number of failed replays from persisted corpus.
+    failed_replays: usize,
+    // History of binned hitcount of edges seen during fuzzing.
+    history_map: Vec<u8>,
+    // Corpus metrics.
+    pub(crate) metrics: CorpusMetrics,
+    // Call sequences with new coverage not yet shared with other workers, if syncing is enabled.
+    sync_queue: Option<Vec<Vec<BasicTxDetails>>>,
+    // Number of shared corpus entries already synced.
+    synced_entries: usize,
+}
+
+impl CorpusManager {
+    pub fn new(
+        config: FuzzCorpusConfig,
+        tx_generator: BoxedStrategy<BasicTxDetails>,
+        executor: &Executor,
+        fuzzed_function: Option<&Function>,
+        fuzzed_contracts: Option<&FuzzRunIdentifiedContracts>,
+    ) -> eyre::Result<Self> {
+        let LoadedCorpus { entries, history_map, metrics, failed_replays } =
+            LoadedCorpus::load(&config, executor, fuzzed_function, fuzzed_contracts)?;
         Ok(Self {
             tx_generator,
-            mutation_generator,
+            mutation_generator: mutation_generator(),
             config,
-            in_memory_corpus,
+            in_memory_corpus: entries,
             current_mutated: None,
             failed_replays,
             history_map,
             metrics,
+            sync_queue: None,
+            synced_entries: 0,
         })
     }
 
+    /// Creates the corpus manager of a campaign worker, starting from the shared corpus.
+    ///
+    /// New call sequences are persisted through the shared corpus when synced, see
+    /// [`Self::sync`].
+    pub fn with_shared(tx_generator: BoxedStrategy<BasicTxDetails>, shared: &SharedCorpus) -> Self {
+        let history_map = shared.history_map.lock().clone();
+        let entries = shared.entries.lock();
+        Self {
+            tx_generator,
+            mutation_generator: mutation_generator(),
+            config: shared.config.clone(),
+            in_memory_corpus: entries
+                .iter()
+                .map(|tx_seq| CorpusEntry::from_tx_seq(tx_seq))
+                .collect(),
+            current_mutated: None,
+            failed_replays: 0,
+            history_map,
+            metrics: shared.metrics.clone(),
+            sync_queue: Some(vec![]),
+            synced_entries: entries.len(),
+        }
+    }
+
+    /// Merges edge coverage with the shared corpus, then exchanges and persists the call sequences
+    /// that produced new coverage since the last sync.
+    pub fn sync(&mut self, shared: &SharedCorpus) {
+        let Some(sync_queue) = &mut self.sync_queue else {
+            return;
+        };
+
+        if self.config.collect_edge_coverage() {
+            let mut history_map = shared.history_map.lock();
+            for (global, local) in std::iter::zip(history_map.iter_mut(), &mut self.history_map) {
+                let bucket = (*global).max(*local);
+                *global = bucket;
+                *local = bucket;
+            }
+        }
+
+        let mut entries = shared.entries.lock();
+        for tx_seq in &entries[self.synced_entries..] {
+            self.metrics.corpus_count += 1;
+            self.in_memory_corpus.push(CorpusEntry::from_tx_seq(tx_seq));
+        }
+        // Entries are persisted while holding the lock, so that only one worker writes at a time.
+        for tx_seq in sync_queue.iter() {
+            persist(&self.config, &CorpusEntry::from_tx_seq(tx_seq));
+        }
+        entries.append(sync_queue);
+        self.synced_entries = entries.len();
+
+        trace!(target: "corpus", "synced {} shared corpus entries", self.synced_entries);
+    }
+
     /// Updates stats for the given call sequence, if new coverage produced.
     /// Persists the call sequence (if corpus directory is configured and new coverage) and updates
     /// in-memory corpus.
     pub fn process_inputs(&mut self, inputs: &[BasicTxDetails], new_coverage: bool) {
         // Early return if corpus dir / coverage guided fuzzing is not configured.
-        let Some(corpus_dir) = &self.config.corpus_dir else {
+        if self.config.corpus_dir.is_none() {
             return;
-        };
+        }
 
         // Update stats of current mutated primary corpus.
         if let Some(uuid) = &self.current_mutated {
@@ -312,29 +443,12 @@ pub fn process_inputs(&mut self, inputs: &[BasicTxDetails], new_coverage: bool)
         }
 
         let corpus = CorpusEntry::from_tx_seq(inputs);
-        let corpus_uuid = corpus.uuid;
-
-        // Persist to disk if corpus dir is configured.
-        let write_result = if self.config.corpus_gzip {
-            foundry_common::fs::write_json_gzip_file(
-                corpus_dir.join(format!("{corpus_uuid}{JSON_EXTENSION}.gz")).as_path(),
-                &corpus.tx_seq,
-            )
-        } else {
-            foundry_common::fs::write_json_file(
-                corpus_dir.join(format!("{corpus_uuid}{JSON_EXTENSION}")).as_path(),
-                &corpus.tx_seq,
-            )
-        };
 
-        if let Err(err) = write_result {
-            debug!(target: "corpus", %err, "Failed to record call sequence {:?}", &corpus.tx_seq);
+        // Workers persist new entries when syncing them with the shared corpus.
+        if let Some(sync_queue) = &mut self.sync_queue {
+            sync_queue.push(corpus.tx_seq.clone());
         } else {
-            trace!(
-                target: "corpus",
-                "persisted {} inputs for new coverage in {corpus_uuid} corpus",
-                &corpus.tx_seq.len()
-            );
+            persist(&self.config, &corpus);
         }
 
         // This includes reverting txs in the corpus and `can_continue` removes
@@ -556,6 +670,18 @@ pub fn merge_edge_coverage(&mut self, call_result: &mut RawCallResult) -> bool {
         new_coverage
     }
 
+    /// Feeds the operands of the comparisons executed during the call to the fuzz dictionary, so
+    /// that mutations can satisfy comparisons against values computed at runtime.
+    pub fn collect_cmp_values(&self, call_result: &mut RawCallResult, fuzz_state: &EvmFuzzState) {
+        if !self.config.is_coverage_guided() {
+            return;
+        }
+
+        if let Some(values) = call_result.cmp_values.take() {
+            fuzz_state.collect_cmp_values(values);
+        }
+    }
+
     /// Flush the oldest corpus mutated more than configured max mutations unless they are
     /// favored.
     fn evict_oldest_corpus(&mut self) -> eyre::Result<()> {
@@ -632,6 +758,49 @@ fn abi_mutate(
     }
 }
 
+/// Returns the call sequence mutation strategy type generator.
+fn mutation_generator() -> BoxedStrategy<MutationType> {
+    prop_oneof![
+        Just(MutationType::Splice),
+        Just(MutationType::Repeat),
+        Just(MutationType::Interleave),
+        Just(MutationType::Prefix),
+        Just(MutationType::Suffix),
+        Just(MutationType::Abi),
+    ]
+    .boxed()
+}
+
+/// Persists the call sequence of a corpus entry in the configured corpus directory.
+fn persist(config: &FuzzCorpusConfig, corpus: &CorpusEntry) {
+    let Some(corpus_dir) = &config.corpus_dir else {
+        return;
+    };
+    let corpus_uuid = corpus.uuid;
+
+    let write_result = if config.corpus_gzip {
+        foundry_common::fs::write_json_gzip_file(
+            corpus_dir.join(format!("{corpus_uuid}{JSON_EXTENSION}.gz")).as_path(),
+            &corpus.tx_seq,
+        )
+    } else {
+        foundry_common::fs::write_json_file(
+            corpus_dir.join(format!("{corpus_uuid}{JSON_EXTENSION}")).as_path(),
+            &corpus.tx_seq,
+        )
+    };
+
+    if let Err(err) = write_result {
+        debug!(target: "corpus", %err, "Failed to record call sequence {:?}", &corpus.tx_seq);
+    } else {
+        trace!(
+            target: "corpus",
+            "persisted {} inputs for new coverage in {corpus_uuid} corpus",
+            &corpus.tx_seq.len()
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -679,6 +848,8 @@ fn new_manager_with_single_corpus() -> (CorpusManager, Uuid) {
             failed_replays: 0,
             history_map: vec![0u8; COVERAGE_MAP_SIZE],
             metrics: CorpusMetrics::default(),
+            sync_queue: None,
+            synced_entries: 0,
         };
 
         (manager, seed_uuid)
@@ -777,6 +948,8 @@ fn eviction_skips_favored_and_evicts_non_favored() {
             failed_replays: 0,
             history_map: vec![0u8; COVERAGE_MAP_SIZE],
             metrics: CorpusMetrics::default(),
+            sync_queue: None,
+            synced_entries: 0,
         };
 
         // First eviction should remove the non-favored one
@@ -791,4 +964,41 @@ fn eviction_skips_favored_and_evicts_non_favored() {
         // ensure the evicted one was the non-favored uuid
         assert!(manager.in_memory_corpus.iter().all(|c| c.uuid != non_favored_uuid));
     }
+
+    #[test]
+    fn workers_share_and_persist_entries_once() {
+        let corpus_dir = temp_corpus_dir();
+        let config = FuzzCorpusConfig {
+            corpus_dir: Some(corpus_dir.clone()),
+            corpus_gzip: false,
+            ..Default::default()
+        };
+        let shared = SharedCorpus {
+            config,
+            entries: Mutex::new(vec![vec![basic_tx()]]),
+            history_map: Mutex::new(vec![0u8; COVERAGE_MAP_SIZE]),
+            metrics: CorpusMetrics { corpus_count: 1, ..Default::default() },
+            failed_replays: 0,
+        };
+
+        // Workers start from the loaded corpus.
+        let mut first = CorpusManager::with_shared(Just(basic_tx()).boxed(), &shared);
+        let mut second = CorpusManager::with_shared(Just(basic_tx()).boxed(), &shared);
+        assert_eq!(first.in_memory_corpus.len(), 1);
+        assert_eq!(second.metrics.corpus_count, 1);
+
+        // New entries are only persisted when synced.
+        first.process_inputs(&[basic_tx(), basic_tx()], true);
+        assert_eq!(fs::read_dir(&corpus_dir).unwrap().count(), 0);
+        first.sync(&shared);
+        assert_eq!(fs::read_dir(&corpus_dir).unwrap().count(), 1);
+
+        // Other workers receive the entry without persisting it again.
+        second.sync(&shared);
+        assert_eq!(second.in_memory_corpus.len(), 2);
+        assert_eq!(second.in_memory_corpus[1].tx_seq.len(), 2);
+        first.sync(&shared);
+        assert_eq!(first.in_memory_corpus.len(), 2);
+        assert_eq!(fs::read_dir(&corpus_dir).unwrap().count(), 1);
+    }
 }
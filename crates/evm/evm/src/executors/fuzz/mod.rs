@@ -184,7 +184,7 @@ pub fn fuzz(
                 }
             };
 
-            match self.single_fuzz(address, input, &mut corpus_manager) {
+            match self.single_fuzz(address, input, state, &mut corpus_manager) {
                 Ok(fuzz_outcome) => match fuzz_outcome {
                     FuzzOutcome::Case(case) => {
                         test_data.gas_by_case.push((case.case.gas, case.case.stipend));
@@ -285,6 +285,7 @@ pub fn fuzz(
             gas_report_traces: traces.into_iter().map(|a| a.arena).collect(),
             line_coverage: test_data.coverage,
             deprecated_cheatcodes: test_data.deprecated_cheatcodes,
+            edges_seen: corpus_manager.metrics.edges_seen(),
             failed_corpus_replays: corpus_manager.failed_replays(),
         };
 
@@ -326,6 +327,7 @@ fn single_fuzz(
         &mut self,
         address: Address,
         calldata: Bytes,
+        state: &EvmFuzzState,
         coverage_metrics: &mut CorpusManager,
     ) -> Result<FuzzOutcome, TestCaseError> {
         let mut call = self
@@ -333,6 +335,7 @@ fn single_fuzz(
             .call_raw(self.sender, address, calldata.clone(), U256::ZERO)
             .map_err(|e| TestCaseError::fail(e.to_string()))?;
         let new_coverage = coverage_metrics.merge_edge_coverage(&mut call);
+        coverage_metrics.collect_cmp_values(&mut call, state);
         coverage_metrics.process_inputs(
             &[BasicTxDetails {
                 warp: None,
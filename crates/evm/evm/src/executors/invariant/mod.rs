@@ -32,12 +32,15 @@
 use revm::state::Account;
 use std::{
     collections::{HashMap as Map, btree_map::Entry},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
     time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
 mod error;
-pub use error::{InvariantFailures, InvariantFuzzError};
+pub use error::{FailedInvariantCaseData, InvariantFailures, InvariantFuzzError};
 use foundry_evm_coverage::HitMaps;
 
 mod replay;
@@ -51,10 +54,14 @@
 
 mod shrink;
 use crate::executors::{
-    DURATION_BETWEEN_METRICS_REPORT, EarlyExit, EvmError, FuzzTestTimer, corpus::CorpusManager,
+    DURATION_BETWEEN_METRICS_REPORT, EarlyExit, EvmError, FuzzTestTimer,
+    corpus::{CorpusManager, SharedCorpus},
 };
 pub use shrink::check_sequence;
 
+/// Number of runs after which a worker syncs its corpus with the other workers of the campaign.
+const CORPUS_SYNC_INTERVAL: u32 = 16;
+
 sol! {
     interface IInvariantTest {
         #[derive(Default)]
@@ -180,6 +187,25 @@ fn new(
         Self { fuzz_state, targeted_contracts, test_data }
     }
 
+    /// Creates the test of an additional campaign worker. The fuzz dictionary and targeted
+    /// contracts are copied, as they change during runs.
+    fn fork(&self, branch_runner: TestRunner) -> Self {
+        let targeted_contracts = FuzzRunIdentifiedContracts::new(
+            self.targeted_contracts.targets.lock().clone(),
+            self.targeted_contracts.is_updatable,
+        );
+        let mut invariant_test = Self::new(
+            self.fuzz_state.fork(),
+            targeted_contracts,
+            InvariantFailures::new(),
+            None,
+            branch_runner,
+        );
+        // The initial case is only recorded once, by the first worker.
+        invariant_test.test_data.fuzz_cases.clear();
+        invariant_test
+    }
+
     /// Returns number of invariant test reverts.
     fn reverts(&self) -> usize {
         self.test_data.failures.reverts
@@ -282,83 +308,105 @@ fn new(first_input: BasicTxDetails, executor: Executor, depth: usize) -> Self {
     }
 }
 
-/// Wrapper around any [`Executor`] implementer which provides fuzzing support using [`proptest`].
-///
-/// After instantiation, calling `invariant_fuzz` will proceed to hammer the deployed smart
-/// contracts with inputs, until it finds a counterexample sequence. The provided [`TestRunner`]
-/// contains all the configuration which can be overridden via [environment
-/// variables](proptest::test_runner::Config)
-pub struct InvariantExecutor<'a> {
-    pub executor: Executor,
-    /// Proptest runner.
-    runner: TestRunner,
-    /// The invariant configuration
-    config: InvariantConfig,
-    /// Contracts deployed with `setUp()`
+/// State shared by the workers of an invariant campaign.
+struct InvariantCampaign<'a, 'b> {
+    invariant_contract: &'a InvariantContract<'b>,
+    fuzz_fixtures: &'a FuzzFixtures,
+    config: &'a InvariantConfig,
     setup_contracts: &'a ContractsByAddress,
-    /// Contracts that are part of the project but have not been deployed yet. We need the bytecode
-    /// to identify them from the stateset changes.
     project_contracts: &'a ContractsByArtifact,
-    /// Filters contracts to be fuzzed through their artifact identifiers.
-    artifact_filters: ArtifactFilters,
+    artifact_filters: &'a ArtifactFilters,
+    progress: Option<&'a ProgressBar>,
+    early_exit: &'a EarlyExit,
+    timer: FuzzTestTimer,
+    // Number of runs started by all workers.
+    runs: AtomicU32,
+    // Whether a worker broke the invariant or errored, stopping all other workers.
+    failed: AtomicBool,
+    // Corpus synced between workers, if running more than one.
+    shared_corpus: Option<SharedCorpus>,
+    // Fuzz state used to exchange the values kept across runs between workers, if running more
+    // than one.
+    shared_fuzz_state: Option<EvmFuzzState>,
 }
 
-impl<'a> InvariantExecutor<'a> {
-    /// Instantiates a fuzzed executor EVM given a testrunner
-    pub fn new(
+/// Data collected by a single worker of an invariant campaign.
+struct InvariantWorkerResult {
+    invariant_test: InvariantTest,
+    // Number of failed replays from persisted corpus.
+    failed_corpus_replays: usize,
+    // Number of edges seen by the worker.
+    edges_seen: usize,
+}
+
+impl InvariantCampaign<'_, '_> {
+    /// Runs the campaign on a single worker.
+    fn run_worker(
+        &self,
+        id: usize,
         executor: Executor,
-        runner: TestRunner,
-        config: InvariantConfig,
-        setup_contracts: &'a ContractsByAddress,
-        project_contracts: &'a ContractsByArtifact,
-    ) -> Self {
-        Self {
-            executor,
-            runner,
-            config,
-            setup_contracts,
-            project_contracts,
-            artifact_filters: ArtifactFilters::default(),
+        mut invariant_test: InvariantTest,
+        senders: SenderFilters,
+    ) -> Result<InvariantWorkerResult> {
+        let result = self.fuzz(id, executor, &mut invariant_test, senders);
+        if result.is_err() || invariant_test.has_errors() {
+            self.failed.store(true, Ordering::Relaxed);
         }
+        result.map(|corpus_manager| InvariantWorkerResult {
+            invariant_test,
+            edges_seen: corpus_manager.metrics.edges_seen(),
+            failed_corpus_replays: corpus_manager.failed_replays(),
+        })
     }
 
-    pub fn config(self) -> InvariantConfig {
-        self.config
-    }
-
-    /// Fuzzes any deployed contract and checks any broken invariant at `invariant_address`.
-    pub fn invariant_fuzz(
-        &mut self,
-        invariant_contract: InvariantContract<'_>,
-        fuzz_fixtures: &FuzzFixtures,
-        fuzz_state: EvmFuzzState,
-        progress: Option<&ProgressBar>,
-        early_exit: &EarlyExit,
-    ) -> Result<InvariantFuzzTestResult> {
-        // Throw an error to abort test run if the invariant function accepts input params
-        if !invariant_contract.invariant_function.inputs.is_empty() {
-            return Err(eyre!("Invariant test function should have no inputs"));
+    /// Claims the next run. Returns `false` if the campaign should stop.
+    fn next_run(&self) -> bool {
+        if self.early_exit.should_stop() || self.failed.load(Ordering::Relaxed) {
+            return false;
         }
 
-        let (mut invariant_test, mut corpus_manager) =
-            self.prepare_test(&invariant_contract, fuzz_fixtures, fuzz_state)?;
-
-        // Start timer for this invariant test.
-        let mut runs = 0;
-        let timer = FuzzTestTimer::new(self.config.timeout);
-        let mut last_metrics_report = Instant::now();
-        let continue_campaign = |runs: u32| {
-            if early_exit.should_stop() {
-                return false;
-            }
+        if self.timer.is_enabled() {
+            !self.timer.is_timed_out()
+        } else {
+            self.runs.fetch_add(1, Ordering::Relaxed) < self.config.runs
+        }
+    }
 
-            if timer.is_enabled() { !timer.is_timed_out() } else { runs < self.config.runs }
+    /// Executes runs until the campaign ends, returning the corpus of the worker.
+    fn fuzz(
+        &self,
+        id: usize,
+        executor: Executor,
+        invariant_test: &mut InvariantTest,
+        senders: SenderFilters,
+    ) -> Result<CorpusManager> {
+        // Creates the invariant strategy.
+        let strategy = invariant_strat(
+            invariant_test.fuzz_state.clone(),
+            senders,
+            invariant_test.targeted_contracts.clone(),
+            self.config.clone(),
+            self.fuzz_fixtures.clone(),
+        )
+        .no_shrink();
+        // The persisted corpus is loaded once for all workers.
+        let mut corpus_manager = match &self.shared_corpus {
+            Some(shared_corpus) => CorpusManager::with_shared(strategy.boxed(), shared_corpus),
+            None => CorpusManager::new(
+                self.config.corpus.clone(),
+                strategy.boxed(),
+                &executor,
+                None,
+                Some(&invariant_test.targeted_contracts),
+            )?,
         };
 
+        let mut worker_runs = 0;
+        let mut last_metrics_report = Instant::now();
         // Invariant runs with edge coverage if corpus dir is set or showing edge coverage.
         let edge_coverage_enabled = self.config.corpus.collect_edge_coverage();
 
-        'stop: while continue_campaign(runs) {
+        'stop: while self.next_run() {
             let initial_seq = corpus_manager.new_inputs(
                 &mut invariant_test.test_data.branch_runner,
                 &invariant_test.fuzz_state,
@@ -369,7 +417,7 @@ pub fn invariant_fuzz(
             let mut current_run = InvariantTestRun::new(
                 initial_seq[0].clone(),
                 // Before each run, we must reset the backend state.
-                self.executor.clone(),
+                executor.clone(),
                 self.config.depth as usize,
             );
 
@@ -379,8 +427,8 @@ pub fn invariant_fuzz(
             }
 
             while current_run.depth < self.config.depth {
-                // Check if the timeout has been reached.
-                if timer.is_timed_out() {
+                // Check if the timeout has been reached or another worker failed.
+                if self.timer.is_timed_out() || self.failed.load(Ordering::Relaxed) {
                     // Since we never record a revert here the test is still considered
                     // successful even though it timed out. We *want*
                     // this behavior for now, so that's ok, but
@@ -407,6 +455,7 @@ pub fn invariant_fuzz(
                 if corpus_manager.merge_edge_coverage(&mut call_result) {
                     current_run.new_coverage = true;
                 }
+                corpus_manager.collect_cmp_values(&mut call_result, &invariant_test.fuzz_state);
 
                 if discarded {
                     current_run.inputs.pop();
@@ -431,7 +480,7 @@ pub fn invariant_fuzz(
                     let mut state_changeset = call_result.state_changeset.clone();
                     if !call_result.reverted {
                         collect_data(
-                            &invariant_test,
+                            invariant_test,
                             &mut state_changeset,
                             tx,
                             &call_result,
@@ -446,7 +495,7 @@ pub fn invariant_fuzz(
                             &state_changeset,
                             self.project_contracts,
                             self.setup_contracts,
-                            &self.artifact_filters,
+                            self.artifact_filters,
                             &mut current_run.created_contracts,
                         )
                     {
@@ -460,10 +509,10 @@ pub fn invariant_fuzz(
 
                     // Determine if test can continue or should exit.
                     let result = can_continue(
-                        &invariant_contract,
-                        &mut invariant_test,
+                        self.invariant_contract,
+                        invariant_test,
                         &mut current_run,
-                        &self.config,
+                        self.config,
                         call_result,
                         &state_changeset,
                     )
@@ -492,26 +541,36 @@ pub fn invariant_fuzz(
             corpus_manager.process_inputs(&current_run.inputs, current_run.new_coverage);
 
             // Call `afterInvariant` only if it is declared and test didn't fail already.
-            if invariant_contract.call_after_invariant && !invariant_test.has_errors() {
+            if self.invariant_contract.call_after_invariant && !invariant_test.has_errors() {
                 assert_after_invariant(
-                    &invariant_contract,
-                    &mut invariant_test,
+                    self.invariant_contract,
+                    invariant_test,
                     &current_run,
-                    &self.config,
+                    self.config,
                 )
                 .map_err(|_| eyre!("Failed to call afterInvariant"))?;
             }
 
             // End current invariant test run.
             invariant_test.end_run(current_run, self.config.gas_report_samples as usize);
-            if let Some(progress) = progress {
+            worker_runs += 1;
+
+            // Share new corpus entries, coverage and fuzz dictionary values with the other
+            // workers.
+            if worker_runs % CORPUS_SYNC_INTERVAL == 0 {
+                self.sync(&mut corpus_manager, invariant_test);
+            }
+
+            // Metrics are only reported by the first worker.
+            if let Some(progress) = self.progress {
                 // If running with progress then increment completed runs.
                 progress.inc(1);
                 // Display metrics in progress bar.
-                if edge_coverage_enabled {
+                if edge_coverage_enabled && id == 0 {
                     progress.set_message(format!("{}", &corpus_manager.metrics));
                 }
             } else if edge_coverage_enabled
+                && id == 0
                 && last_metrics_report.elapsed() > DURATION_BETWEEN_METRICS_REPORT
             {
                 // Display metrics inline if corpus dir set.
@@ -519,56 +578,239 @@ pub fn invariant_fuzz(
                     "timestamp": SystemTime::now()
                         .duration_since(UNIX_EPOCH)?
                         .as_secs(),
-                    "invariant": invariant_contract.invariant_function.name,
+                    "invariant": self.invariant_contract.invariant_function.name,
                     "metrics": &corpus_manager.metrics,
                 });
                 let _ = sh_println!("{}", serde_json::to_string(&metrics)?);
                 last_metrics_report = Instant::now();
             }
+        }
+
+        // Share and persist the corpus entries found since the last sync.
+        self.sync(&mut corpus_manager, invariant_test);
+
+        Ok(corpus_manager)
+    }
+
+    /// Syncs the corpus and fuzz dictionary of a worker with the other workers.
+    fn sync(&self, corpus_manager: &mut CorpusManager, invariant_test: &InvariantTest) {
+        if let Some(shared_corpus) = &self.shared_corpus {
+            corpus_manager.sync(shared_corpus);
+        }
+        if let Some(shared_fuzz_state) = &self.shared_fuzz_state {
+            invariant_test.fuzz_state.sync(shared_fuzz_state);
+        }
+    }
+}
+
+/// Merges the data collected by the workers of a campaign into a single result. The data of the
+/// worker that broke the invariant takes precedence.
+fn merge_workers(
+    mut workers: Vec<InvariantWorkerResult>,
+    gas_samples: usize,
+) -> InvariantFuzzTestResult {
+    if let Some(index) = workers.iter().position(|w| w.invariant_test.has_errors()) {
+        workers.swap(0, index);
+    }
+
+    // Edge coverage is synced between workers, the most advanced one is reported.
+    let edges_seen = workers.iter().map(|w| w.edges_seen).max().unwrap_or_default();
+    let failed_corpus_replays = workers.iter().map(|w| w.failed_corpus_replays).sum();
+    let mut workers = workers.into_iter();
+    let InvariantWorkerResult { invariant_test, .. } = workers.next().expect("no invariant worker");
+    invariant_test.fuzz_state.log_stats();
+
+    let mut result = invariant_test.test_data;
+    for worker in workers {
+        let data = worker.invariant_test.test_data;
+        result.failures.reverts += data.failures.reverts;
+        result.fuzz_cases.extend(data.fuzz_cases);
+        result.gas_report_traces.extend(data.gas_report_traces);
+        HitMaps::merge_opt(&mut result.line_coverage, data.line_coverage);
+        for (selector, metrics) in data.metrics {
+            let merged = result.metrics.entry(selector).or_default();
+            merged.calls += metrics.calls;
+            merged.reverts += metrics.reverts;
+            merged.discards += metrics.discards;
+        }
+    }
+    result.gas_report_traces.truncate(gas_samples);
+
+    InvariantFuzzTestResult {
+        error: result.failures.error,
+        cases: result.fuzz_cases,
+        reverts: result.failures.reverts,
+        last_run_inputs: result.last_run_inputs,
+        gas_report_traces: result.gas_report_traces,
+        line_coverage: result.line_coverage,
+        metrics: result.metrics,
+        failed_corpus_replays,
+        edges_seen,
+    }
+}
 
-            runs += 1;
+/// Wrapper around any [`Executor`] implementer which provides fuzzing support using [`proptest`].
+///
+/// After instantiation, calling `invariant_fuzz` will proceed to hammer the deployed smart
+/// contracts with inputs, until it finds a counterexample sequence. The provided [`TestRunner`]
+/// contains all the configuration which can be overridden via [environment
+/// variables](proptest::test_runner::Config)
+pub struct InvariantExecutor<'a> {
+    pub executor: Executor,
+    /// Proptest runner.
+    runner: TestRunner,
+    /// The invariant configuration
+    config: InvariantConfig,
+    /// Contracts deployed with `setUp()`
+    setup_contracts: &'a ContractsByAddress,
+    /// Contracts that are part of the project but have not been deployed yet. We need the bytecode
+    /// to identify them from the stateset changes.
+    project_contracts: &'a ContractsByArtifact,
+    /// Filters contracts to be fuzzed through their artifact identifiers.
+    artifact_filters: ArtifactFilters,
+}
+
+impl<'a> InvariantExecutor<'a> {
+    /// Instantiates a fuzzed executor EVM given a testrunner
+    pub fn new(
+        executor: Executor,
+        runner: TestRunner,
+        config: InvariantConfig,
+        setup_contracts: &'a ContractsByAddress,
+        project_contracts: &'a ContractsByArtifact,
+    ) -> Self {
+        Self {
+            executor,
+            runner,
+            config,
+            setup_contracts,
+            project_contracts,
+            artifact_filters: ArtifactFilters::default(),
         }
+    }
+
+    pub fn config(self) -> InvariantConfig {
+        self.config
+    }
+
+    /// Fuzzes any deployed contract and checks any broken invariant at `invariant_address`.
+    ///
+    /// If more than one thread is configured, runs are split between parallel workers that
+    /// periodically sync their corpus, edge coverage and the fuzz dictionary values kept across
+    /// runs. The persisted corpus is loaded once and shared by all workers.
+    pub fn invariant_fuzz(
+        &mut self,
+        invariant_contract: InvariantContract<'_>,
+        fuzz_fixtures: &FuzzFixtures,
+        fuzz_state: EvmFuzzState,
+        progress: Option<&ProgressBar>,
+        early_exit: &EarlyExit,
+    ) -> Result<InvariantFuzzTestResult> {
+        // Throw an error to abort test run if the invariant function accepts input params
+        if !invariant_contract.invariant_function.inputs.is_empty() {
+            return Err(eyre!("Invariant test function should have no inputs"));
+        }
+
+        let (invariant_test, senders) =
+            self.prepare_test(&invariant_contract, fuzz_fixtures, fuzz_state)?;
+
+        // Additional workers use their own deterministic random generator.
+        let threads = self.worker_threads();
+        let shared_corpus = if threads > 1 {
+            Some(SharedCorpus::load(
+                self.config.corpus.clone(),
+                &self.executor,
+                &invariant_test.targeted_contracts,
+            )?)
+        } else {
+            None
+        };
+        let shared_fuzz_state = (threads > 1).then(|| invariant_test.fuzz_state.fork());
+        let mut worker_tests = Vec::with_capacity(threads);
+        for _ in 1..threads {
+            let branch_runner =
+                TestRunner::new_with_rng(self.runner.config().clone(), self.runner.new_rng());
+            worker_tests.push(invariant_test.fork(branch_runner));
+        }
+        worker_tests.insert(0, invariant_test);
+
+        let campaign = InvariantCampaign {
+            invariant_contract: &invariant_contract,
+            fuzz_fixtures,
+            config: &self.config,
+            setup_contracts: self.setup_contracts,
+            project_contracts: self.project_contracts,
+            artifact_filters: &self.artifact_filters,
+            progress,
+            early_exit,
+            timer: FuzzTestTimer::new(self.config.timeout),
+            runs: AtomicU32::new(0),
+            failed: AtomicBool::new(false),
+            shared_corpus,
+            shared_fuzz_state,
+        };
+
+        let workers = if threads == 1 {
+            let invariant_test = worker_tests.pop().unwrap();
+            vec![campaign.run_worker(0, self.executor.clone(), invariant_test, senders)?]
+        } else {
+            let campaign = &campaign;
+            std::thread::scope(|scope| {
+                let handles = worker_tests
+                    .into_iter()
+                    .enumerate()
+                    .map(|(id, invariant_test)| {
+                        let executor = self.executor.clone();
+                        let senders = senders.clone();
+                        scope.spawn(move || {
+                            campaign.run_worker(id, executor, invariant_test, senders)
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle.join().unwrap_or_else(|_| Err(eyre!("invariant worker panicked")))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })?
+        };
 
         trace!(?fuzz_fixtures);
-        invariant_test.fuzz_state.log_stats();
-
-        let result = invariant_test.test_data;
-        Ok(InvariantFuzzTestResult {
-            error: result.failures.error,
-            cases: result.fuzz_cases,
-            reverts: result.failures.reverts,
-            last_run_inputs: result.last_run_inputs,
-            gas_report_traces: result.gas_report_traces,
-            line_coverage: result.line_coverage,
-            metrics: result.metrics,
-            failed_corpus_replays: corpus_manager.failed_replays(),
-        })
+        let mut result = merge_workers(workers, self.config.gas_report_samples as usize);
+        if let Some(shared_corpus) = &campaign.shared_corpus {
+            result.failed_corpus_replays += shared_corpus.failed_replays();
+        }
+        Ok(result)
+    }
+
+    /// Returns the number of parallel workers to run the campaign with.
+    fn worker_threads(&self) -> usize {
+        // Overridden calls are generated by a single runner, shared by all executor clones.
+        if self.config.call_override {
+            return 1;
+        }
+
+        match self.config.threads {
+            0 => std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+            threads => threads as usize,
+        }
     }
 
     /// Prepares certain structures to execute the invariant tests:
     /// * Invariant Fuzz Test.
-    /// * Invariant Corpus Manager.
+    /// * Targeted senders, used to build the invariant strategy of each worker.
     fn prepare_test(
         &mut self,
         invariant_contract: &InvariantContract<'_>,
         fuzz_fixtures: &FuzzFixtures,
         fuzz_state: EvmFuzzState,
-    ) -> Result<(InvariantTest, CorpusManager)> {
+    ) -> Result<(InvariantTest, SenderFilters)> {
         // Finds out the chosen deployed contracts and/or senders.
         self.select_contract_artifacts(invariant_contract.address)?;
         let (targeted_senders, targeted_contracts) =
             self.select_contracts_and_senders(invariant_contract.address)?;
 
-        // Creates the invariant strategy.
-        let strategy = invariant_strat(
-            fuzz_state.clone(),
-            targeted_senders,
-            targeted_contracts.clone(),
-            self.config.clone(),
-            fuzz_fixtures.clone(),
-        )
-        .no_shrink();
-
         // Allows `override_call_strat` to use the address given by the Fuzzer inspector during
         // EVM execution.
         let mut call_generator = None;
@@ -620,13 +862,6 @@ fn prepare_test(
             return Err(eyre!(error.revert_reason().unwrap_or_default()));
         }
 
-        let corpus_manager = CorpusManager::new(
-            self.config.corpus.clone(),
-            strategy.boxed(),
-            &self.executor,
-            None,
-            Some(&targeted_contracts),
-        )?;
         let invariant_test = InvariantTest::new(
             fuzz_state,
             targeted_contracts,
@@ -635,7 +870,7 @@ fn prepare_test(
             self.runner.clone(),
         );
 
-        Ok((invariant_test, corpus_manager))
+        Ok((invariant_test, targeted_senders))
     }
 
     /// Fills the `InvariantExecutor` with the artifact identifier filters (in `path:name` string
@@ -901,19 +1136,10 @@ pub fn select_selectors(
             let selectors: Vec<_> = target
                 .abi
                 .functions()
-                .filter_map(|func| {
-                    if matches!(
-                        func.state_mutability,
-                        alloy_json_abi::StateMutability::Pure
-                            | alloy_json_abi::StateMutability::View
-                    ) || func.is_reserved()
-                        || excluded_test_selectors.contains(&func.selector())
-                    {
-                        None
-                    } else {
-                        Some(func.selector())
-                    }
+                .filter(|func| {
+                    is_fuzzed_function(func) && !excluded_test_selectors.contains(&func.selector())
                 })
+                .map(|func| func.selector())
                 .collect();
             self.add_address_with_functions(address, &selectors, false, targeted_contracts)?;
         }
@@ -1044,3 +1270,12 @@ pub(crate) fn execute_tx(executor: &mut Executor, tx: &BasicTxDetails) -> Result
     }
     Ok(call_result)
 }
+
+/// Whether the function of a targeted contract is fuzzed when no selector is explicitly targeted:
+/// state-changing functions that are not reserved.
+pub fn is_fuzzed_function(func: &alloy_json_abi::Function) -> bool {
+    !matches!(
+        func.state_mutability,
+        alloy_json_abi::StateMutability::Pure | alloy_json_abi::StateMutability::View
+    ) && !func.is_reserved()
+}
@@ -102,8 +102,15 @@ pub fn replay_error(
     early_exit: &EarlyExit,
 ) -> Result<Vec<BaseCounterExample>> {
     // Shrink sequence of failed calls.
-    let calls =
-        shrink_sequence(&config, invariant_contract, calls, &executor, progress, early_exit)?;
+    let calls = shrink_sequence(
+        &config,
+        invariant_contract,
+        calls,
+        &executor,
+        &ided_contracts,
+        progress,
+        early_exit,
+    )?;
 
     if let Some(sequence) = inner_sequence {
         set_up_inner_replay(&mut executor, &sequence);
@@ -34,6 +34,8 @@ pub struct InvariantFuzzTestResult {
     pub metrics: HashMap<String, InvariantMetrics>,
     /// NUmber of failed replays from persisted corpus.
     pub failed_corpus_replays: usize,
+    /// Number of edges seen with edge coverage enabled.
+    pub edges_seen: usize,
 }
 
 /// Enriched results of an invariant run check.
@@ -2,7 +2,9 @@
     EarlyExit, Executor,
     invariant::{call_after_invariant_function, call_invariant_function, execute_tx},
 };
-use alloy_primitives::{Address, Bytes};
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_primitives::{Address, B256, Bytes, I256, U256};
+use foundry_common::ContractsByAddress;
 use foundry_config::InvariantConfig;
 use foundry_evm_core::constants::MAGIC_ASSUME;
 use foundry_evm_fuzz::{BasicTxDetails, invariant::InvariantContract};
@@ -32,11 +34,16 @@ fn current(&self) -> impl Iterator<Item = usize> + '_ {
     }
 }
 
+/// Shrinks a failing call sequence in two phases.
+///
+/// First removes calls that are not needed to break the invariant, then simplifies the arguments
+/// and block adjustments of the remaining calls. Both phases share the `shrink_run_limit` budget.
 pub(crate) fn shrink_sequence(
     config: &InvariantConfig,
     invariant_contract: &InvariantContract<'_>,
     calls: &[BasicTxDetails],
     executor: &Executor,
+    ided_contracts: &ContractsByAddress,
     progress: Option<&ProgressBar>,
     early_exit: &EarlyExit,
 ) -> eyre::Result<Vec<BasicTxDetails>> {
@@ -59,12 +66,14 @@ pub(crate) fn shrink_sequence(
     }
 
     let mut call_idx = 0;
+    let mut runs = 0;
 
     let mut shrinker = CallSequenceShrinker::new(calls.len());
-    for _ in 0..config.shrink_run_limit {
+    while runs < config.shrink_run_limit {
         if early_exit.should_stop() {
             break;
         }
+        runs += 1;
 
         // Remove call at current index.
         shrinker.included_calls.clear(call_idx);
@@ -97,7 +106,240 @@ pub(crate) fn shrink_sequence(
         };
     }
 
-    Ok(shrinker.current().map(|idx| &calls[idx]).cloned().collect())
+    let calls = shrinker.current().map(|idx| &calls[idx]).cloned().collect();
+    let is_failing = |calls: &[BasicTxDetails]| {
+        matches!(
+            check_sequence(
+                executor.clone(),
+                calls,
+                (0..calls.len()).collect(),
+                target_address,
+                calldata.clone(),
+                config.fail_on_revert,
+                invariant_contract.call_after_invariant,
+            ),
+            Ok((false, _))
+        )
+    };
+    Ok(ArgumentShrinker::new(calls, ided_contracts, config.shrink_run_limit - runs)
+        .shrink(progress, early_exit, is_failing))
+}
+
+/// Shrinker for the calls of a failing sequence.
+///
+/// Replaces the ABI-encoded arguments and the `warp`/`roll` delays of each call by simpler
+/// values, keeping every replacement that still breaks the invariant, until no call can be
+/// simplified further or the run budget is exhausted.
+struct ArgumentShrinker<'a> {
+    /// The shrunk call sequence.
+    calls: Vec<BasicTxDetails>,
+    /// The contracts used to decode the calldata of the calls.
+    ided_contracts: &'a ContractsByAddress,
+    /// Addresses arguments can be replaced with, simplest first.
+    addresses: Vec<Address>,
+    /// Remaining number of candidate sequences to check.
+    runs: u32,
+}
+
+impl<'a> ArgumentShrinker<'a> {
+    fn new(calls: Vec<BasicTxDetails>, ided_contracts: &'a ContractsByAddress, runs: u32) -> Self {
+        let mut addresses = Vec::new();
+        let known = ided_contracts
+            .keys()
+            .copied()
+            .chain(calls.iter().flat_map(|tx| [tx.sender, tx.call_details.target]));
+        for address in known {
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+        Self { calls, ided_contracts, addresses, runs }
+    }
+
+    fn shrink(
+        mut self,
+        progress: Option<&ProgressBar>,
+        early_exit: &EarlyExit,
+        mut is_failing: impl FnMut(&[BasicTxDetails]) -> bool,
+    ) -> Vec<BasicTxDetails> {
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for idx in 0..self.calls.len() {
+                // Keep simplifying the call until none of its candidates breaks the invariant.
+                'call: loop {
+                    for candidate in self.candidates(&self.calls[idx]) {
+                        if self.runs == 0 || early_exit.should_stop() {
+                            return self.calls;
+                        }
+                        self.runs -= 1;
+                        if let Some(progress) = progress {
+                            progress.inc(1);
+                        }
+
+                        let original = std::mem::replace(&mut self.calls[idx], candidate);
+                        if is_failing(&self.calls) {
+                            improved = true;
+                            continue 'call;
+                        }
+                        self.calls[idx] = original;
+                    }
+                    break;
+                }
+            }
+        }
+        self.calls
+    }
+
+    /// Returns the simplifications of `tx`, each changing a single delay or argument.
+    fn candidates(&self, tx: &BasicTxDetails) -> Vec<BasicTxDetails> {
+        let mut candidates = Vec::new();
+        if let Some(warp) = tx.warp {
+            candidates.extend(shrink_uint(warp).into_iter().map(|warp| BasicTxDetails {
+                warp: (!warp.is_zero()).then_some(warp),
+                ..tx.clone()
+            }));
+        }
+        if let Some(roll) = tx.roll {
+            candidates.extend(shrink_uint(roll).into_iter().map(|roll| BasicTxDetails {
+                roll: (!roll.is_zero()).then_some(roll),
+                ..tx.clone()
+            }));
+        }
+
+        let calldata = &tx.call_details.calldata;
+        let Some(function) =
+            self.ided_contracts.get(&tx.call_details.target).and_then(|(_, abi)| {
+                abi.functions().find(|f| calldata.len() >= 4 && f.selector() == calldata[..4])
+            })
+        else {
+            return candidates;
+        };
+        let Ok(args) = function.abi_decode_input(&calldata[4..]) else { return candidates };
+        for (i, arg) in args.iter().enumerate() {
+            for simpler in simplify_value(arg, &self.addresses) {
+                let mut args = args.clone();
+                args[i] = simpler;
+                if let Ok(calldata) = function.abi_encode_input(&args) {
+                    let mut candidate = tx.clone();
+                    candidate.call_details.calldata = calldata.into();
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates
+    }
+}
+
+/// Returns values simpler than `value`, simplest first.
+///
+/// Integers shrink toward zero, addresses toward the first of `addresses`, and arrays, bytes and
+/// strings toward shorter ones.
+fn simplify_value(value: &DynSolValue, addresses: &[Address]) -> Vec<DynSolValue> {
+    match value {
+        DynSolValue::Bool(true) => vec![DynSolValue::Bool(false)],
+        DynSolValue::Uint(v, size) => {
+            shrink_uint(*v).into_iter().map(|v| DynSolValue::Uint(v, *size)).collect()
+        }
+        DynSolValue::Int(v, size) => {
+            shrink_int(*v).into_iter().map(|v| DynSolValue::Int(v, *size)).collect()
+        }
+        DynSolValue::Address(address) => {
+            let rank = addresses.iter().position(|a| a == address).unwrap_or(addresses.len());
+            addresses[..rank].iter().map(|a| DynSolValue::Address(*a)).collect()
+        }
+        DynSolValue::FixedBytes(word, size) if !word.is_zero() => {
+            vec![DynSolValue::FixedBytes(B256::ZERO, *size)]
+        }
+        DynSolValue::Bytes(bytes) => {
+            shorter(bytes).into_iter().map(|b| DynSolValue::Bytes(b.to_vec())).collect()
+        }
+        DynSolValue::String(s) => {
+            let chars = s.chars().collect::<Vec<_>>();
+            shorter(&chars)
+                .into_iter()
+                .map(|chars| DynSolValue::String(chars.iter().collect()))
+                .collect()
+        }
+        DynSolValue::Array(values) => {
+            let mut simpler = shorter(values)
+                .into_iter()
+                .map(|values| DynSolValue::Array(values.to_vec()))
+                .collect::<Vec<_>>();
+            simpler.extend(simplify_elements(values, addresses).map(DynSolValue::Array));
+            simpler
+        }
+        DynSolValue::FixedArray(values) => {
+            simplify_elements(values, addresses).map(DynSolValue::FixedArray).collect()
+        }
+        DynSolValue::Tuple(values) => {
+            simplify_elements(values, addresses).map(DynSolValue::Tuple).collect()
+        }
+        _ => vec![],
+    }
+}
+
+/// Returns copies of `values`, each with a single element simplified.
+fn simplify_elements<'a>(
+    values: &'a [DynSolValue],
+    addresses: &'a [Address],
+) -> impl Iterator<Item = Vec<DynSolValue>> + 'a {
+    values.iter().enumerate().flat_map(move |(i, value)| {
+        simplify_value(value, addresses).into_iter().map(move |simpler| {
+            let mut values = values.to_vec();
+            values[i] = simpler;
+            values
+        })
+    })
+}
+
+/// Returns prefixes of `items`, shortest first: empty, first half and all but the last item.
+fn shorter<T>(items: &[T]) -> Vec<&[T]> {
+    let mut prefixes = Vec::new();
+    if !items.is_empty() {
+        prefixes.push(&items[..0]);
+    }
+    if items.len() > 1 {
+        prefixes.push(&items[..items.len() / 2]);
+    }
+    if items.len() > 2 {
+        prefixes.push(&items[..items.len() - 1]);
+    }
+    prefixes
+}
+
+/// Returns unsigned integers smaller than `v`: zero, one and half of `v`.
+fn shrink_uint(v: U256) -> Vec<U256> {
+    let mut simpler = Vec::new();
+    if !v.is_zero() {
+        simpler.push(U256::ZERO);
+    }
+    if v > U256::ONE {
+        simpler.push(U256::ONE);
+    }
+    if v > U256::from(2) {
+        simpler.push(v >> 1);
+    }
+    simpler
+}
+
+/// Returns signed integers closer to zero than `v`: zero, one with the sign of `v` and half of
+/// `v`, rounded down.
+fn shrink_int(v: I256) -> Vec<I256> {
+    let mut simpler = Vec::new();
+    if v.is_zero() {
+        return simpler;
+    }
+    simpler.push(I256::ZERO);
+    let one = if v.is_negative() { I256::MINUS_ONE } else { I256::ONE };
+    if v != one {
+        simpler.push(one);
+        let half = v.asr(1);
+        if half != one {
+            simpler.push(half);
+        }
+    }
+    simpler
 }
 
 /// Checks if the given call sequence breaks the invariant.
@@ -140,3 +382,71 @@ pub fn check_sequence(
 
     Ok((success, true))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_integers() {
+        assert_eq!(shrink_uint(U256::ZERO), vec![]);
+        assert_eq!(shrink_uint(U256::ONE), vec![U256::ZERO]);
+        assert_eq!(shrink_uint(U256::from(100)), vec![U256::ZERO, U256::ONE, U256::from(50)]);
+
+        assert_eq!(shrink_int(I256::ZERO), vec![]);
+        assert_eq!(shrink_int(I256::MINUS_ONE), vec![I256::ZERO]);
+        assert_eq!(
+            shrink_int(I256::try_from(-100i64).unwrap()),
+            vec![I256::ZERO, I256::MINUS_ONE, I256::try_from(-50i64).unwrap()]
+        );
+    }
+
+    #[test]
+    fn simplify_values() {
+        let a = Address::with_last_byte(1);
+        let b = Address::with_last_byte(2);
+        let c = Address::with_last_byte(3);
+        let addresses = [a, b];
+
+        assert_eq!(
+            simplify_value(&DynSolValue::Address(b), &addresses),
+            vec![DynSolValue::Address(a)]
+        );
+        assert_eq!(simplify_value(&DynSolValue::Address(a), &addresses), vec![]);
+        assert_eq!(
+            simplify_value(&DynSolValue::Address(c), &addresses),
+            vec![DynSolValue::Address(a), DynSolValue::Address(b)]
+        );
+
+        let array = DynSolValue::Array(vec![
+            DynSolValue::Uint(U256::ONE, 8),
+            DynSolValue::Uint(U256::ZERO, 8),
+            DynSolValue::Uint(U256::ZERO, 8),
+        ]);
+        assert_eq!(
+            simplify_value(&array, &addresses),
+            vec![
+                DynSolValue::Array(vec![]),
+                DynSolValue::Array(vec![DynSolValue::Uint(U256::ONE, 8)]),
+                DynSolValue::Array(vec![
+                    DynSolValue::Uint(U256::ONE, 8),
+                    DynSolValue::Uint(U256::ZERO, 8),
+                ]),
+                DynSolValue::Array(vec![
+                    DynSolValue::Uint(U256::ZERO, 8),
+                    DynSolValue::Uint(U256::ZERO, 8),
+                    DynSolValue::Uint(U256::ZERO, 8),
+                ]),
+            ]
+        );
+
+        assert_eq!(
+            simplify_value(&DynSolValue::String("abc".into()), &addresses),
+            vec![
+                DynSolValue::String(String::new()),
+                DynSolValue::String("a".into()),
+                DynSolValue::String("ab".into()),
+            ]
+        );
+    }
+}
@@ -9,13 +9,14 @@
 use crate::{
     Env,
     inspectors::{
-        Cheatcodes, InspectorData, InspectorStack, cheatcodes::BroadcastableTransactions,
+        Cheatcodes, GasProfile, InspectorData, InspectorStack,
+        cheatcodes::BroadcastableTransactions,
     },
 };
 use alloy_dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt};
 use alloy_json_abi::Function;
 use alloy_primitives::{
-    Address, Bytes, Log, TxKind, U256, keccak256,
+    Address, B256, Bytes, Log, TxKind, U256, keccak256,
     map::{AddressHashMap, HashMap},
 };
 use alloy_sol_types::{SolCall, sol};
@@ -863,6 +864,10 @@ pub struct RawCallResult {
     pub line_coverage: Option<HitMaps>,
     /// The edge coverage info collected during the call
     pub edge_coverage: Option<Vec<u8>>,
+    /// The operands of the failed comparisons executed during the call
+    pub cmp_values: Option<Vec<B256>>,
+    /// The gas profile collected during the call
+    pub gas_profile: Option<GasProfile>,
     /// Scripted transactions generated from this call
     pub transactions: Option<BroadcastableTransactions>,
     /// The changeset of the state.
@@ -893,6 +898,8 @@ fn default() -> Self {
             traces: None,
             line_coverage: None,
             edge_coverage: None,
+            cmp_values: None,
+            gas_profile: None,
             transactions: None,
             state_changeset: HashMap::default(),
             env: Env::default(),
@@ -1079,6 +1086,8 @@ fn convert_executed_result(
         traces,
         line_coverage,
         edge_coverage,
+        cmp_values,
+        gas_profile,
         cheatcodes,
         chisel_state,
         reverter,
@@ -1106,6 +1115,8 @@ fn convert_executed_result(
         traces,
         line_coverage,
         edge_coverage,
+        cmp_values,
+        gas_profile,
         transactions,
         state_changeset,
         env,
@@ -1145,11 +1156,19 @@ pub struct EarlyExit {
     /// Shared atomic flag set to `true` when a failure occurs or ctrl-c received.
     /// None if running without fail-fast or show-progress.
     inner: Option<Arc<AtomicBool>>,
+    /// Whether a test failure stops the other tests.
+    on_failure: bool,
 }
 
 impl EarlyExit {
     pub fn new(early_exit: bool) -> Self {
-        Self { inner: early_exit.then_some(Arc::new(AtomicBool::new(false))) }
+        Self { inner: early_exit.then_some(Arc::new(AtomicBool::new(false))), on_failure: true }
+    }
+
+    /// Returns an early exit that is only triggered by [`record_exit`](Self::record_exit), e.g.
+    /// on interrupt, test failures not stopping the other tests.
+    pub fn interrupt_only() -> Self {
+        Self { inner: Some(Arc::new(AtomicBool::new(false))), on_failure: false }
     }
 
     /// Returns `true` if fail-fast is enabled.
@@ -1164,6 +1183,13 @@ pub fn record_exit(&self) {
         }
     }
 
+    /// Sets the exit flag after a test failure, if failures stop the other tests.
+    pub fn record_failure(&self) {
+        if self.on_failure {
+            self.record_exit();
+        }
+    }
+
     /// Whether tests should stop and exit early.
     pub fn should_stop(&self) -> bool {
         self.inner.as_ref().map(|flag| flag.load(Ordering::Relaxed)).unwrap_or(false)
@@ -0,0 +1,61 @@
+use alloy_primitives::{B256, U256};
+use revm::{
+    Inspector,
+    bytecode::opcode,
+    context::ContextTr,
+    interpreter::{Interpreter, interpreter::EthInterpreter, interpreter_types::Jumps},
+};
+
+/// The maximum number of values recorded during a single call.
+const MAX_CMP_VALUES: usize = 1024;
+
+/// An inspector that records the targets of failed comparisons.
+///
+/// Feeding these targets to the fuzz dictionary makes branches guarded by magic values computed
+/// at runtime, such as `require(x == keccak256("..."))`, reachable.
+#[derive(Clone, Debug, Default)]
+pub struct CmpLog {
+    /// The recorded targets.
+    pub values: Vec<B256>,
+}
+
+impl CmpLog {
+    /// Records the target of the comparison of `a` against `b`.
+    ///
+    /// The target is `b`, the right-hand side of the comparison as compiled by `solc`, which
+    /// evaluates the operands from right to left. Ordering comparisons also record the neighbor
+    /// of the target that crosses it.
+    #[cold]
+    fn record(&mut self, op: u8, a: U256, b: U256) {
+        if a == b {
+            return;
+        }
+
+        self.push(b);
+        match op {
+            opcode::LT | opcode::SLT => self.push(b.wrapping_sub(U256::ONE)),
+            opcode::GT | opcode::SGT => self.push(b.wrapping_add(U256::ONE)),
+            _ => {}
+        }
+    }
+
+    fn push(&mut self, value: U256) {
+        if self.values.len() < MAX_CMP_VALUES {
+            self.values.push(value.into());
+        }
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX, EthInterpreter> for CmpLog {
+    #[inline]
+    fn step(&mut self, interpreter: &mut Interpreter, _context: &mut CTX) {
+        let op = interpreter.bytecode.opcode();
+        if matches!(
+            op,
+            opcode::EQ | opcode::LT | opcode::GT | opcode::SLT | opcode::SGT | opcode::SUB
+        ) && let (Ok(a), Ok(b)) = (interpreter.stack.peek(0), interpreter.stack.peek(1))
+        {
+            self.record(op, a, b);
+        }
+    }
+}
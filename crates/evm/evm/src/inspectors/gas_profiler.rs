@@ -0,0 +1,336 @@
+use alloy_primitives::{Address, map::HashMap};
+use foundry_evm_core::precompiles::{
+    BLAKE_2F, EC_ADD, EC_MUL, EC_PAIRING, EC_RECOVER, IDENTITY, MOD_EXP, POINT_EVALUATION,
+    RIPEMD_160, SHA_256,
+};
+use revm::{
+    Inspector,
+    bytecode::opcode::{self, OpCode},
+    context::{ContextTr, JournalTr},
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+        gas::{COLD_SLOAD_COST, SSTORE_SET, WARM_SSTORE_RESET, WARM_STORAGE_READ_COST},
+        interpreter::EthInterpreter,
+        interpreter_types::{Jumps, MemoryTr},
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tempo_precompiles::{
+    NONCE_PRECOMPILE_ADDRESS, PATH_USD_ADDRESS, STABLECOIN_EXCHANGE_ADDRESS, TIP_ACCOUNT_REGISTRAR,
+    TIP_FEE_MANAGER_ADDRESS, TIP20_FACTORY_ADDRESS, TIP20_REWARDS_REGISTRY_ADDRESS,
+    TIP403_REGISTRY_ADDRESS, VALIDATOR_CONFIG_ADDRESS, tip20::is_tip20_prefix,
+};
+
+/// The number of times an operation was executed and the gas it cost in total.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasUsage {
+    pub count: u64,
+    pub gas: u64,
+}
+
+impl GasUsage {
+    fn record(&mut self, gas: u64) {
+        self.count += 1;
+        self.gas += gas;
+    }
+}
+
+/// The gas spent by a call, broken down by where it went.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasProfile {
+    /// Gas spent per opcode, excluding the gas spent by the calls and creations they spawn.
+    pub opcodes: BTreeMap<String, GasUsage>,
+    /// `SLOAD`s of slots that were not accessed before in the transaction.
+    pub cold_sloads: GasUsage,
+    /// `SLOAD`s of slots that were already accessed in the transaction.
+    pub warm_sloads: GasUsage,
+    /// `SSTORE`s of slots that were not accessed before in the transaction.
+    pub cold_sstores: GasUsage,
+    /// `SSTORE`s of slots that were already accessed in the transaction.
+    pub warm_sstores: GasUsage,
+    /// Gas spent expanding memory, included in the gas of the expanding opcodes.
+    pub memory_expansion: u64,
+    /// Intrinsic gas of the top-level calldata.
+    pub calldata: u64,
+    /// `LOG0` to `LOG4` opcodes.
+    pub logs: GasUsage,
+    /// Precompile calls, by precompile name.
+    pub precompiles: BTreeMap<String, GasUsage>,
+}
+
+/// An inspector that profiles the gas spent by a call.
+///
+/// Opcode costs are measured from the gas remaining before and after each step. The gas forwarded
+/// to a call or a creation is attributed to the opcodes of the sub-frame, so that the cost of the
+/// calling opcode only includes its own overhead.
+#[derive(Clone, Debug, Default)]
+pub struct GasProfiler {
+    opcodes: HashMap<u8, GasUsage>,
+    cold_sloads: GasUsage,
+    warm_sloads: GasUsage,
+    cold_sstores: GasUsage,
+    warm_sstores: GasUsage,
+    memory_expansion: u64,
+    calldata: u64,
+    logs: GasUsage,
+    precompiles: BTreeMap<Address, GasUsage>,
+    /// Per-depth state of the frames being executed.
+    frames: Vec<Frame>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Frame {
+    /// The step being executed.
+    step: Option<Step>,
+    /// A call or creation whose cost is known once its sub-frame returns.
+    pending: Option<Step>,
+    /// The gas spent by the sub-frames of the pending step.
+    sub_frames_gas: u64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Step {
+    op: u8,
+    gas_remaining: u64,
+    memory_size: usize,
+}
+
+impl GasProfiler {
+    /// Consumes the inspector and returns the collected profile.
+    pub fn finish(self) -> GasProfile {
+        GasProfile {
+            opcodes: self
+                .opcodes
+                .into_iter()
+                .map(|(op, usage)| (OpCode::name_by_op(op).to_string(), usage))
+                .collect(),
+            cold_sloads: self.cold_sloads,
+            warm_sloads: self.warm_sloads,
+            cold_sstores: self.cold_sstores,
+            warm_sstores: self.warm_sstores,
+            memory_expansion: self.memory_expansion,
+            calldata: self.calldata,
+            logs: self.logs,
+            precompiles: self
+                .precompiles
+                .into_iter()
+                .map(|(address, usage)| {
+                    (precompile_name(address).unwrap_or_else(|| address.to_string()), usage)
+                })
+                .collect(),
+        }
+    }
+
+    fn frame(&mut self, depth: usize) -> &mut Frame {
+        if self.frames.len() <= depth {
+            self.frames.resize_with(depth + 1, Default::default);
+        }
+        &mut self.frames[depth]
+    }
+
+    /// Invoked when a call or creation is about to start a sub-frame at `depth + 1`.
+    fn frame_start(&mut self, depth: usize) {
+        if depth == 0 {
+            self.frames.clear();
+        } else {
+            // Drop the state of previous sub-frames at the same depth.
+            self.frames.truncate(depth + 1);
+        }
+    }
+
+    /// Invoked when a call or creation returns to the frame at `depth`.
+    fn frame_end(&mut self, depth: usize, gas_spent: u64) {
+        if let Some(frame) = self.frames.get_mut(depth) {
+            frame.sub_frames_gas += gas_spent;
+        }
+    }
+
+    fn record(&mut self, step: Step, gas: u64) {
+        self.opcodes.entry(step.op).or_default().record(gas);
+        match step.op {
+            opcode::SLOAD if is_cold_storage_access(step.op, gas) => self.cold_sloads.record(gas),
+            opcode::SLOAD => self.warm_sloads.record(gas),
+            opcode::SSTORE if is_cold_storage_access(step.op, gas) => self.cold_sstores.record(gas),
+            opcode::SSTORE => self.warm_sstores.record(gas),
+            opcode::LOG0..=opcode::LOG4 => self.logs.record(gas),
+            _ => {}
+        }
+    }
+}
+
+impl<CTX: ContextTr> Inspector<CTX, EthInterpreter> for GasProfiler {
+    fn step(&mut self, interpreter: &mut Interpreter, context: &mut CTX) {
+        let depth = context.journal().depth();
+        let gas_remaining = interpreter.gas.remaining();
+
+        let frame = self.frame(depth);
+        if let Some(pending) = frame.pending.take() {
+            let gas = pending
+                .gas_remaining
+                .saturating_sub(gas_remaining)
+                .saturating_sub(std::mem::take(&mut frame.sub_frames_gas));
+            self.record(pending, gas);
+        }
+
+        let op = interpreter.bytecode.opcode();
+        self.frame(depth).step =
+            Some(Step { op, gas_remaining, memory_size: interpreter.memory.size() });
+    }
+
+    fn step_end(&mut self, interpreter: &mut Interpreter, context: &mut CTX) {
+        let depth = context.journal().depth();
+        let Some(frame) = self.frames.get_mut(depth) else { return };
+        let Some(step) = frame.step.take() else { return };
+
+        let memory_size = interpreter.memory.size();
+        if memory_size > step.memory_size {
+            self.memory_expansion += memory_gas(memory_size) - memory_gas(step.memory_size);
+        }
+
+        if is_frame_opcode(step.op) {
+            // The gas forwarded to the sub-frame is only settled once it returns.
+            frame.pending = Some(step);
+            frame.sub_frames_gas = 0;
+        } else {
+            self.record(step, step.gas_remaining.saturating_sub(interpreter.gas.remaining()));
+        }
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let depth = context.journal().depth();
+        if depth == 0 {
+            self.calldata = calldata_gas(&inputs.input.bytes(context));
+        }
+        self.frame_start(depth);
+        None
+    }
+
+    fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        let gas_spent = outcome.result.gas.spent();
+        if precompile_name(inputs.target_address).is_some() {
+            self.precompiles.entry(inputs.target_address).or_default().record(gas_spent);
+        }
+        self.frame_end(context.journal().depth(), gas_spent);
+    }
+
+    fn create(&mut self, context: &mut CTX, _inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.frame_start(context.journal().depth());
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut CTX,
+        _inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.frame_end(context.journal().depth(), outcome.result.gas.spent());
+    }
+}
+
+/// Returns whether the `SLOAD` or `SSTORE` that cost `gas` accessed a cold storage slot.
+///
+/// The cold access surcharge is read from the cost of the opcode, so that slots warmed by the
+/// access list of the transaction and warm accesses rolled back by reverts are accounted for.
+fn is_cold_storage_access(op: u8, gas: u64) -> bool {
+    match op {
+        opcode::SLOAD => gas >= COLD_SLOAD_COST,
+        // A cold `SSTORE` costs the surcharge on top of the cost of the warm store.
+        opcode::SSTORE => gas.checked_sub(COLD_SLOAD_COST).is_some_and(|warm| {
+            matches!(warm, WARM_STORAGE_READ_COST | WARM_SSTORE_RESET | SSTORE_SET)
+        }),
+        _ => false,
+    }
+}
+
+/// Returns whether the opcode starts a sub-frame.
+fn is_frame_opcode(op: u8) -> bool {
+    matches!(
+        op,
+        opcode::CALL
+            | opcode::CALLCODE
+            | opcode::DELEGATECALL
+            | opcode::STATICCALL
+            | opcode::CREATE
+            | opcode::CREATE2
+    )
+}
+
+/// Returns the total cost of a memory of `size` bytes.
+fn memory_gas(size: usize) -> u64 {
+    let words = size.div_ceil(32) as u64;
+    3 * words + words * words / 512
+}
+
+/// Returns the intrinsic gas of the given calldata.
+fn calldata_gas(data: &[u8]) -> u64 {
+    data.iter().map(|&byte| if byte == 0 { 4 } else { 16 }).sum()
+}
+
+/// Returns the name of the precompile at `address`, if any.
+fn precompile_name(address: Address) -> Option<String> {
+    let precompiles = [
+        (EC_RECOVER, "ECRecover"),
+        (SHA_256, "SHA-256"),
+        (RIPEMD_160, "RIPEMD-160"),
+        (IDENTITY, "Identity"),
+        (MOD_EXP, "ModExp"),
+        (EC_ADD, "ECAdd"),
+        (EC_MUL, "ECMul"),
+        (EC_PAIRING, "ECPairing"),
+        (BLAKE_2F, "Blake2F"),
+        (POINT_EVALUATION, "PointEvaluation"),
+        // Tempo
+        (TIP_FEE_MANAGER_ADDRESS, "TipFeeManager"),
+        (PATH_USD_ADDRESS, "PathUSD"),
+        (TIP403_REGISTRY_ADDRESS, "TIP403Registry"),
+        (TIP20_FACTORY_ADDRESS, "TIP20Factory"),
+        (TIP20_REWARDS_REGISTRY_ADDRESS, "TIP20RewardsRegistry"),
+        (TIP_ACCOUNT_REGISTRAR, "TIPAccountRegistrar"),
+        (STABLECOIN_EXCHANGE_ADDRESS, "StablecoinAMM"),
+        (NONCE_PRECOMPILE_ADDRESS, "Nonce"),
+        (VALIDATOR_CONFIG_ADDRESS, "ValidatorConfig"),
+    ];
+    if let Some((_, name)) = precompiles.iter().find(|(precompile, _)| *precompile == address) {
+        Some(name.to_string())
+    } else if is_tip20_prefix(address) {
+        Some(format!("TIP20 ({address})"))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_and_calldata_gas() {
+        assert_eq!(memory_gas(0), 0);
+        assert_eq!(memory_gas(32), 3);
+        assert_eq!(memory_gas(64), 6);
+        assert_eq!(memory_gas(33 * 32), 3 * 33 + 33 * 33 / 512);
+        assert_eq!(calldata_gas(&[0, 0, 1, 0xff]), 4 + 4 + 16 + 16);
+    }
+
+    #[test]
+    fn classifies_cold_storage_accesses() {
+        assert!(is_cold_storage_access(opcode::SLOAD, 2100));
+        assert!(!is_cold_storage_access(opcode::SLOAD, 100));
+        for gas in [2200, 5000, 22100] {
+            assert!(is_cold_storage_access(opcode::SSTORE, gas), "{gas}");
+        }
+        for gas in [100, 2900, 20000] {
+            assert!(!is_cold_storage_access(opcode::SSTORE, gas), "{gas}");
+        }
+        assert!(!is_cold_storage_access(opcode::ADD, 2100));
+    }
+
+    #[test]
+    fn names_precompiles() {
+        assert_eq!(precompile_name(EC_RECOVER).as_deref(), Some("ECRecover"));
+        assert_eq!(precompile_name(PATH_USD_ADDRESS).as_deref(), Some("PathUSD"));
+        assert_eq!(precompile_name(Address::repeat_byte(0x11)), None);
+    }
+}
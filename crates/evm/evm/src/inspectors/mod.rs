@@ -13,6 +13,12 @@
 mod chisel_state;
 pub use chisel_state::ChiselState;
 
+mod cmp_log;
+pub use cmp_log::CmpLog;
+
+mod gas_profiler;
+pub use gas_profiler::{GasProfile, GasProfiler, GasUsage};
+
 mod logs;
 pub use logs::LogCollector;
 
@@ -1,12 +1,13 @@
 use crate::inspectors::tempo_labels::TempoLabels;
 
 use super::{
-    Cheatcodes, CheatsConfig, ChiselState, CustomPrintTracer, Fuzzer, LineCoverageCollector,
-    LogCollector, RevertDiagnostic, ScriptExecutionInspector, TracingInspector,
+    Cheatcodes, CheatsConfig, ChiselState, CmpLog, CustomPrintTracer, Fuzzer, GasProfile,
+    GasProfiler, LineCoverageCollector, LogCollector, RevertDiagnostic, ScriptExecutionInspector,
+    TracingInspector,
 };
 use alloy_evm::Evm;
 use alloy_primitives::{
-    Address, Bytes, Log, TxKind, U256,
+    Address, B256, Bytes, Log, TxKind, U256,
     map::{AddressHashMap, HashMap},
 };
 use foundry_cheatcodes::{CheatcodeAnalysis, CheatcodesExecutor, Wallets};
@@ -62,6 +63,8 @@ pub struct InspectorStackBuilder {
     pub logs: Option<bool>,
     /// Whether line coverage info should be collected.
     pub line_coverage: Option<bool>,
+    /// Whether the gas spent should be profiled.
+    pub gas_profile: Option<bool>,
     /// Whether to print all opcode traces into the console. Useful for debugging the EVM.
     pub print: Option<bool>,
     /// The chisel state inspector.
@@ -148,6 +151,13 @@ pub fn line_coverage(mut self, yes: bool) -> Self {
         self
     }
 
+    /// Set whether to profile the gas spent.
+    #[inline]
+    pub fn gas_profile(mut self, yes: bool) -> Self {
+        self.gas_profile = Some(yes);
+        self
+    }
+
     /// Set whether to enable the trace printer.
     #[inline]
     pub fn print(mut self, yes: bool) -> Self {
@@ -197,6 +207,7 @@ pub fn build(self) -> InspectorStack {
             trace_mode,
             logs,
             line_coverage,
+            gas_profile,
             print,
             chisel_state,
             enable_isolation,
@@ -228,6 +239,7 @@ pub fn build(self) -> InspectorStack {
             stack.set_chisel(chisel_state);
         }
         stack.collect_line_coverage(line_coverage.unwrap_or(false));
+        stack.collect_gas_profile(gas_profile.unwrap_or(false));
         stack.collect_logs(logs.unwrap_or(true));
         stack.print(print.unwrap_or(false));
         stack.tracing(trace_mode);
@@ -279,6 +291,8 @@ pub struct InspectorData {
     pub traces: Option<SparsedTraceArena>,
     pub line_coverage: Option<HitMaps>,
     pub edge_coverage: Option<Vec<u8>>,
+    pub cmp_values: Option<Vec<B256>>,
+    pub gas_profile: Option<GasProfile>,
     pub cheatcodes: Option<Box<Cheatcodes>>,
     pub chisel_state: Option<(Vec<U256>, Vec<u8>)>,
     pub reverter: Option<Address>,
@@ -329,8 +343,10 @@ pub struct InspectorStackInner {
     // These are boxed to reduce the size of the struct and slightly improve performance of the
     // `if let Some` checks.
     pub chisel_state: Option<Box<ChiselState>>,
+    pub cmp_log: Option<Box<CmpLog>>,
     pub edge_coverage: Option<Box<EdgeCovInspector>>,
     pub fuzzer: Option<Box<Fuzzer>>,
+    pub gas_profiler: Option<Box<GasProfiler>>,
     pub line_coverage: Option<Box<LineCoverageCollector>>,
     pub log_collector: Option<Box<LogCollector>>,
     pub printer: Option<Box<CustomPrintTracer>>,
@@ -462,6 +478,19 @@ pub fn collect_edge_coverage(&mut self, yes: bool) {
         self.edge_coverage = yes.then(EdgeCovInspector::new).map(Into::into);
     }
 
+    /// Set whether to record the operands of comparisons, to be fed to the fuzz dictionary.
+    #[inline]
+    pub fn collect_cmp_log(&mut self, yes: bool) {
+        self.cmp_log = yes.then(Default::default);
+    }
+
+    /// Set whether to profile the gas spent by opcode, storage access, memory expansion, calldata,
+    /// logs and precompile calls.
+    #[inline]
+    pub fn collect_gas_profile(&mut self, yes: bool) {
+        self.gas_profiler = yes.then(Default::default);
+    }
+
     /// Set whether to enable call isolation.
     #[inline]
     pub fn enable_isolation(&mut self, yes: bool) {
@@ -530,6 +559,8 @@ pub fn collect(self) -> InspectorData {
             inner:
                 InspectorStackInner {
                     chisel_state,
+                    cmp_log,
+                    gas_profiler,
                     line_coverage,
                     edge_coverage,
                     log_collector,
@@ -569,6 +600,8 @@ pub fn collect(self) -> InspectorData {
             traces,
             line_coverage: line_coverage.map(|line_coverage| line_coverage.finish()),
             edge_coverage: edge_coverage.map(|edge_coverage| edge_coverage.into_hitcount()),
+            cmp_values: cmp_log.map(|cmp_log| cmp_log.values),
+            gas_profile: gas_profiler.map(|gas_profiler| gas_profiler.finish()),
             cheatcodes,
             chisel_state: chisel_state.and_then(|state| state.state),
             reverter,
@@ -598,6 +631,7 @@ fn do_call_end(
             #[ret]
             [
                 &mut self.fuzzer,
+                &mut self.gas_profiler,
                 &mut self.tracer,
                 &mut self.cheatcodes,
                 &mut self.printer,
@@ -633,7 +667,7 @@ fn do_create_end(
         let result = outcome.result.result;
         call_inspectors!(
             #[ret]
-            [&mut self.tracer, &mut self.cheatcodes, &mut self.printer],
+            [&mut self.gas_profiler, &mut self.tracer, &mut self.cheatcodes, &mut self.printer],
             |inspector| {
                 let previous_outcome = outcome.clone();
                 inspector.create_end(ecx, call, outcome);
@@ -848,8 +882,10 @@ fn step_inlined(
         call_inspectors!(
             [
                 // These are sorted in definition order.
+                &mut self.cmp_log,
                 &mut self.edge_coverage,
                 &mut self.fuzzer,
+                &mut self.gas_profiler,
                 &mut self.line_coverage,
                 &mut self.printer,
                 &mut self.revert_diag,
@@ -872,6 +908,7 @@ fn step_end_inlined(
             [
                 // These are sorted in definition order.
                 &mut self.chisel_state,
+                &mut self.gas_profiler,
                 &mut self.printer,
                 &mut self.revert_diag,
                 &mut self.tracer,
@@ -958,6 +995,7 @@ fn call(
             #[ret]
             [
                 &mut self.fuzzer,
+                &mut self.gas_profiler,
                 &mut self.tracer,
                 &mut self.log_collector,
                 &mut self.printer,
@@ -1074,7 +1112,12 @@ fn create(
 
         call_inspectors!(
             #[ret]
-            [&mut self.tracer, &mut self.line_coverage, &mut self.cheatcodes],
+            [
+                &mut self.gas_profiler,
+                &mut self.tracer,
+                &mut self.line_coverage,
+                &mut self.cheatcodes
+            ],
             |inspector| inspector.create(ecx, create).map(Some),
         );
 
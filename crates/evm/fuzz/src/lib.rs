@@ -279,6 +279,8 @@ pub struct FuzzTestResult {
 
     /// NUmber of failed replays from persisted corpus.
     pub failed_corpus_replays: usize,
+    /// Number of edges seen with edge coverage enabled.
+    pub edges_seen: usize,
 }
 
 impl FuzzTestResult {
@@ -88,6 +88,13 @@ pub fn collect_values(&self, values: impl IntoIterator<Item = B256>) {
         }
     }
 
+    /// Collects the operands of comparisons executed at runtime.
+    ///
+    /// Unlike other collected values, these are kept across runs.
+    pub fn collect_cmp_values(&self, values: impl IntoIterator<Item = B256>) {
+        self.inner.write().insert_cmp_values(values);
+    }
+
     /// Collects state changes from a [StateChangeset] and logs into an [EvmFuzzState] according to
     /// the given [FuzzDictionaryConfig].
     pub fn collect_values_from_call(
@@ -123,6 +130,32 @@ pub fn revert(&self) {
         self.inner.write().revert();
     }
 
+    /// Returns a copy of the state with its own dictionary.
+    ///
+    /// Used by parallel campaign workers, so that values collected and reverted during a run of
+    /// one worker don't affect the runs of other workers. See [`Self::sync`].
+    pub fn fork(&self) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(self.inner.read().clone())),
+            deployed_libs: self.deployed_libs.clone(),
+            mapping_slots: self.mapping_slots.clone(),
+        }
+    }
+
+    /// Exchanges the values kept across runs, i.e. comparison operands and samples, with another
+    /// state.
+    ///
+    /// Should only be called between runs.
+    pub fn sync(&self, other: &Self) {
+        if Arc::ptr_eq(&self.inner, &other.inner) {
+            return;
+        }
+        let mut dict = self.inner.write();
+        let mut other = other.inner.write();
+        other.merge_persistent(&dict);
+        dict.merge_persistent(&other);
+    }
+
     pub fn dictionary_read(&self) -> RwLockReadGuard<'_, RawRwLock, FuzzDictionary> {
         self.inner.read()
     }
@@ -141,6 +174,7 @@ pub(crate) fn seed_literals(&self, map: super::LiteralMaps) {
 
 // We're using `IndexSet` to have a stable element order when restoring persisted state, as well as
 // for performance when iterating over the sets.
+#[derive(Clone)]
 pub struct FuzzDictionary {
     /// Collected state values.
     state_values: B256IndexSet,
@@ -154,6 +188,8 @@ pub struct FuzzDictionary {
     /// Number of address values initially collected from db.
     /// Used to revert new collected addresses at the end of each run.
     db_addresses: usize,
+    /// Operands of runtime comparisons, persisted across invariant runs.
+    cmp_values: B256IndexSet,
     /// Typed runtime sample values persisted across invariant runs.
     /// Initially seeded with literal values collected from the source code.
     sample_values: HashMap<DynSolType, B256IndexSet>,
@@ -194,6 +230,7 @@ pub fn new(config: FuzzDictionaryConfig) -> Self {
             addresses: Default::default(),
             db_state_values: Default::default(),
             db_addresses: Default::default(),
+            cmp_values: Default::default(),
             sample_values: Default::default(),
             literal_values: Default::default(),
             misses: Default::default(),
@@ -502,10 +539,48 @@ pub fn addresses(&self) -> &AddressIndexSet {
         &self.addresses
     }
 
+    /// Insert targets of runtime comparisons into fuzz dictionary.
+    fn insert_cmp_values(&mut self, values: impl IntoIterator<Item = B256>) {
+        for value in values {
+            if self.values_full() {
+                break;
+            }
+            if self.cmp_values.insert(value) {
+                self.insert_value(value);
+            }
+        }
+    }
+
+    /// Merges the values kept across runs of another dictionary into this one.
+    fn merge_persistent(&mut self, other: &Self) {
+        self.insert_cmp_values(other.cmp_values.iter().copied());
+        if other.samples_seeded {
+            if !self.samples_seeded {
+                self.seed_samples();
+            }
+            for (sample_type, values) in &other.sample_values {
+                self.sample_values
+                    .entry(sample_type.clone())
+                    .or_default()
+                    .extend(values.iter().copied());
+            }
+        }
+    }
+
     /// Revert values and addresses collected during the run by truncating to initial db len.
+    ///
+    /// Targets of runtime comparisons are restored, up to the maximum number of values, as they
+    /// are needed to cross the same branches in the next runs.
     pub fn revert(&mut self) {
         self.state_values.truncate(self.db_state_values);
         self.addresses.truncate(self.db_addresses);
+        for &value in &self.cmp_values {
+            // Same as `values_full`, borrowing the fields separately.
+            if self.state_values.len() >= self.config.max_fuzz_dictionary_values {
+                break;
+            }
+            self.state_values.insert(value);
+        }
     }
 
     pub fn log_stats(&self) {
@@ -525,3 +600,34 @@ pub(crate) fn seed_literals(&mut self, map: super::LiteralMaps) {
         self.literal_values.set(map);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forked_states_revert_and_sync() {
+        let shared = EvmFuzzState::test();
+        let first = shared.fork();
+        let second = shared.fork();
+
+        let first_value = B256::with_last_byte(1);
+        let second_value = B256::with_last_byte(2);
+        first.collect_values([first_value]);
+        second.collect_values([second_value]);
+
+        // Reverting a run of a worker doesn't revert the values of other workers.
+        first.revert();
+        assert!(!first.dictionary_read().values().contains(&first_value));
+        assert!(second.dictionary_read().values().contains(&second_value));
+
+        // Comparison operands are exchanged through the shared state.
+        let cmp_value = B256::with_last_byte(3);
+        first.collect_cmp_values([cmp_value]);
+        first.sync(&shared);
+        second.sync(&shared);
+        second.revert();
+        assert!(second.dictionary_read().values().contains(&cmp_value));
+        assert!(!second.dictionary_read().values().contains(&second_value));
+    }
+}
@@ -9,7 +9,9 @@
 use foundry_compilers::artifacts::sourcemap::{Jump, SourceElement};
 use revm::bytecode::opcode::OpCode;
 use revm_inspectors::tracing::types::{CallTraceStep, DecodedInternalCall, DecodedTraceStep};
-pub use sources::{ArtifactData, ContractSources, SourceData};
+pub use sources::{
+    ArtifactData, ContractSources, FunctionDebugInfo, SourceData, VariableDebugInfo,
+};
 
 #[derive(Clone, Debug)]
 pub struct DebugTraceIdentifier {
@@ -281,6 +283,25 @@ fn try_decode_args_from_step(args: &Parameters<'_>, step: &CallTraceStep) -> Opt
     Some(decoded)
 }
 
+/// Formats the value of a variable stored in the given stack word, reading variables in memory
+/// from `memory`.
+///
+/// Returns `None` if the type of the variable can't be decoded, e.g. user-defined types and
+/// pointers to storage or calldata.
+pub fn format_stack_variable(
+    var: &VariableDebugInfo,
+    word: U256,
+    memory: &[u8],
+) -> Option<String> {
+    let ty = DynSolType::parse(&var.ty.replace("address payable", "address")).ok()?;
+    let value = match var.data_location {
+        Some("memory") => decode_from_memory(&ty, memory, word.try_into().ok()?),
+        Some(_) => None,
+        None => ty.abi_decode(&word.to_be_bytes::<32>()).ok(),
+    }?;
+    Some(format_token(&value))
+}
+
 /// Decodes given [DynSolType] from memory.
 fn decode_from_memory(ty: &DynSolType, memory: &[u8], location: usize) -> Option<DynSolValue> {
     let first_word = memory.get(location..location + 32)?;
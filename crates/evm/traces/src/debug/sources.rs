@@ -11,10 +11,15 @@
 use foundry_evm_core::ic::PcIcMap;
 use foundry_linking::Linker;
 use rayon::prelude::*;
+use solar::{
+    ast::{self, Visit},
+    interface::{SpannedOption, data_structures::Never},
+    parse::{ast::Span, interface::SourceMap},
+};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     fmt::Write,
-    ops::Range,
+    ops::{ControlFlow, Range},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -27,6 +32,8 @@ pub struct SourceData {
     /// Maps contract name to (start, end) of the contract definition in the source code.
     /// This is useful for determining which contract contains given function definition.
     pub contract_definitions: Vec<(String, Range<usize>)>,
+    /// Debug info of the function definitions, in source order.
+    pub functions: Vec<FunctionDebugInfo>,
 }
 
 impl SourceData {
@@ -38,6 +45,7 @@ pub fn new(
         root: &Path,
     ) -> Self {
         let mut contract_definitions = Vec::new();
+        let mut functions = Vec::new();
 
         match language {
             MultiCompilerLanguage::Vyper(_) => {
@@ -49,14 +57,19 @@ pub fn new(
             MultiCompilerLanguage::Solc(_) => {
                 let r = output.parser().solc().compiler().enter(|compiler| -> Option<()> {
                     let (_, source) = compiler.gcx().get_ast_source(root.join(&path))?;
-                    for item in source.ast.as_ref()?.items.iter() {
+                    let source_map = compiler.sess().source_map();
+                    let ast = source.ast.as_ref()?;
+                    for item in ast.items.iter() {
                         if let solar::ast::ItemKind::Contract(contract) = &item.kind {
                             contract_definitions.push((
                                 contract.name.to_string(),
-                                compiler.sess().source_map().span_to_range(item.span).unwrap(),
+                                source_map.span_to_range(item.span).unwrap(),
                             ));
                         }
                     }
+                    let mut collector = FunctionCollector::new(source_map);
+                    let _ = collector.visit_source_unit(ast);
+                    functions = collector.functions;
                     Some(())
                 });
                 if r.is_none() {
@@ -65,7 +78,7 @@ pub fn new(
             }
         }
 
-        Self { source, language, path, contract_definitions }
+        Self { source, language, path, contract_definitions, functions }
     }
 
     /// Finds name of contract that contains given loc.
@@ -75,6 +88,192 @@ pub fn find_contract_name(&self, start: usize, end: usize) -> Option<&str> {
             .find(|(_, r)| start >= r.start && end <= r.end)
             .map(|(name, _)| name.as_str())
     }
+
+    /// Finds the function definition that contains given offset.
+    pub fn find_function(&self, offset: usize) -> Option<&FunctionDebugInfo> {
+        self.functions.iter().find(|func| func.range.contains(&offset))
+    }
+
+    /// Returns the 1-based line number of given offset.
+    pub fn line_number(&self, offset: usize) -> usize {
+        let offset = offset.min(self.source.len());
+        self.source.as_bytes()[..offset].iter().filter(|&&b| b == b'\n').count() + 1
+    }
+}
+
+/// Debug info of a function definition, used to step through its statements and inspect its
+/// variables.
+#[derive(Clone, Debug)]
+pub struct FunctionDebugInfo {
+    /// Name of the function, or its kind if it is unnamed (e.g. `constructor`).
+    pub name: String,
+    /// Range of the function definition in the source code.
+    pub range: Range<usize>,
+    pub parameters: Vec<VariableDebugInfo>,
+    pub returns: Vec<VariableDebugInfo>,
+    /// Local variables, in declaration order.
+    pub locals: Vec<VariableDebugInfo>,
+    /// Ranges of the statements of the function body, in source order.
+    ///
+    /// Compound statements (`if`, loops, ...) contain the ranges of their inner statements.
+    pub statements: Vec<Range<usize>>,
+}
+
+impl FunctionDebugInfo {
+    /// Returns the range of the innermost statement containing given offset.
+    pub fn find_statement(&self, offset: usize) -> Option<&Range<usize>> {
+        self.statements
+            .iter()
+            .filter(|range| range.contains(&offset))
+            .min_by_key(|range| range.len())
+    }
+}
+
+/// Debug info of a function parameter or local variable.
+#[derive(Clone, Debug)]
+pub struct VariableDebugInfo {
+    /// Name of the variable, empty if unnamed.
+    pub name: String,
+    /// Type of the variable, as written in the source code.
+    pub ty: String,
+    /// Data location of reference types: `memory`, `storage` or `calldata`.
+    pub data_location: Option<&'static str>,
+    /// Offset from which the variable is on the stack, i.e. the end of its declaration
+    /// statement for local variables.
+    pub declared_at: usize,
+    /// Range of the block the variable is visible in.
+    pub scope: Range<usize>,
+}
+
+impl VariableDebugInfo {
+    /// Returns the number of stack slots used by the variable.
+    ///
+    /// Dynamic calldata arrays are stored as an offset and a length, every other variable takes a
+    /// single slot.
+    pub fn stack_slots(&self) -> usize {
+        let dynamic = self.ty == "bytes" || self.ty == "string" || self.ty.ends_with("[]");
+        if self.data_location == Some("calldata") && dynamic { 2 } else { 1 }
+    }
+
+    /// Returns whether the variable is on the stack at given offset of its function.
+    pub fn is_visible(&self, offset: usize) -> bool {
+        self.declared_at <= offset && self.scope.contains(&offset)
+    }
+}
+
+/// Collects [`FunctionDebugInfo`] of the function definitions of a source unit.
+struct FunctionCollector<'a> {
+    source_map: &'a SourceMap,
+    functions: Vec<FunctionDebugInfo>,
+    /// Ranges of the blocks enclosing the current statement.
+    scopes: Vec<Range<usize>>,
+}
+
+impl<'a> FunctionCollector<'a> {
+    fn new(source_map: &'a SourceMap) -> Self {
+        Self { source_map, functions: Vec::new(), scopes: Vec::new() }
+    }
+
+    fn range(&self, span: Span) -> Range<usize> {
+        self.source_map.span_to_range(span).unwrap_or_default()
+    }
+
+    fn variable(
+        &self,
+        var: &ast::VariableDefinition<'_>,
+        declared_at: usize,
+        scope: Range<usize>,
+    ) -> VariableDebugInfo {
+        let ty = self.source_map.span_to_snippet(var.ty.span).unwrap_or_default();
+        VariableDebugInfo {
+            name: var.name.map(|name| name.to_string()).unwrap_or_default(),
+            ty: ty.split_whitespace().collect::<Vec<_>>().join(" "),
+            data_location: var.data_location.map(|location| location.to_str()),
+            declared_at,
+            scope,
+        }
+    }
+
+    fn push_local(&mut self, var: &ast::VariableDefinition<'_>, declared_at: usize) {
+        let scope = self.scopes.last().cloned().unwrap_or_default();
+        let local = self.variable(var, declared_at, scope);
+        if let Some(func) = self.functions.last_mut() {
+            func.locals.push(local);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for FunctionCollector<'_> {
+    type BreakValue = Never;
+
+    fn visit_item(&mut self, item: &'ast ast::Item<'ast>) -> ControlFlow<Self::BreakValue> {
+        let ast::ItemKind::Function(func) = &item.kind else { return self.walk_item(item) };
+        let Some(body) = &func.body else { return ControlFlow::Continue(()) };
+
+        let range = self.range(item.span);
+        let parameters = func
+            .header
+            .parameters
+            .iter()
+            .chain(func.header.returns().iter())
+            .map(|var| self.variable(var, range.start, range.clone()))
+            .collect::<Vec<_>>();
+        let (parameters, returns) = parameters.split_at(func.header.parameters.len());
+        self.functions.push(FunctionDebugInfo {
+            name: func
+                .header
+                .name
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| func.kind.to_str().to_string()),
+            range,
+            parameters: parameters.to_vec(),
+            returns: returns.to_vec(),
+            locals: Vec::new(),
+            statements: Vec::new(),
+        });
+
+        self.scopes.push(self.range(body.span));
+        for stmt in body.iter() {
+            let _ = self.visit_stmt(stmt);
+        }
+        self.scopes.pop();
+        ControlFlow::Continue(())
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast ast::Stmt<'ast>) -> ControlFlow<Self::BreakValue> {
+        let range = self.range(stmt.span);
+        match &stmt.kind {
+            ast::StmtKind::Block(_) | ast::StmtKind::UncheckedBlock(_) => {
+                self.scopes.push(range);
+                let _ = self.walk_stmt(stmt);
+                self.scopes.pop();
+                return ControlFlow::Continue(());
+            }
+            // Variables declared in the initialization of a loop are scoped to the loop.
+            ast::StmtKind::For { .. } => {
+                if let Some(func) = self.functions.last_mut() {
+                    func.statements.push(range.clone());
+                }
+                self.scopes.push(range);
+                let _ = self.walk_stmt(stmt);
+                self.scopes.pop();
+                return ControlFlow::Continue(());
+            }
+            ast::StmtKind::DeclSingle(var) => self.push_local(var, range.end),
+            ast::StmtKind::DeclMulti(vars, _) => {
+                for var in vars.iter() {
+                    if let SpannedOption::Some(var) = var.as_ref() {
+                        self.push_local(var, range.end);
+                    }
+                }
+            }
+            _ => {}
+        }
+        if let Some(func) = self.functions.last_mut() {
+            func.statements.push(range);
+        }
+        self.walk_stmt(stmt)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -307,3 +506,98 @@ pub fn find_source_mapping(
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solar::{
+        interface::{Session, source_map::FileName},
+        parse::Parser,
+    };
+
+    const SOURCE: &str = r#"
+contract C {
+    function f(uint256 a, bytes calldata data) public returns (uint256 r) {
+        uint256 b = a + 1;
+        if (b > data.length) {
+            uint256 c = b;
+            r = c;
+        }
+        for (uint256 i; i < 2; i++) {
+            r += i;
+        }
+    }
+
+    function g() external;
+}
+"#;
+
+    fn collect_functions(src: &str) -> Vec<FunctionDebugInfo> {
+        let sess = Session::builder().with_buffer_emitter(Default::default()).build();
+        sess.enter(|| {
+            let arena = ast::Arena::new();
+            let mut parser = Parser::from_source_code(
+                &sess,
+                &arena,
+                FileName::Custom("C.sol".to_string()),
+                src.to_string(),
+            )
+            .unwrap();
+            let ast = parser.parse_file().map_err(|e| e.emit()).unwrap();
+            let mut collector = FunctionCollector::new(sess.source_map());
+            let _ = collector.visit_source_unit(&ast);
+            collector.functions
+        })
+    }
+
+    fn offset(needle: &str) -> usize {
+        SOURCE.find(needle).unwrap()
+    }
+
+    #[test]
+    fn collects_function_debug_info() {
+        // Functions without a body are skipped.
+        let functions = collect_functions(SOURCE);
+        assert_eq!(functions.len(), 1);
+        let f = &functions[0];
+        assert_eq!(f.name, "f");
+        assert!(SOURCE[f.range.clone()].starts_with("function f("));
+
+        let names = |vars: &[VariableDebugInfo]| -> Vec<String> {
+            vars.iter().map(|var| var.name.clone()).collect()
+        };
+        assert_eq!(names(&f.parameters), vec!["a", "data"]);
+        assert_eq!(names(&f.returns), vec!["r"]);
+        assert_eq!(names(&f.locals), vec!["b", "c", "i"]);
+
+        let data = &f.parameters[1];
+        assert_eq!(data.ty, "bytes");
+        assert_eq!(data.data_location, Some("calldata"));
+        assert_eq!(data.stack_slots(), 2);
+        assert_eq!(f.parameters[0].stack_slots(), 1);
+
+        // Locals are visible from the end of their declaration until the end of their block.
+        let (b, c, i) = (&f.locals[0], &f.locals[1], &f.locals[2]);
+        assert!(!b.is_visible(offset("a + 1")));
+        assert!(b.is_visible(offset("if (b")));
+        assert!(b.is_visible(offset("r += i")));
+        assert!(c.is_visible(offset("r = c")));
+        assert!(!c.is_visible(offset("for (")));
+        assert!(SOURCE[i.scope.clone()].starts_with("for ("));
+        assert!(i.is_visible(offset("r += i")));
+    }
+
+    #[test]
+    fn finds_innermost_statement() {
+        let f = &collect_functions(SOURCE)[0];
+        let statement = |needle| &SOURCE[f.find_statement(offset(needle)).unwrap().clone()];
+
+        assert!(statement("a + 1").starts_with("uint256 b = a + 1"));
+        assert!(statement("b > data").starts_with("if (b > data.length)"));
+        assert!(statement("r = c").starts_with("r = c"));
+        // Loop headers belong to the loop statement.
+        assert!(statement("i < 2").starts_with("for ("));
+        assert!(statement("r += i").starts_with("r += i"));
+        assert!(f.find_statement(offset("function f")).is_none());
+    }
+}
@@ -16,13 +16,21 @@
 
 use solar::{
     parse::{
-        ast::{SourceUnit, Span},
-        interface::{Session, diagnostics::EmittedDiagnostics, source_map::SourceFile},
+        ast::{self, SourceUnit, Span},
+        interface::{
+            Session,
+            diagnostics::EmittedDiagnostics,
+            source_map::{FileName, SourceFile, SourceMap},
+        },
     },
     sema::{Compiler, Gcx, Source},
 };
 
-use std::{path::Path, sync::Arc};
+use std::{
+    ops::{Range, RangeInclusive},
+    path::Path,
+    sync::Arc,
+};
 
 pub use foundry_config::fmt::*;
 
@@ -244,6 +252,175 @@ pub fn format_ast<'ast>(
     Some(state.s.eof())
 }
 
+/// Formats only the given lines of the source, leaving the rest as-is.
+///
+/// Lines are 1-based and ranges are inclusive. The innermost items and statements overlapping a
+/// range are formatted as a whole, while the enclosing items and statements are left untouched.
+///
+/// Returns `None` if the source has no AST, or if the items and statements of the formatted
+/// source can't be matched with the original ones. Callers should then format the whole source.
+pub fn format_ast_lines<'ast>(
+    gcx: Gcx<'ast>,
+    source: &'ast Source<'ast>,
+    config: Arc<FormatterConfig>,
+    lines: &[RangeInclusive<usize>],
+) -> Option<String> {
+    let formatted = format_ast(gcx, source, config)?;
+    let ast = source.ast.as_ref()?;
+
+    // Parse the formatted source to find the formatted counterpart of each selected node.
+    let name = match &source.file.name {
+        FileName::Real(path) => path.with_extension("lines.sol").into(),
+        FileName::Stdin => FileName::Custom("stdin-lines".to_string()),
+        FileName::Custom(name) => FileName::Custom(format!("{name}-lines")),
+    };
+    let arena = ast::Arena::new();
+    let mut parser =
+        solar::parse::Parser::from_source_code(gcx.sess, &arena, name, formatted.clone()).ok()?;
+    let formatted_ast = parser.parse_file().map_err(|e| e.emit()).ok()?;
+    if ast.items.len() != formatted_ast.items.len() {
+        return None;
+    }
+
+    let selection = LineSelection::new(gcx.sess.source_map(), &source.file.src, lines);
+    let mut replacements = Vec::new();
+    for (item, formatted_item) in ast.items.iter().zip(formatted_ast.items.iter()) {
+        selection.collect(Node::Item(item), Node::Item(formatted_item), &mut replacements)?;
+    }
+
+    let src = source.file.src.as_str();
+    let mut out = String::with_capacity(src.len());
+    let mut cursor = 0;
+    for (range, formatted_range) in replacements {
+        out.push_str(&src[cursor..range.start]);
+        out.push_str(&formatted[formatted_range]);
+        cursor = range.end;
+    }
+    out.push_str(&src[cursor..]);
+    Some(out)
+}
+
+/// An item or statement that can be formatted on its own.
+#[derive(Clone, Copy)]
+enum Node<'a, 'ast> {
+    Item(&'a ast::Item<'ast>),
+    Stmt(&'a ast::Stmt<'ast>),
+}
+
+impl<'a, 'ast> Node<'a, 'ast> {
+    fn span(&self) -> Span {
+        match *self {
+            Self::Item(item) => item.span,
+            Self::Stmt(stmt) => stmt.span,
+        }
+    }
+
+    /// Returns the items and statements nested in the node.
+    ///
+    /// Blocks are flattened into their statements, so that braces are part of the node header.
+    fn children(&self) -> Vec<Self> {
+        let stmts = |stmt: &'a ast::Stmt<'ast>| match &stmt.kind {
+            ast::StmtKind::Block(block) => block.iter().map(Self::Stmt).collect(),
+            _ => vec![Self::Stmt(stmt)],
+        };
+        match *self {
+            Self::Item(item) => match &item.kind {
+                ast::ItemKind::Contract(contract) => contract.body.iter().map(Self::Item).collect(),
+                ast::ItemKind::Function(func) => {
+                    func.body.iter().flat_map(|body| body.iter().map(Self::Stmt)).collect()
+                }
+                _ => vec![],
+            },
+            Self::Stmt(stmt) => match &stmt.kind {
+                ast::StmtKind::Block(block) | ast::StmtKind::UncheckedBlock(block) => {
+                    block.iter().map(Self::Stmt).collect()
+                }
+                ast::StmtKind::If(_, then, els) => {
+                    let mut children = stmts(then);
+                    if let Some(els) = els {
+                        children.extend(stmts(els));
+                    }
+                    children
+                }
+                ast::StmtKind::While(_, body)
+                | ast::StmtKind::DoWhile(body, _)
+                | ast::StmtKind::For { body, .. } => stmts(body),
+                _ => vec![],
+            },
+        }
+    }
+}
+
+/// The lines selected for formatting.
+struct LineSelection<'a> {
+    source_map: &'a SourceMap,
+    /// Byte offsets of the start of each line.
+    line_starts: Vec<usize>,
+    lines: &'a [RangeInclusive<usize>],
+}
+
+impl<'a> LineSelection<'a> {
+    fn new(source_map: &'a SourceMap, src: &str, lines: &'a [RangeInclusive<usize>]) -> Self {
+        let line_starts =
+            std::iter::once(0).chain(src.match_indices('\n').map(|(i, _)| i + 1)).collect();
+        Self { source_map, line_starts, lines }
+    }
+
+    /// Returns the byte range and the 1-based first and last lines of a span.
+    fn lines_of(&self, span: Span) -> Option<(Range<usize>, RangeInclusive<usize>)> {
+        let range = self.source_map.span_to_range(span).ok()?;
+        let line = |offset: usize| self.line_starts.partition_point(|&start| start <= offset);
+        let lines = line(range.start)..=line(range.end.saturating_sub(1).max(range.start));
+        Some((range, lines))
+    }
+
+    fn is_selected(&self, line: usize) -> bool {
+        self.lines.iter().any(|range| range.contains(&line))
+    }
+
+    /// Collects the byte ranges of the innermost nodes to format and of their formatted
+    /// counterparts, in source order.
+    ///
+    /// A node is formatted as a whole if one of its own lines is selected, i.e. its first or last
+    /// line, or a line outside of its children. Otherwise only its selected children are.
+    fn collect(
+        &self,
+        node: Node<'_, '_>,
+        formatted: Node<'_, '_>,
+        out: &mut Vec<(Range<usize>, Range<usize>)>,
+    ) -> Option<()> {
+        let (range, lines) = self.lines_of(node.span())?;
+        if !lines.clone().any(|line| self.is_selected(line)) {
+            return Some(());
+        }
+
+        let (children, formatted_children) = (node.children(), formatted.children());
+        if children.len() != formatted_children.len() {
+            return None;
+        }
+        let child_lines = children
+            .iter()
+            .map(|child| Some(self.lines_of(child.span())?.1))
+            .collect::<Option<Vec<_>>>()?;
+        let own_line_selected = lines.clone().any(|line| {
+            self.is_selected(line)
+                && (line == *lines.start()
+                    || line == *lines.end()
+                    || !child_lines.iter().any(|child| child.contains(&line)))
+        });
+        if own_line_selected {
+            let (formatted_range, _) = self.lines_of(formatted.span())?;
+            out.push((range, formatted_range));
+            return Some(());
+        }
+
+        for (child, formatted_child) in children.into_iter().zip(formatted_children) {
+            self.collect(child, formatted_child, out)?;
+        }
+        Some(())
+    }
+}
+
 fn parse_inline_config<'ast>(
     sess: &Session,
     comments: &Comments,
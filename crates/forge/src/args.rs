@@ -31,7 +31,7 @@ pub fn setup() -> Result<()> {
 pub fn run_command(args: Forge) -> Result<()> {
     // Set the execution context based on the subcommand.
     let context = match &args.cmd {
-        ForgeSubcommand::Test(_) => ForgeContext::Test,
+        ForgeSubcommand::Test(_) | ForgeSubcommand::Fuzz(_) => ForgeContext::Test,
         ForgeSubcommand::Coverage(_) => ForgeContext::Coverage,
         ForgeSubcommand::Snapshot(_) => ForgeContext::Snapshot,
         ForgeSubcommand::Script(cmd) => {
@@ -60,6 +60,7 @@ pub fn run_command(args: Forge) -> Result<()> {
                 outcome.ensure_ok(silent)
             }
         }
+        ForgeSubcommand::Fuzz(cmd) => global.block_on(cmd.run()),
         ForgeSubcommand::Script(cmd) => global.block_on(cmd.run_script()),
         ForgeSubcommand::Coverage(cmd) => {
             if cmd.is_watch() {
@@ -130,6 +131,7 @@ pub fn run_command(args: Forge) -> Result<()> {
         ForgeSubcommand::Selectors { command } => global.block_on(command.run()),
         ForgeSubcommand::Generate(cmd) => match cmd.sub {
             GenerateSubcommands::Test(cmd) => cmd.run(),
+            GenerateSubcommands::InvariantHandler(cmd) => cmd.run(),
         },
         ForgeSubcommand::Compiler(cmd) => cmd.run(),
         ForgeSubcommand::Soldeer(cmd) => global.block_on(cmd.run()),
@@ -1,7 +1,7 @@
 use alloy_primitives::map::HashSet;
-use clap::{Parser, ValueHint};
+use clap::{Parser, ValueEnum, ValueHint};
 use eyre::Result;
-use forge_sol_macro_gen::{MultiSolMacroGen, SolMacroGen};
+use forge_sol_macro_gen::{MultiSolMacroGen, MultiTsBindingGen, SolMacroGen, TsBindingGen};
 use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
 use foundry_common::{compile::ProjectCompiler, fs::json_files};
 use foundry_config::impl_figment_convert;
@@ -38,6 +38,18 @@ pub struct BindArgs {
     #[arg(long, conflicts_with_all = &["select", "skip"])]
     pub select_all: bool,
 
+    /// The language of the bindings to generate.
+    ///
+    /// TypeScript bindings are written as one module per contract, exporting the ABI `as const`,
+    /// the deploy bytecode, event and custom error types, and a custom error decoder.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = BindingsLang::Rust,
+        conflicts_with_all = &["module", "skip_cargo_toml", "alloy_version", "alloy_rev"]
+    )]
+    pub lang: BindingsLang,
+
     /// The name of the Rust crate to generate.
     ///
     /// This should be a valid crates.io crate name,
@@ -211,8 +223,32 @@ fn get_solmacrogen(&self, artifacts: &Path) -> Result<MultiSolMacroGen> {
         Ok(multi)
     }
 
+    fn get_ts_binding_gen(&self, artifacts: &Path) -> Result<MultiTsBindingGen> {
+        let mut dup = HashSet::<String>::default();
+        let instances = self
+            .get_json_files(artifacts)?
+            .filter_map(|(name, path)| {
+                trace!(?path, "parsing TsBindingGen from file");
+                if dup.insert(name.clone()) { Some(TsBindingGen::new(path, name)) } else { None }
+            })
+            .collect::<Vec<_>>();
+
+        let multi = MultiTsBindingGen::new(instances);
+        eyre::ensure!(!multi.instances.is_empty(), "No contract artifacts found");
+        Ok(multi)
+    }
+
     /// Check that the existing bindings match the expected abigen output
     fn check_existing_bindings(&self, artifacts: &Path, bindings_root: &Path) -> Result<()> {
+        if self.lang == BindingsLang::Typescript {
+            let mut bindings = self.get_ts_binding_gen(artifacts)?;
+            bindings.generate_bindings()?;
+            sh_println!("Checking bindings for {} contracts", bindings.instances.len())?;
+            bindings.check_consistency(bindings_root, self.single_file)?;
+            sh_println!("OK.")?;
+            return Ok(());
+        }
+
         let mut bindings = self.get_solmacrogen(artifacts)?;
         bindings.generate_bindings(!self.skip_extra_derives)?;
         sh_println!("Checking bindings for {} contracts", bindings.instances.len())?;
@@ -232,6 +268,13 @@ fn check_existing_bindings(&self, artifacts: &Path, bindings_root: &Path) -> Res
 
     /// Generate the bindings
     fn generate_bindings(&self, artifacts: &Path, bindings_root: &Path) -> Result<()> {
+        if self.lang == BindingsLang::Typescript {
+            let mut bindings = self.get_ts_binding_gen(artifacts)?;
+            sh_println!("Generating bindings for {} contracts", bindings.instances.len())?;
+            trace!(single_file = self.single_file, "generating typescript modules");
+            return bindings.write_to_dir(bindings_root, self.single_file);
+        }
+
         let mut solmacrogen = self.get_solmacrogen(artifacts)?;
         sh_println!("Generating bindings for {} contracts", solmacrogen.instances.len())?;
 
@@ -261,6 +304,17 @@ fn generate_bindings(&self, artifacts: &Path, bindings_root: &Path) -> Result<()
     }
 }
 
+/// The language of the generated bindings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum BindingsLang {
+    /// A Rust crate or module using `alloy`'s `sol!` macro.
+    #[default]
+    Rust,
+    /// TypeScript modules.
+    #[value(alias = "ts")]
+    Typescript,
+}
+
 pub enum Filter {
     All,
     Select(Vec<regex::Regex>),
@@ -1,7 +1,10 @@
 use super::{install, watch::WatchArgs};
 use clap::Parser;
 use eyre::{Context, Result};
-use forge_lint::{linter::Linter, sol::SolidityLinter};
+use forge_lint::{
+    linter::Linter,
+    sol::{SolidityLinter, custom::CustomLints},
+};
 use foundry_cli::{
     opts::{BuildOpts, configure_pcx_from_solc, get_solar_sources_from_compile_output},
     utils::{LoadConfig, cache_local_signatures},
@@ -131,6 +134,7 @@ fn lint(
     ) -> Result<()> {
         let format_json = shell::is_json();
         if project.compiler.solc.is_some() && !shell::is_quiet() {
+            let custom_lints = CustomLints::new(&config.lint.load_custom_rules(&config.root)?)?;
             let linter = SolidityLinter::new(config.project_paths())
                 .with_json_emitter(format_json)
                 .with_description(!format_json)
@@ -147,10 +151,15 @@ fn lint(
                             .lint
                             .exclude_lints
                             .iter()
-                            .filter_map(|s| forge_lint::sol::SolLint::try_from(s.as_str()).ok())
+                            .filter_map(|s| {
+                                custom_lints
+                                    .get(s)
+                                    .or_else(|| forge_lint::sol::SolLint::try_from(s.as_str()).ok())
+                            })
                             .collect(),
                     )
                 })
+                .with_custom_lints(custom_lints)
                 .with_mixed_case_exceptions(&config.lint.mixed_case_exceptions);
 
             // Expand ignore globs and canonicalize from the get go
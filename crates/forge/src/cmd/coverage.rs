@@ -1,14 +1,15 @@
 use super::{install, test::TestArgs, watch::WatchArgs};
 use crate::coverage::{
-    BytecodeReporter, ContractId, CoverageReport, CoverageReporter, CoverageSummaryReporter,
-    DebugReporter, ItemAnchor, LcovReporter,
+    BytecodeReporter, CoberturaReporter, ContractId, CoverageReport, CoverageReporter,
+    CoverageSummaryReporter, DebugReporter, DiffCoverageReporter, HtmlReporter, ItemAnchor,
+    LcovReporter,
     analysis::{SourceAnalysis, SourceFiles},
     anchors::find_anchors,
 };
 use alloy_primitives::{Address, Bytes, U256, map::HashMap};
 use clap::{Parser, ValueEnum, ValueHint};
 use eyre::Result;
-use foundry_cli::utils::{LoadConfig, STATIC_FUZZ_SEED};
+use foundry_cli::utils::{Git, LoadConfig, STATIC_FUZZ_SEED};
 use foundry_common::{compile::ProjectCompiler, errors::convert_solar_errors};
 use foundry_compilers::{
     Artifact, ArtifactId, Project, ProjectCompileOutput, ProjectPathsConfig, VYPER_EXTENSIONS,
@@ -52,7 +53,8 @@ pub struct CoverageArgs {
 
     /// The path to output the report.
     ///
-    /// If not specified, the report will be stored in the root of the project.
+    /// If not specified, the report will be stored in the root of the project: `lcov.info` for
+    /// LCOV, `cobertura.xml` for Cobertura, and the `coverage-report` directory for HTML.
     #[arg(
         long,
         short,
@@ -61,6 +63,16 @@ pub struct CoverageArgs {
     )]
     report_file: Option<PathBuf>,
 
+    /// Report the coverage of the Solidity lines changed since the given git revision.
+    ///
+    /// Uncommitted changes in the working tree are included.
+    #[arg(long, value_name = "GIT_REF")]
+    diff_base: Option<String>,
+
+    /// Fail if the line coverage of the changed lines is below the given percentage.
+    #[arg(long, value_name = "PERCENT", requires = "diff_base", value_parser = parse_percentage)]
+    min_diff_coverage: Option<f64>,
+
     /// Whether to include libraries in the coverage report.
     #[arg(long)]
     include_libs: bool,
@@ -96,7 +108,7 @@ pub async fn run(mut self) -> Result<()> {
             (project.paths, output)
         };
 
-        self.populate_reporters(&paths.root);
+        self.populate_reporters(&paths.root, &config)?;
 
         sh_println!("Analysing contracts...")?;
         let report = self.prepare(&paths, &mut output)?;
@@ -105,19 +117,30 @@ pub async fn run(mut self) -> Result<()> {
         self.collect(&paths.root, &output, report, config, evm_opts).await
     }
 
-    fn populate_reporters(&mut self, root: &Path) {
+    fn populate_reporters(&mut self, root: &Path, config: &Config) -> Result<()> {
         self.reporters = self
             .report
             .iter()
             .map(|report_kind| match report_kind {
                 CoverageReportKind::Summary => {
-                    Box::<CoverageSummaryReporter>::default() as Box<dyn CoverageReporter>
+                    Box::new(CoverageSummaryReporter::new(config.coverage.clone()))
+                        as Box<dyn CoverageReporter>
                 }
                 CoverageReportKind::Lcov => {
                     let path =
                         root.join(self.report_file.as_deref().unwrap_or("lcov.info".as_ref()));
                     Box::new(LcovReporter::new(path, self.lcov_version.clone()))
                 }
+                CoverageReportKind::Cobertura => {
+                    let path =
+                        root.join(self.report_file.as_deref().unwrap_or("cobertura.xml".as_ref()));
+                    Box::new(CoberturaReporter::new(root.to_path_buf(), path))
+                }
+                CoverageReportKind::Html => {
+                    let path = root
+                        .join(self.report_file.as_deref().unwrap_or("coverage-report".as_ref()));
+                    Box::new(HtmlReporter::new(root.to_path_buf(), path))
+                }
                 CoverageReportKind::Bytecode => Box::new(BytecodeReporter::new(
                     root.to_path_buf(),
                     root.join("bytecode-coverage"),
@@ -125,6 +148,13 @@ fn populate_reporters(&mut self, root: &Path) {
                 CoverageReportKind::Debug => Box::new(DebugReporter),
             })
             .collect::<Vec<_>>();
+
+        if let Some(base) = &self.diff_base {
+            let changed = Git::new(root).changed_lines(base, ["*.sol"])?;
+            self.reporters
+                .push(Box::new(DiffCoverageReporter::new(changed, self.min_diff_coverage)));
+        }
+        Ok(())
     }
 
     /// Builds the project.
@@ -305,11 +335,16 @@ async fn collect(
 
     #[instrument(name = "Coverage::report", skip_all)]
     fn report(&mut self, report: &CoverageReport) -> Result<()> {
+        // Run all reporters before failing, as some of them enforce thresholds.
+        let mut result = Ok(());
         for reporter in &mut self.reporters {
             let _guard = debug_span!("reporter.report", kind=%reporter.name()).entered();
-            reporter.report(report)?;
+            let res = reporter.report(report);
+            if result.is_ok() {
+                result = res;
+            }
         }
-        Ok(())
+        result
     }
 
     pub fn is_watch(&self) -> bool {
@@ -327,6 +362,8 @@ pub enum CoverageReportKind {
     #[default]
     Summary,
     Lcov,
+    Cobertura,
+    Html,
     Debug,
     Bytecode,
 }
@@ -421,6 +458,14 @@ fn parse_lcov_version(s: &str) -> Result<Version, String> {
     Ok(Version::new(c.major, c.minor.unwrap_or(0), c.patch.unwrap_or(0)))
 }
 
+fn parse_percentage(s: &str) -> Result<f64, String> {
+    let value = s.trim_end_matches('%').parse::<f64>().map_err(|e| e.to_string())?;
+    if !(0.0..=100.0).contains(&value) {
+        return Err("percentage must be between 0 and 100".to_string());
+    }
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -433,4 +478,12 @@ fn lcov_version() {
         assert_eq!(parse_lcov_version("1.1").unwrap(), Version::new(1, 1, 0));
         assert_eq!(parse_lcov_version("1.11").unwrap(), Version::new(1, 11, 0));
     }
+
+    #[test]
+    fn percentage() {
+        assert_eq!(parse_percentage("80").unwrap(), 80.);
+        assert_eq!(parse_percentage("92.5%").unwrap(), 92.5);
+        assert!(parse_percentage("101").is_err());
+        assert!(parse_percentage("-1").is_err());
+    }
 }
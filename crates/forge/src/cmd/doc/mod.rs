@@ -2,10 +2,11 @@
 use clap::{Parser, ValueHint};
 use eyre::Result;
 use forge_doc::{
-    ContractInheritance, Deployments, DocBuilder, GitSource, InferInlineHyperlinks, Inheritdoc,
+    ContractInheritance, Deployments, DocBuilder, DocFormat, GitSource, InferInlineHyperlinks,
+    Inheritdoc, NatSpecCoverage,
 };
 use foundry_cli::opts::GH_REPO_PREFIX_REGEX;
-use foundry_common::compile::ProjectCompiler;
+use foundry_common::{compile::ProjectCompiler, shell};
 use foundry_config::{Config, load_config_with_root};
 use std::{path::PathBuf, process::Command};
 
@@ -32,6 +33,12 @@ pub struct DocArgs {
     )]
     out: Option<PathBuf>,
 
+    /// The output format.
+    ///
+    /// The JSON and HTML formats are written without mdbook, and cannot be built or served.
+    #[arg(long, value_enum, default_value = "markdown", conflicts_with_all = ["build", "serve"])]
+    format: DocFormat,
+
     /// Build the `mdbook` from generated files.
     #[arg(long, short)]
     build: bool,
@@ -63,6 +70,15 @@ pub struct DocArgs {
     /// Whether to create docs for external libraries.
     #[arg(long, short)]
     include_libraries: bool,
+
+    /// Report the public functions, events, errors and state variables lacking NatSpec tags,
+    /// instead of writing the documentation.
+    #[arg(long, conflicts_with_all = ["build", "serve"])]
+    coverage: bool,
+
+    /// Fail if the NatSpec coverage is below the given percentage.
+    #[arg(long, value_name = "PERCENT", requires = "coverage")]
+    coverage_threshold: Option<f64>,
 }
 
 impl DocArgs {
@@ -103,6 +119,7 @@ pub async fn run(self) -> Result<()> {
             self.include_libraries,
         )
         .with_should_build(self.build)
+        .with_format(self.format)
         .with_config(doc_config.clone())
         .with_fmt(config.fmt)
         .with_preprocessor(ContractInheritance { include_libraries: self.include_libraries })
@@ -119,6 +136,11 @@ pub async fn run(self) -> Result<()> {
             builder = builder.with_preprocessor(Deployments { root: root.clone(), deployments });
         }
 
+        if self.coverage {
+            let coverage = NatSpecCoverage::new(&builder.documents(compiler)?, root);
+            return report_coverage(&coverage, self.coverage_threshold);
+        }
+
         builder.build(compiler)?;
 
         if self.serve {
@@ -141,3 +163,41 @@ pub fn config(&self) -> Result<Config> {
         load_config_with_root(self.root.as_deref())
     }
 }
+
+/// Prints the NatSpec coverage and fails if it is below the threshold.
+fn report_coverage(coverage: &NatSpecCoverage, threshold: Option<f64>) -> Result<()> {
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string(coverage)?)?;
+    } else {
+        for contract in &coverage.contracts {
+            sh_println!(
+                "{}:{} {}/{} ({:.2}%)",
+                contract.path.display(),
+                contract.name,
+                contract.documented,
+                contract.total,
+                contract.percentage()
+            )?;
+            for item in &contract.missing {
+                sh_println!("  {} {}: missing {}", item.kind, item.name, item.tags.join(", "))?;
+            }
+        }
+        sh_println!(
+            "NatSpec coverage: {}/{} ({:.2}%)",
+            coverage.documented(),
+            coverage.total(),
+            coverage.percentage()
+        )?;
+    }
+
+    if let Some(threshold) = threshold
+        && coverage.percentage() < threshold
+    {
+        eyre::bail!(
+            "NatSpec coverage {:.2}% is below the threshold of {threshold}%",
+            coverage.percentage()
+        );
+    }
+
+    Ok(())
+}
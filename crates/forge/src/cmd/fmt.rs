@@ -1,7 +1,7 @@
 use super::watch::WatchArgs;
 use clap::{Parser, ValueHint};
 use eyre::Result;
-use foundry_cli::utils::{FoundryPathExt, LoadConfig};
+use foundry_cli::utils::{FoundryPathExt, Git, LoadConfig};
 use foundry_common::{errors::convert_solar_errors, fs};
 use foundry_compilers::{compilers::solc::SolcLanguage, solc::SOLC_EXTENSIONS};
 use foundry_config::{filter::expand_globs, impl_figment_convert_basic};
@@ -9,9 +9,11 @@
 use similar::{ChangeTag, TextDiff};
 use solar::sema::Compiler;
 use std::{
+    collections::{BTreeMap, BTreeSet},
     fmt::{self, Write},
     io,
     io::Write as _,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -42,6 +44,24 @@ pub struct FmtArgs {
     #[arg(long, short)]
     raw: bool,
 
+    /// Only format the given lines, e.g. `10:20`.
+    ///
+    /// Items and statements overlapping the range are formatted as a whole.
+    #[arg(
+        long,
+        value_name = "START:END",
+        value_parser = parse_line_range,
+        conflicts_with = "changed_since"
+    )]
+    range: Option<RangeInclusive<usize>>,
+
+    /// Only format the lines changed since the given git revision.
+    ///
+    /// Items and statements overlapping the changes are formatted as a whole, and unchanged files
+    /// are left untouched.
+    #[arg(long, value_name = "REV")]
+    changed_since: Option<String>,
+
     #[command(flatten)]
     pub watch: WatchArgs,
 }
@@ -125,6 +145,23 @@ pub fn run(self) -> Result<()> {
             }
         };
 
+        // The changed lines of each file, by canonical path.
+        let changed = match &self.changed_since {
+            Some(rev) => Some(
+                Git::new(&config.root)
+                    .changed_lines(rev, ["*.sol"])?
+                    .into_iter()
+                    .filter_map(|(path, lines)| {
+                        Some((
+                            fs::canonicalize_path(config.root.join(path)).ok()?,
+                            line_ranges(&lines),
+                        ))
+                    })
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+            None => None,
+        };
+
         let mut compiler = Compiler::new(
             solar::interface::Session::builder().with_buffer_emitter(Default::default()).build(),
         );
@@ -156,7 +193,38 @@ pub fn run(self) -> Result<()> {
                 .filter_map(|source_unit| {
                     let path = source_unit.file.name.as_real();
                     let original = source_unit.file.src.as_str();
-                    let formatted = forge_fmt::format_ast(gcx, source_unit, fmt_config.clone())?;
+                    // Falls back to formatting the whole file if the formatted items can't be
+                    // matched with the original ones.
+                    let format_lines = |lines: &[RangeInclusive<usize>]| {
+                        forge_fmt::format_ast_lines(gcx, source_unit, fmt_config.clone(), lines)
+                            .or_else(|| {
+                                let name = path.map_or_else(
+                                    || "stdin".to_string(),
+                                    |path| path.display().to_string(),
+                                );
+                                let _ = sh_warn!(
+                                    "Could not format only the selected lines of {name}, \
+                                     formatting the whole file"
+                                );
+                                forge_fmt::format_ast(gcx, source_unit, fmt_config.clone())
+                            })
+                    };
+                    let formatted = match (&self.range, &changed) {
+                        (Some(range), _) => format_lines(std::slice::from_ref(range))?,
+                        (None, Some(changed)) => {
+                            match path
+                                .and_then(|path| fs::canonicalize_path(path).ok())
+                                .and_then(|path| changed.get(&path))
+                            {
+                                Some(lines) => format_lines(lines)?,
+                                // Unchanged files are left as-is.
+                                None => original.to_string(),
+                            }
+                        }
+                        (None, None) => {
+                            forge_fmt::format_ast(gcx, source_unit, fmt_config.clone())?
+                        }
+                    };
                     let from_stdin = path.is_none();
 
                     // Return formatted code when read from stdin and raw enabled.
@@ -227,6 +295,30 @@ enum Input {
     Paths(Vec<PathBuf>),
 }
 
+/// Parses a `<start>:<end>` range of 1-based lines.
+fn parse_line_range(s: &str) -> Result<RangeInclusive<usize>, String> {
+    let (start, end) = s.split_once(':').ok_or("expected `<start>:<end>`")?;
+    let start = start.trim().parse::<usize>().map_err(|e| e.to_string())?;
+    let end = end.trim().parse::<usize>().map_err(|e| e.to_string())?;
+    if start == 0 || end < start {
+        return Err("lines start at 1 and the end must not precede the start".to_string());
+    }
+    Ok(start..=end)
+}
+
+/// Merges consecutive lines into ranges.
+fn line_ranges(lines: &BTreeSet<u32>) -> Vec<RangeInclusive<usize>> {
+    let mut ranges: Vec<RangeInclusive<usize>> = Vec::new();
+    for &line in lines {
+        let line = line as usize;
+        match ranges.last_mut() {
+            Some(range) if *range.end() + 1 == line => *range = *range.start()..=line,
+            _ => ranges.push(line..=line),
+        }
+    }
+    ranges
+}
+
 struct Line(Option<usize>);
 
 impl fmt::Display for Line {
@@ -0,0 +1,347 @@
+use super::test::{ProjectPathsAwareFilter, TestArgs};
+use crate::{
+    MultiContractRunnerBuilder, TestFilter,
+    result::{SuiteResult, TestKind, TestResult, TestStatus},
+};
+use alloy_primitives::{hex, keccak256};
+use clap::{Parser, Subcommand, builder::RangedU64ValueParser};
+use eyre::Result;
+use foundry_cli::utils::LoadConfig;
+use foundry_common::{TestFunctionKind, compile::ProjectCompiler, fs, shell};
+use foundry_compilers::{ProjectCompileOutput, compilers::multi::MultiCompiler};
+use foundry_config::Config;
+use foundry_evm::{executors::EarlyExit, opts::EvmOpts};
+use regex::Regex;
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// CLI arguments for `forge fuzz`.
+#[derive(Clone, Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct FuzzArgs {
+    #[command(subcommand)]
+    pub command: Option<FuzzSubcommands>,
+
+    /// Only fuzz the fuzz and invariant tests matching the specified regex pattern.
+    #[arg(value_name = "TEST_PATTERN")]
+    pub pattern: Option<Regex>,
+
+    /// Stop the campaign after the given number of seconds.
+    ///
+    /// By default, the campaign runs until interrupted.
+    #[arg(long, value_name = "SECONDS")]
+    pub duration: Option<u64>,
+
+    /// Number of seconds between two stats reports.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 60,
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..),
+    )]
+    pub stats_interval: u64,
+
+    /// All test arguments are supported
+    #[command(flatten)]
+    pub test: TestArgs,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum FuzzSubcommands {
+    /// Shrink the persisted corpus of the matching tests, keeping the same edge coverage.
+    Minimize(FuzzMinimizeArgs),
+}
+
+/// CLI arguments for `forge fuzz minimize`.
+#[derive(Clone, Debug, Parser)]
+pub struct FuzzMinimizeArgs {
+    /// Only minimize the corpus of the tests matching the specified regex pattern.
+    #[arg(value_name = "TEST_PATTERN")]
+    pub pattern: Option<Regex>,
+
+    /// All test arguments are supported
+    #[command(flatten)]
+    pub test: TestArgs,
+}
+
+impl FuzzArgs {
+    pub async fn run(self) -> Result<()> {
+        match self.command {
+            Some(FuzzSubcommands::Minimize(args)) => args.run().await,
+            None => self.run_campaign().await,
+        }
+    }
+
+    /// Runs the matching tests in rounds of `stats_interval` seconds, until interrupted or the
+    /// duration of the campaign is reached. The corpus is persisted, so a new campaign resumes
+    /// from the coverage reached by the previous ones.
+    async fn run_campaign(self) -> Result<()> {
+        let (config, evm_opts, output, filter) = prepare(&self.test, self.pattern.as_ref())?;
+        let filter = CampaignFilter(&filter);
+        let corpus_dirs = corpus_dirs(&config);
+
+        // Stop the running round on interrupt, so that its results are recorded and the summary is
+        // printed.
+        let interrupt = EarlyExit::interrupt_only();
+        tokio::spawn({
+            let interrupt = interrupt.clone();
+            async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    interrupt.record_exit();
+                }
+            }
+        });
+
+        let interval = Duration::from_secs(self.stats_interval);
+        let duration = self.duration.map(Duration::from_secs);
+        let start = Instant::now();
+        let mut failures = BTreeSet::new();
+        while !interrupt.should_stop() {
+            let round = match duration {
+                Some(duration) => {
+                    let remaining = duration.saturating_sub(start.elapsed());
+                    if remaining.is_zero() {
+                        break;
+                    }
+                    remaining.min(interval)
+                }
+                None => interval,
+            };
+
+            let mut round_config = config.clone();
+            let timeout = round.as_secs().clamp(1, u32::MAX as u64) as u32;
+            round_config.fuzz.timeout = Some(timeout);
+            round_config.invariant.timeout = Some(timeout);
+
+            let round_start = Instant::now();
+            let results =
+                run_tests(round_config, &evm_opts, &output, &filter, Some(&interrupt)).await?;
+            let round_elapsed = round_start.elapsed().as_secs_f64().max(f64::EPSILON);
+
+            let mut execs = 0;
+            let mut edges = 0;
+            for (suite, suite_result) in &results {
+                let contract = suite.split(':').next_back().unwrap();
+                for (signature, result) in &suite_result.test_results {
+                    execs += match &result.kind {
+                        TestKind::Fuzz { runs, .. } => *runs,
+                        TestKind::Invariant { calls, .. } => *calls,
+                        _ => 0,
+                    };
+                    edges += result.edges_seen;
+
+                    if result.status == TestStatus::Failure {
+                        let persist_dir = match result.kind {
+                            TestKind::Invariant { .. } => &config.invariant.failure_persist_dir,
+                            _ => &config.fuzz.failure_persist_dir,
+                        };
+                        let persist_dir = config.root.join(persist_dir.as_ref().unwrap());
+                        let (path, is_new) =
+                            record_failure(&persist_dir, contract, signature, result)?;
+                        if is_new {
+                            sh_println!(
+                                "New failure in {contract}::{signature}: {}\nWritten to {}",
+                                result.reason.as_deref().unwrap_or("unknown reason"),
+                                path.display()
+                            )?;
+                        }
+                        failures.insert(path);
+                    }
+                }
+            }
+
+            let corpus: usize = corpus_dirs.iter().map(|dir| corpus_size(dir)).sum();
+            sh_println!(
+                "[{}s] execs: {execs} ({:.0}/s), edges: {edges}, corpus: {corpus}, failures: {}",
+                start.elapsed().as_secs(),
+                execs as f64 / round_elapsed,
+                failures.len()
+            )?;
+        }
+
+        sh_println!(
+            "Campaign {} after {}s, {} unique failures",
+            if interrupt.should_stop() { "interrupted" } else { "finished" },
+            start.elapsed().as_secs(),
+            failures.len()
+        )?;
+        if !failures.is_empty() {
+            eyre::bail!("fuzz campaign found {} unique failures", failures.len());
+        }
+        Ok(())
+    }
+}
+
+impl FuzzMinimizeArgs {
+    pub async fn run(self) -> Result<()> {
+        let (mut config, evm_opts, output, filter) = prepare(&self.test, self.pattern.as_ref())?;
+        let filter = CampaignFilter(&filter);
+        let corpus_dirs = corpus_dirs(&config);
+
+        // Loading the corpus minimizes it, no new inputs are generated.
+        for corpus in [&mut config.fuzz.corpus, &mut config.invariant.corpus] {
+            corpus.corpus_minimize = true;
+        }
+        config.fuzz.runs = 0;
+        config.fuzz.timeout = None;
+        config.invariant.runs = 0;
+        config.invariant.timeout = None;
+        config.invariant.threads = 1;
+
+        let before: usize = corpus_dirs.iter().map(|dir| corpus_size(dir)).sum();
+        run_tests(config, &evm_opts, &output, &filter, None).await?;
+        let after: usize = corpus_dirs.iter().map(|dir| corpus_size(dir)).sum();
+
+        sh_println!("Minimized corpus from {before} to {after} entries")?;
+        Ok(())
+    }
+}
+
+/// Compiles the project and returns the config, EVM options, compiler output and filter of a
+/// campaign over the tests matching `pattern`.
+fn prepare(
+    test: &TestArgs,
+    pattern: Option<&Regex>,
+) -> Result<(Config, EvmOpts, ProjectCompileOutput, ProjectPathsAwareFilter)> {
+    let (mut config, evm_opts) = test.load_config_and_evm_opts()?;
+
+    let mut filter = test.filter(&config)?;
+    if let Some(pattern) = pattern {
+        filter.args_mut().test_pattern = Some(pattern.clone());
+    }
+
+    let project = config.project()?;
+    let output = ProjectCompiler::new()
+        .dynamic_test_linking(config.dynamic_test_linking)
+        .quiet(shell::is_json())
+        .files(test.get_sources_to_compile(&config, &filter)?)
+        .compile(&project)?;
+
+    // Campaigns are coverage guided and record their failures, persisting them in the cache if
+    // no directory is configured.
+    let cache = config.root.join(&config.cache_path);
+    let fuzz_dir = cache.join("fuzz");
+    let invariant_dir = cache.join("invariant");
+    config.fuzz.corpus.corpus_dir.get_or_insert_with(|| fuzz_dir.join("corpus"));
+    config.fuzz.failure_persist_dir.get_or_insert(fuzz_dir);
+    config.invariant.corpus.corpus_dir.get_or_insert_with(|| invariant_dir.join("corpus"));
+    config.invariant.failure_persist_dir.get_or_insert(invariant_dir);
+    // Gas report traces are never used.
+    config.fuzz.gas_report_samples = 0;
+    config.invariant.gas_report_samples = 0;
+    config.show_progress = false;
+
+    Ok((config, evm_opts, output, filter))
+}
+
+/// Runs the tests matching the filter once, stopping early if `interrupt` is triggered.
+async fn run_tests(
+    config: Config,
+    evm_opts: &EvmOpts,
+    output: &ProjectCompileOutput,
+    filter: &dyn TestFilter,
+    interrupt: Option<&EarlyExit>,
+) -> Result<BTreeMap<String, SuiteResult>> {
+    let evm_opts = evm_opts.clone();
+    let env = evm_opts.evm_env().await?;
+
+    let config = Arc::new(config);
+    let mut runner = MultiContractRunnerBuilder::new(config.clone())
+        .initial_balance(evm_opts.initial_balance)
+        .evm_spec(config.evm_spec_id())
+        .sender(evm_opts.sender)
+        .with_fork(evm_opts.get_fork(&config, env.clone()))
+        .enable_isolation(evm_opts.isolate)
+        .networks(evm_opts.networks)
+        .build::<MultiCompiler>(output, env, evm_opts)?;
+
+    if let Some(interrupt) = interrupt {
+        runner.tcfg.early_exit = interrupt.clone();
+    }
+
+    if runner.matching_test_functions(filter).next().is_none() {
+        eyre::bail!("no fuzz or invariant tests match the provided pattern");
+    }
+
+    runner.test_collect(filter)
+}
+
+/// Records the failure of a test in `persist_dir`, returning the path of the record and whether
+/// the failure is new. Failures are unique by test and reason.
+///
+/// The counterexample persisted by the test is kept, so that `forge test` replays it.
+fn record_failure(
+    persist_dir: &Path,
+    contract: &str,
+    signature: &str,
+    result: &TestResult,
+) -> Result<(PathBuf, bool)> {
+    let name = signature.split('(').next().unwrap_or(signature);
+    let failures_dir = persist_dir.join("failures").join(contract);
+
+    let hash = keccak256(format!("{signature}:{}", result.reason.as_deref().unwrap_or_default()));
+    let path = failures_dir.join(format!("{name}-{}.json", hex::encode(&hash[..4])));
+    if path.exists() {
+        return Ok((path, false));
+    }
+
+    fs::create_dir_all(&failures_dir)?;
+    fs::write_json_file(
+        &path,
+        &json!({
+            "test": format!("{contract}::{signature}"),
+            "reason": result.reason,
+            "counterexample": result.counterexample,
+        }),
+    )?;
+    Ok((path, true))
+}
+
+/// Returns the distinct corpus directories of fuzz and invariant tests.
+fn corpus_dirs(config: &Config) -> BTreeSet<PathBuf> {
+    [&config.fuzz.corpus.corpus_dir, &config.invariant.corpus.corpus_dir]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect()
+}
+
+/// Returns the number of corpus entries persisted in `dir` and its subdirectories.
+fn corpus_size(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else { return 0 };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                corpus_size(&path)
+            } else {
+                usize::from(!entry.file_name().to_string_lossy().contains("metadata"))
+            }
+        })
+        .sum()
+}
+
+/// Filters out the tests that are neither fuzz nor invariant tests.
+struct CampaignFilter<'a>(&'a ProjectPathsAwareFilter);
+
+impl TestFilter for CampaignFilter<'_> {
+    fn matches_test(&self, test_signature: &str) -> bool {
+        let (name, params) = test_signature.split_once('(').unwrap_or((test_signature, ")"));
+        let kind = TestFunctionKind::classify(name, params != ")");
+        (kind.is_fuzz_test() || kind.is_invariant_test()) && self.0.matches_test(test_signature)
+    }
+
+    fn matches_contract(&self, contract_name: &str) -> bool {
+        self.0.matches_contract(contract_name)
+    }
+
+    fn matches_path(&self, path: &Path) -> bool {
+        self.0.matches_path(path)
+    }
+}
@@ -0,0 +1,298 @@
+use alloy_json_abi::{Function, InternalType, JsonAbi, Param, StateMutability};
+use clap::{Parser, ValueHint};
+use eyre::{OptionExt, Result};
+use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
+use foundry_common::{
+    compile::{PathOrContractInfo, ProjectCompiler},
+    find_matching_contract_artifact, find_target_path, fs,
+};
+use foundry_evm::executors::invariant::is_fuzzed_function;
+use std::{fmt::Write, path::PathBuf, str::FromStr};
+use yansi::Paint;
+
+/// CLI arguments for `forge generate invariant-handler`.
+#[derive(Debug, Parser)]
+pub struct GenerateInvariantHandlerArgs {
+    /// The identifier of the target contract in the form `(<path>:)?<contractname>`.
+    #[arg(value_parser = PathOrContractInfo::from_str)]
+    pub contract: PathOrContractInfo,
+
+    /// Path of the generated handler.
+    ///
+    /// Defaults to `<test>/handlers/<contractname>Handler.sol`.
+    #[arg(long, short, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+
+    /// Number of actors making the calls to the target contract.
+    #[arg(long, default_value_t = 3, value_name = "NUM")]
+    pub actors: usize,
+
+    #[command(flatten)]
+    build: BuildOpts,
+}
+
+impl GenerateInvariantHandlerArgs {
+    pub fn run(self) -> Result<()> {
+        let config = self.build.load_config()?;
+        let project = config.project()?;
+        let target_path = find_target_path(&project, &self.contract)?;
+        let mut output =
+            ProjectCompiler::new().quiet(true).files([target_path.clone()]).compile(&project)?;
+        let artifact =
+            find_matching_contract_artifact(&mut output, &target_path, self.contract.name())?;
+        let abi = artifact.abi.as_ref().ok_or_eyre("could not find the ABI of the contract")?;
+
+        let name = match self.contract.name() {
+            Some(name) => name.to_string(),
+            None => target_path.file_stem().unwrap().to_string_lossy().into_owned(),
+        };
+        let import = target_path.strip_prefix(&config.root).unwrap_or(&target_path);
+        let handler = HandlerGenerator::new(&name, abi, self.actors)
+            .generate(&import.to_string_lossy().replace('\\', "/"));
+        let handler = match forge_fmt::format(&handler, config.fmt.clone()).into_result() {
+            Ok(formatted) => formatted,
+            Err(e) => {
+                sh_warn!("Failed to format the handler of {name}: {e}")?;
+                handler
+            }
+        };
+
+        let out = self.out.unwrap_or_else(|| {
+            config.root.join(&config.test).join("handlers").join(format!("{name}Handler.sol"))
+        });
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&out, handler)?;
+
+        sh_println!("{} invariant handler: {}", "Generated".green(), out.display())?;
+        Ok(())
+    }
+}
+
+/// Generates the source of an invariant handler wrapping the functions of a target contract.
+struct HandlerGenerator<'a> {
+    name: &'a str,
+    instance: String,
+    abi: &'a JsonAbi,
+    actors: usize,
+    /// Whether the target is a token, exposing `balanceOf(address)`.
+    is_token: bool,
+}
+
+impl<'a> HandlerGenerator<'a> {
+    fn new(name: &'a str, abi: &'a JsonAbi, actors: usize) -> Self {
+        let mut chars = name.chars();
+        let instance = chars
+            .next()
+            .map(|first| first.to_lowercase().chain(chars).collect())
+            .unwrap_or_default();
+        let is_token = abi.function("balanceOf").is_some_and(|funcs| {
+            funcs.iter().any(|func| {
+                func.inputs.len() == 1
+                    && func.inputs[0].ty == "address"
+                    && func.outputs.len() == 1
+                    && func.outputs[0].ty == "uint256"
+            })
+        });
+        Self { name, instance, abi, actors: actors.max(1), is_token }
+    }
+
+    fn generate(&self, import: &str) -> String {
+        let Self { name, instance, actors, is_token, .. } = self;
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {{CommonBase}} from "forge-std/Base.sol";
+import {{StdUtils}} from "forge-std/StdUtils.sol";
+import {{{name}}} from "{import}";
+
+/// Invariant test handler for `{name}`, to register with `targetContract(address(handler))`.
+contract {name}Handler is CommonBase, StdUtils {{
+    {name} public {instance};
+
+    address[] public actors;
+    address internal currentActor;
+
+    /// Number of calls made to each function.
+    mapping(string => uint256) public calls;
+"#
+        );
+        if *is_token {
+            let _ = writeln!(
+                out,
+                r#"
+    /// Balances of the actors after the last call.
+    mapping(address => uint256) public ghost_balances;
+    /// Sum of the balances of the actors after the last call.
+    uint256 public ghost_sumBalances;
+"#
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            r#"
+    modifier useActor(uint256 actorIndexSeed) {{
+        currentActor = actors[bound(actorIndexSeed, 0, actors.length - 1)];
+        vm.startPrank(currentActor);
+        _;
+        vm.stopPrank();{}
+    }}
+
+    modifier countCall(string memory key) {{
+        calls[key]++;
+        _;
+    }}
+
+    constructor({name} _{instance}) {{
+        {instance} = _{instance};
+        for (uint256 i = 1; i <= {actors}; i++) {{
+            actors.push(vm.addr(i));
+        }}
+    }}"#,
+            if *is_token { "\n        _updateGhostBalances();" } else { "" }
+        );
+
+        for func in self.abi.functions().filter(|&func| is_fuzzed_function(func)) {
+            self.write_function(&mut out, func);
+        }
+
+        if *is_token {
+            let _ = writeln!(
+                out,
+                r#"
+    function _updateGhostBalances() internal {{
+        ghost_sumBalances = 0;
+        for (uint256 i; i < actors.length; i++) {{
+            uint256 balance = {instance}.balanceOf(actors[i]);
+            ghost_balances[actors[i]] = balance;
+            ghost_sumBalances += balance;
+        }}
+    }}"#
+            );
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Whether the name is used by the handler, as a member or a generated parameter.
+    fn is_reserved(&self, name: &str) -> bool {
+        ["actorIndexSeed", "callValue", "actors", "currentActor", "calls", "vm", "bound"]
+            .contains(&name)
+            || name == self.instance
+            || name == self.name
+            || name.starts_with("ghost_")
+    }
+
+    /// Writes the handler function wrapping the given target function.
+    fn write_function(&self, out: &mut String, func: &Function) {
+        let signature = func.signature();
+        if !func.inputs.iter().all(is_supported) {
+            let _ = writeln!(out, "\n    // Skipped `{signature}`: unsupported parameter types.");
+            return;
+        }
+
+        let mut names = Vec::<String>::with_capacity(func.inputs.len());
+        for (i, param) in func.inputs.iter().enumerate() {
+            let mut name =
+                if param.name.is_empty() { format!("arg{i}") } else { param.name.clone() };
+            // Suffix the names shadowing the members of the handler or its generated parameters.
+            while self.is_reserved(&name)
+                || names.contains(&name)
+                || (name != param.name && func.inputs.iter().any(|param| param.name == name))
+            {
+                name.push('_');
+            }
+            names.push(name);
+        }
+        let payable = func.state_mutability == StateMutability::Payable;
+
+        let mut params = vec!["uint256 actorIndexSeed".to_string()];
+        params.extend(func.inputs.iter().zip(&names).map(|(param, name)| {
+            let ty = match param.internal_type {
+                Some(InternalType::AddressPayable(_)) => "address payable",
+                _ => param.ty.as_str(),
+            };
+            let location = if is_reference_type(ty) { " memory" } else { "" };
+            format!("{ty}{location} {name}")
+        }));
+        if payable {
+            params.push("uint256 callValue".to_string());
+        }
+
+        let _ = writeln!(
+            out,
+            "\n    function {}({}) public useActor(actorIndexSeed) countCall(\"{signature}\") {{",
+            func.name,
+            params.join(", ")
+        );
+        for (param, name) in func.inputs.iter().zip(&names) {
+            if let Some(bounded) = bounded(param, name) {
+                let todo =
+                    if param.ty == "address" { "" } else { " // TODO: narrow down the range" };
+                let _ = writeln!(out, "        {name} = {bounded};{todo}");
+            }
+        }
+        let value = if payable {
+            let _ = writeln!(out, "        callValue = bound(callValue, 0, 100 ether);");
+            let _ = writeln!(out, "        vm.deal(currentActor, callValue);");
+            "{value: callValue}"
+        } else {
+            ""
+        };
+        let _ =
+            writeln!(out, "        {}.{}{value}({});", self.instance, func.name, names.join(", "));
+        out.push_str("    }\n");
+    }
+}
+
+/// Whether the parameter can be declared in the handler without importing user defined types.
+fn is_supported(param: &Param) -> bool {
+    param.components.is_empty()
+        && match &param.internal_type {
+            None | Some(InternalType::AddressPayable(_)) => true,
+            Some(InternalType::Other { contract: None, ty }) => *ty == param.ty,
+            Some(_) => false,
+        }
+}
+
+fn is_reference_type(ty: &str) -> bool {
+    ty == "bytes" || ty == "string" || ty.ends_with(']')
+}
+
+/// Returns the expression bounding the parameter, if it can be bounded without knowing the
+/// semantics of the target contract.
+///
+/// Addresses are bounded to the actors. Integers are bounded to a default range of at most 96
+/// bits, which fits token amounts while avoiding overflows in the target, and is meant to be
+/// narrowed down.
+fn bounded(param: &Param, name: &str) -> Option<String> {
+    let ty = param.ty.as_str();
+    if ty == "address" {
+        let actor = format!("actors[bound(uint256(uint160({name})), 0, actors.length - 1)]");
+        return Some(match param.internal_type {
+            Some(InternalType::AddressPayable(_)) => format!("payable({actor})"),
+            _ => actor,
+        });
+    }
+
+    let (signed, bits) = match ty.strip_prefix("uint") {
+        Some(bits) => (false, bits),
+        None => (true, ty.strip_prefix("int")?),
+    };
+    let bits = bits.parse::<usize>().ok()?;
+    let range_ty = format!("{}{}", if signed { "int" } else { "uint" }, bits.min(96));
+    let min = if signed { format!("type({range_ty}).min") } else { "0".to_string() };
+    let max = format!("type({range_ty}).max");
+    Some(match (signed, bits) {
+        (_, 256) => format!("bound({name}, {min}, {max})"),
+        (false, _) => format!("{ty}(bound(uint256({name}), {min}, {max}))"),
+        (true, _) => format!("{ty}(bound(int256({name}), {min}, {max}))"),
+    })
+}
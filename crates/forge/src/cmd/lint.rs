@@ -1,8 +1,8 @@
 use clap::{Parser, ValueHint};
 use eyre::{Result, eyre};
 use forge_lint::{
-    linter::Linter,
-    sol::{SolLint, SolLintError, SolidityLinter},
+    linter::{DEFAULT_BASELINE_FILE, LintBaseline, Linter},
+    sol::{SolLint, SolLintError, SolidityLinter, custom::CustomLints},
 };
 use foundry_cli::{
     opts::{BuildOpts, configure_pcx_from_solc, get_solar_sources_from_compile_output},
@@ -31,6 +31,23 @@ pub struct LintArgs {
     #[arg(long = "only-lint", value_name = "LINT_ID", num_args(1..))]
     pub(crate) lint: Option<Vec<String>>,
 
+    /// Only report the findings that are not part of the given baseline file.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub(crate) baseline: Option<PathBuf>,
+
+    /// Record the current findings to a baseline file instead of reporting them.
+    ///
+    /// Defaults to `lint-baseline.json` in the project root.
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH",
+        num_args(0..=1),
+        default_missing_value = DEFAULT_BASELINE_FILE,
+        conflicts_with = "baseline",
+    )]
+    pub(crate) write_baseline: Option<PathBuf>,
+
     #[command(flatten)]
     pub(crate) build: BuildOpts,
 }
@@ -81,8 +98,13 @@ pub fn run(self) -> Result<()> {
             return Ok(());
         }
 
+        let custom_lints = CustomLints::new(&config.lint.load_custom_rules(&config.root)?)?;
+
         let parse_lints = |lints: &[String]| -> Result<Vec<SolLint>, SolLintError> {
-            lints.iter().map(|s| SolLint::try_from(s.as_str())).collect()
+            lints
+                .iter()
+                .map(|s| custom_lints.get(s).map_or_else(|| SolLint::try_from(s.as_str()), Ok))
+                .collect()
         };
 
         // Override default lint config with user-defined lints
@@ -98,12 +120,20 @@ pub fn run(self) -> Result<()> {
             return Err(eyre!("linting not supported for this language"));
         }
 
+        let baseline = match (&self.baseline, &self.write_baseline) {
+            (Some(path), _) => Some(LintBaseline::load(&config.root.join(path))?),
+            (_, Some(_)) => Some(LintBaseline::record()),
+            _ => None,
+        };
+
         let linter = SolidityLinter::new(path_config)
             .with_json_emitter(shell::is_json())
             .with_description(true)
             .with_lints(include)
             .without_lints(exclude)
             .with_severity(if severity.is_empty() { None } else { Some(severity) })
+            .with_custom_lints(custom_lints)
+            .with_baseline(baseline.as_ref())
             .with_mixed_case_exceptions(&config.lint.mixed_case_exceptions);
 
         let output = ProjectCompiler::new().files(input.iter().cloned()).compile(&project)?;
@@ -129,6 +159,13 @@ pub fn run(self) -> Result<()> {
         });
         linter.lint(&input, config.deny, &mut compiler)?;
 
+        if let Some(path) = &self.write_baseline
+            && let Some(baseline) = &baseline
+        {
+            baseline.write(&config.root.join(path))?;
+            sh_println!("Wrote {} finding(s) to {}", baseline.len(), path.display())?;
+        }
+
         Ok(())
     }
 }
@@ -18,6 +18,7 @@
 pub mod eip712;
 pub mod flatten;
 pub mod fmt;
+pub mod fuzz;
 pub mod geiger;
 pub mod generate;
 pub mod init;
@@ -2,7 +2,7 @@
 use crate::{
     MultiContractRunner, MultiContractRunnerBuilder,
     decode::decode_console_logs,
-    gas_report::GasReport,
+    gas_report::{GasReport, GasReportBaseline},
     multi_runner::matches_artifact,
     result::{SuiteResult, TestOutcome, TestStatus},
     traces::{
@@ -23,7 +23,7 @@
 use foundry_common::{EmptyTestFilter, TestFunctionExt, compile::ProjectCompiler, fs, shell};
 use foundry_compilers::{
     ProjectCompileOutput,
-    artifacts::output_selection::OutputSelection,
+    artifacts::output_selection::{ContractOutputSelection, OutputSelection},
     compilers::{
         Language,
         multi::{MultiCompiler, MultiCompilerLanguage},
@@ -38,7 +38,7 @@
     },
     filter::GlobMatcher,
 };
-use foundry_debugger::Debugger;
+use foundry_debugger::{DapTransport, Debugger};
 use foundry_evm::{
     opts::EvmOpts,
     traces::{backtrace::BacktraceBuilder, identifier::TraceIdentifiers, prune_trace_depth},
@@ -54,11 +54,12 @@
 use yansi::Paint;
 
 mod filter;
+mod regression;
 mod summary;
 use crate::{result::TestKind, traces::render_trace_arena_inner};
-pub use filter::FilterArgs;
+pub use filter::{FilterArgs, ProjectPathsAwareFilter};
 use quick_junit::{NonSuccessKind, Report, TestCase, TestCaseStatus, TestSuite};
-use summary::{TestSummaryReport, format_invariant_metrics_table};
+use summary::{TestSummaryReport, format_gas_profile_table, format_invariant_metrics_table};
 
 // Loads project's figment and merges the build cli arguments into it
 foundry_config::merge_impl_figment_convert!(TestArgs, build, evm);
@@ -116,10 +117,43 @@ pub struct TestArgs {
     )]
     dump: Option<PathBuf>,
 
+    /// Serve the debugger over the Debug Adapter Protocol instead of opening the TUI.
+    ///
+    /// Messages are exchanged over stdio by default, or over a TCP connection accepted on the
+    /// given address, e.g. `--dap=127.0.0.1:4711`.
+    #[arg(
+        long,
+        requires = "debug",
+        conflicts_with = "dump",
+        value_name = "ADDRESS",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "stdio"
+    )]
+    dap: Option<DapTransport>,
+
     /// Print a gas report.
     #[arg(long, env = "FORGE_GAS_REPORT")]
     gas_report: bool,
 
+    /// Compare the gas report against a baseline, i.e. the output of a previous
+    /// `forge test --gas-report --json` run.
+    #[arg(long, requires = "gas_report", value_hint = ValueHint::FilePath, value_name = "PATH")]
+    gas_report_baseline: Option<PathBuf>,
+
+    /// Fail if the deployment cost or the average gas of a function increased by more than the
+    /// given percentage compared to the baseline.
+    #[arg(long, requires = "gas_report_baseline", value_name = "PERCENT")]
+    gas_report_threshold: Option<f64>,
+
+    /// Profile the gas spent by each unit test.
+    ///
+    /// The gas is broken down by opcode, cold and warm storage access, memory expansion,
+    /// calldata, logs and precompile calls, and printed as a table after each test or included in
+    /// the JSON output.
+    #[arg(long)]
+    gas_profile: bool,
+
     /// Check gas snapshots against previous runs.
     #[arg(long, env = "FORGE_SNAPSHOT_CHECK")]
     gas_snapshot_check: Option<bool>,
@@ -144,6 +178,11 @@ pub struct TestArgs {
     #[arg(long, conflicts_with_all = ["quiet", "json", "gas_report", "summary", "list", "show_progress"], help_heading = "Display options")]
     pub junit: bool,
 
+    /// Write a regression test replaying the counterexample of each failing fuzz and invariant
+    /// test to the given directory.
+    #[arg(long, value_hint = ValueHint::DirPath, value_name = "DIR")]
+    pub emit_regressions: Option<PathBuf>,
+
     /// Stop running tests after the first failure.
     #[arg(long)]
     pub fail_fast: bool,
@@ -208,6 +247,10 @@ pub struct TestArgs {
 impl TestArgs {
     pub async fn run(mut self) -> Result<TestOutcome> {
         trace!(target: "forge::test", "executing test command");
+        // Keep stdout for the protocol messages.
+        if self.dap == Some(DapTransport::Stdio) {
+            shell::Shell::get().set_output_mode(shell::OutputMode::Quiet);
+        }
         self.compile_and_run().await
     }
 
@@ -268,6 +311,11 @@ pub async fn compile_and_run(&mut self) -> Result<TestOutcome> {
             config = self.load_config()?;
         }
 
+        // The debugger identifies storage slots with the storage layout.
+        if self.debug && !config.extra_output.contains(&ContractOutputSelection::StorageLayout) {
+            config.extra_output.push(ContractOutputSelection::StorageLayout);
+        }
+
         // Set up the project.
         let project = config.project()?;
 
@@ -335,6 +383,7 @@ pub async fn run_tests(
         let runner = MultiContractRunnerBuilder::new(config.clone())
             .set_debug(should_debug)
             .set_decode_internal(decode_internal)
+            .set_gas_profile(self.gas_profile)
             .initial_balance(evm_opts.initial_balance)
             .evm_spec(config.evm_spec_id())
             .sender(evm_opts.sender)
@@ -348,6 +397,22 @@ pub async fn run_tests(
         let libraries = runner.libraries.clone();
         let mut outcome = self.run_tests_inner(runner, config, verbosity, filter, output).await?;
 
+        if let Some(dir) = &self.emit_regressions
+            && let Some(runner) = &outcome.runner
+        {
+            let paths = regression::emit_regressions(
+                &project_root.join(dir),
+                &outcome.results,
+                &runner.known_contracts,
+            )?;
+            if !paths.is_empty() && !shell::is_json() && !self.junit {
+                sh_println!("\nWrote {} regression tests:", paths.len())?;
+                for path in paths {
+                    sh_println!("  {}", path.display())?;
+                }
+            }
+        }
+
         if should_draw {
             let (suite_name, test_name, mut test_result) =
                 outcome.remove_first().ok_or_eyre("no tests were executed")?;
@@ -403,6 +468,9 @@ pub async fn run_tests(
                     test_result.traces.iter().filter(|(t, _)| t.is_execution()).cloned().collect(),
                 )
                 .sources(sources)
+                .storage_layouts(output.artifact_ids().filter_map(|(id, artifact)| {
+                    Some((id.name, Arc::new(artifact.storage_layout.clone()?)))
+                }))
                 .breakpoints(test_result.breakpoints.clone());
 
             if let Some(decoder) = &outcome.last_run_decoder {
@@ -412,6 +480,8 @@ pub async fn run_tests(
             let mut debugger = builder.build();
             if let Some(dump_path) = &self.dump {
                 debugger.dump_to_file(dump_path)?;
+            } else if let Some(transport) = self.dap {
+                debugger.run_dap(transport, project_root)?;
             } else {
                 debugger.try_run_tui()?;
             }
@@ -610,6 +680,11 @@ async fn run_tests_inner(
                         let _ = sh_println!("\n{}\n", format_invariant_metrics_table(metrics));
                     }
 
+                    // Display the gas profile if collected.
+                    if let Some(profile) = &result.gas_profile {
+                        sh_println!("\n{}\n", format_gas_profile_table(profile))?;
+                    }
+
                     // We only display logs at level 2 and above
                     if verbosity >= 2 && show_traces {
                         // We only decode logs from Hardhat and DS-style console events
@@ -836,9 +911,19 @@ async fn run_tests_inner(
 
         trace!(target: "forge::test", len=outcome.results.len(), %any_test_failed, "done with results");
 
+        let mut gas_regressions = Vec::new();
         if let Some(gas_report) = gas_report {
             let finalized = gas_report.finalize();
             sh_println!("{}", &finalized)?;
+            if let Some(path) = &self.gas_report_baseline {
+                let diff = finalized.diff(&GasReportBaseline::load(path)?);
+                if !shell::is_json() {
+                    sh_println!("{diff}")?;
+                }
+                if let Some(threshold) = self.gas_report_threshold {
+                    gas_regressions = diff.regressions(threshold);
+                }
+            }
             outcome.gas_report = Some(finalized);
         }
 
@@ -863,6 +948,14 @@ async fn run_tests_inner(
         // Persist test run failures to enable replaying.
         persist_run_failures(&config, &outcome);
 
+        if !gas_regressions.is_empty() {
+            bail!(
+                "gas regressions above {}% against the baseline:\n{}",
+                self.gas_report_threshold.unwrap_or_default(),
+                gas_regressions.join("\n")
+            );
+        }
+
         Ok(outcome)
     }
 
@@ -0,0 +1,356 @@
+//! Regression tests generated from the counterexamples of failing fuzz and invariant tests.
+
+use crate::result::{BrokenInvariant, SuiteResult, TestKind, TestStatus};
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::{Function, InternalType, JsonAbi, Param};
+use alloy_primitives::{Address, Bytes, hex, map::AddressHashMap};
+use eyre::Result;
+use foundry_common::{ContractsByArtifact, fs, get_contract_name, get_file_name};
+use foundry_evm::fuzz::{BaseCounterExample, CounterExample};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Component, Path, PathBuf},
+};
+
+/// The highest nonce searched when resolving the addresses of contracts deployed by the test
+/// contract.
+const MAX_CREATE_NONCE: u64 = 64;
+
+/// Writes a regression test for each failing fuzz and invariant test with a counterexample to
+/// `dir`, returning the paths of the written files.
+///
+/// The regression contract deploys the failing test contract as a harness and runs its `setUp`.
+/// Addresses of the counterexample that were created by the test contract, directly or through a
+/// contract it deployed, are resolved against the harness.
+pub fn emit_regressions(
+    dir: &Path,
+    results: &BTreeMap<String, SuiteResult>,
+    known_contracts: &ContractsByArtifact,
+) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for (suite, suite_result) in results {
+        for (signature, result) in &suite_result.test_results {
+            if result.status != TestStatus::Failure {
+                continue;
+            }
+            let Some(counterexample) = &result.counterexample else { continue };
+            if !matches!(result.kind, TestKind::Fuzz { .. } | TestKind::Invariant { .. }) {
+                continue;
+            }
+
+            let mut regression = Regression::new(suite, signature, known_contracts);
+            match (counterexample, &result.invariant_failure) {
+                (CounterExample::Single(call), _) => regression.fuzz_call(call),
+                (CounterExample::Sequence(_, sequence), Some(failure)) => {
+                    regression.invariant_sequence(failure, sequence)
+                }
+                (CounterExample::Sequence(..), None) => continue,
+            }
+
+            // Mirror the source path, so that test contracts sharing a name don't collide.
+            let source_dir = Path::new(regression.path)
+                .with_extension("")
+                .components()
+                .filter(|component| matches!(component, Component::Normal(_)))
+                .collect::<PathBuf>();
+            let path = dir
+                .join(source_dir)
+                .join(format!("{}.{}.t.sol", regression.contract, regression.test));
+            fs::create_dir_all(path.parent().unwrap())?;
+            // Replayed invariant failures are reported with a generic reason, use the original one.
+            let reason = match &result.invariant_failure {
+                Some(failure) => failure.reason.as_deref(),
+                None => result.reason.as_deref(),
+            };
+            fs::write(&path, regression.render(reason))?;
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+/// A regression test being generated for a failing test.
+struct Regression<'a> {
+    /// The identifier of the failing test contract, `path:name`.
+    suite: &'a str,
+    /// The source path of the failing test contract.
+    path: &'a str,
+    /// The name of the failing test contract.
+    contract: &'a str,
+    /// The name of the failing test function.
+    test: &'a str,
+    /// The ABI of the failing test contract.
+    abi: Option<&'a JsonAbi>,
+    known_contracts: &'a ContractsByArtifact,
+    /// Solidity expressions of the addresses created by the test contract, relative to the
+    /// harness.
+    created: AddressHashMap<String>,
+    /// Contracts to import, by name.
+    imports: BTreeMap<&'a str, &'a str>,
+    /// Statements of the regression test body.
+    body: Vec<String>,
+}
+
+impl<'a> Regression<'a> {
+    fn new(suite: &'a str, signature: &'a str, known_contracts: &'a ContractsByArtifact) -> Self {
+        let (path, contract) = (get_file_name(suite), get_contract_name(suite));
+        let abi = known_contracts
+            .iter()
+            .find(|(id, _)| id.identifier() == suite)
+            .map(|(_, contract)| &contract.abi);
+        Self {
+            suite,
+            path,
+            contract,
+            test: signature.split('(').next().unwrap_or(signature),
+            abi,
+            known_contracts,
+            created: Default::default(),
+            imports: BTreeMap::from([(contract, path)]),
+            body: vec![],
+        }
+    }
+
+    /// Returns the Solidity expression of an address.
+    fn address(&self, addr: Address) -> String {
+        self.created.get(&addr).cloned().unwrap_or_else(|| addr.to_string())
+    }
+
+    /// Resolves the test contract address and the addresses of contracts created by it, or by
+    /// contracts it created, to expressions relative to the harness.
+    fn resolve_created(&mut self, test_address: Address) {
+        self.created.insert(test_address, "address(harness)".to_string());
+        for nonce in 1..=MAX_CREATE_NONCE {
+            let deployer = test_address.create(nonce);
+            let expr = format!("vm.computeCreateAddress(address(harness), {nonce})");
+            for inner_nonce in 1..=MAX_CREATE_NONCE {
+                self.created.insert(
+                    deployer.create(inner_nonce),
+                    format!("vm.computeCreateAddress({expr}, {inner_nonce})"),
+                );
+            }
+            self.created.insert(deployer, expr);
+        }
+    }
+
+    /// Replays the fuzz test with the counterexample arguments.
+    fn fuzz_call(&mut self, call: &BaseCounterExample) {
+        let func = self.abi.and_then(|abi| find_function(abi, &call.calldata));
+        match func.and_then(|func| call_args(func, &call.calldata, &self.created)) {
+            Some(args) => self.body.push(format!("harness.{}({args});", self.test)),
+            None => {
+                self.body.push(format!(
+                    "(bool success, bytes memory data) = address(harness).call(hex\"{}\");",
+                    hex::encode(&call.calldata)
+                ));
+                self.body.push(bubble_revert("success", "data"));
+            }
+        }
+    }
+
+    /// Replays the calls of the shrunk invariant sequence, then asserts the broken invariant.
+    ///
+    /// Calls of the sequence are allowed to revert unless the invariant failed on revert.
+    fn invariant_sequence(
+        &mut self,
+        failure: &BrokenInvariant,
+        sequence: &'a [BaseCounterExample],
+    ) {
+        self.resolve_created(failure.addr);
+
+        for (i, call) in sequence.iter().enumerate() {
+            if let Some(warp) = &call.warp {
+                self.body.push(format!("vm.warp(block.timestamp + {warp});"));
+            }
+            if let Some(roll) = &call.roll {
+                self.body.push(format!("vm.roll(block.number + {roll});"));
+            }
+            let Some(addr) = call.addr else { continue };
+            if let Some(sender) = call.sender {
+                self.body.push(format!("vm.prank({});", self.address(sender)));
+            }
+
+            match self.typed_call(call) {
+                Some(call_expr) if failure.fail_on_revert => {
+                    self.body.push(format!("{call_expr};"))
+                }
+                Some(call_expr) => self.body.push(format!("try {call_expr} {{}} catch {{}}")),
+                None => {
+                    let raw_call = format!(
+                        "address({}).call(hex\"{}\")",
+                        self.address(addr),
+                        hex::encode(&call.calldata)
+                    );
+                    if failure.fail_on_revert {
+                        let (success, data) = (format!("success{i}"), format!("data{i}"));
+                        self.body
+                            .push(format!("(bool {success}, bytes memory {data}) = {raw_call};"));
+                        self.body.push(bubble_revert(&success, &data));
+                    } else {
+                        self.body.push(format!("{raw_call};"));
+                    }
+                }
+            }
+        }
+
+        // Invariants are asserted after the sequence and `afterInvariant` at the end of the run.
+        match self.abi.and_then(|abi| find_function(abi, &failure.calldata)) {
+            Some(func) => self.body.push(format!("harness.{}();", func.name)),
+            None => {
+                self.body.push(format!(
+                    "(bool success, bytes memory data) = address(harness).call(hex\"{}\");",
+                    hex::encode(&failure.calldata)
+                ));
+                self.body.push(bubble_revert("success", "data"));
+            }
+        }
+        if self.has_function("afterInvariant") {
+            self.body.push("harness.afterInvariant();".to_string());
+        }
+    }
+
+    /// Returns the typed call of the counterexample target, importing its contract, if the target
+    /// contract is known and the arguments can be written as Solidity literals.
+    fn typed_call(&mut self, call: &'a BaseCounterExample) -> Option<String> {
+        let (identifier, addr) = (call.contract_name.as_deref()?, call.addr?);
+        let (path, name) = identifier.split_once(':')?;
+        let func = find_function(self.find_abi(identifier)?, &call.calldata)?;
+        let args = call_args(func, &call.calldata, &self.created)?;
+
+        // Targets sharing the name of another imported contract are called with raw calldata.
+        if *self.imports.entry(name).or_insert(path) != path {
+            return None;
+        }
+        Some(format!("{name}({}).{}({args})", self.address(addr), func.name))
+    }
+
+    fn has_function(&self, name: &str) -> bool {
+        self.abi.is_some_and(|abi| abi.functions().any(|func| func.name == name))
+    }
+
+    fn find_abi(&self, identifier: &str) -> Option<&'a JsonAbi> {
+        self.known_contracts
+            .iter()
+            .find(|(id, _)| id.identifier() == identifier)
+            .map(|(_, contract)| &contract.abi)
+    }
+
+    /// Renders the regression test file.
+    fn render(&self, reason: Option<&str>) -> String {
+        let mut out = String::new();
+        out.push_str("// SPDX-License-Identifier: UNLICENSED\npragma solidity >=0.6.2;\n\n");
+        let mut imports = BTreeMap::<_, Vec<_>>::new();
+        for (name, path) in &self.imports {
+            imports.entry(path).or_default().push(*name);
+        }
+        for (path, names) in imports {
+            let _ = writeln!(out, "import {{{}}} from \"{path}\";", names.join(", "));
+        }
+
+        out.push_str(REGRESSION_VM);
+
+        let _ = writeln!(
+            out,
+            "\n// Regression test for the failure of `{}::{}`.",
+            self.suite, self.test
+        );
+        if let Some(reason) = reason.filter(|reason| !reason.is_empty()) {
+            for line in reason.lines() {
+                let _ = writeln!(out, "// {line}");
+            }
+        }
+        let _ = writeln!(out, "contract {}_{}_Regression {{", self.contract, self.test);
+        out.push_str(
+            "    RegressionVm internal constant vm =\n        \
+             RegressionVm(0x7109709ECfa91a80626fF3989D68f67F5b1DD12D);\n\n",
+        );
+        let _ = writeln!(out, "    {} internal harness;\n", self.contract);
+        out.push_str("    function setUp() public {\n");
+        let _ = writeln!(out, "        harness = new {}();", self.contract);
+        out.push_str("        vm.deal(address(harness), address(this).balance);\n");
+        if self.has_function("setUp") {
+            out.push_str("        harness.setUp();\n");
+        }
+        out.push_str("    }\n\n");
+        let _ = writeln!(out, "    function test_{}_regression() public {{", self.test);
+        for statement in &self.body {
+            let _ = writeln!(out, "        {statement}");
+        }
+        out.push_str("    }\n}\n");
+        out
+    }
+}
+
+/// The cheatcodes used by regression tests, declared so that they don't depend on forge-std.
+const REGRESSION_VM: &str = "
+interface RegressionVm {
+    function computeCreateAddress(address deployer, uint256 nonce) external pure returns (address);
+    function deal(address account, uint256 newBalance) external;
+    function prank(address msgSender) external;
+    function roll(uint256 newHeight) external;
+    function warp(uint256 newTimestamp) external;
+}
+";
+
+/// Returns the statement reverting with the revert data of a failed low-level call.
+fn bubble_revert(success: &str, data: &str) -> String {
+    format!(
+        "if (!{success}) {{\n            assembly {{\n                revert(add({data}, 32), mload({data}))\n            }}\n        }}"
+    )
+}
+
+fn find_function<'a>(abi: &'a JsonAbi, calldata: &Bytes) -> Option<&'a Function> {
+    let selector = calldata.get(..4)?;
+    abi.functions().find(|func| func.selector().as_slice() == selector)
+}
+
+/// Decodes the calldata arguments as a comma separated list of Solidity literals.
+fn call_args(
+    func: &Function,
+    calldata: &Bytes,
+    created: &AddressHashMap<String>,
+) -> Option<String> {
+    let values = func.abi_decode_input(calldata.get(4..)?).ok()?;
+    let args = func
+        .inputs
+        .iter()
+        .zip(&values)
+        .map(|(param, value)| literal(param, value, created))
+        .collect::<Option<Vec<_>>>()?;
+    Some(args.join(", "))
+}
+
+/// Returns the Solidity literal of an argument, if it can be written as one.
+///
+/// Addresses created by the test contract are written relative to the harness. Arrays, tuples and
+/// user defined types are not supported.
+fn literal(param: &Param, value: &DynSolValue, created: &AddressHashMap<String>) -> Option<String> {
+    let payable = match &param.internal_type {
+        None => false,
+        Some(InternalType::AddressPayable(_)) => true,
+        Some(InternalType::Other { contract: None, ty }) if *ty == param.ty => false,
+        Some(_) => return None,
+    };
+
+    Some(match value {
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::Address(addr) => {
+            let addr = created.get(addr).cloned().unwrap_or_else(|| addr.to_string());
+            if payable { format!("payable({addr})") } else { addr }
+        }
+        DynSolValue::FixedBytes(word, size) => {
+            format!("bytes{size}(0x{})", hex::encode(&word[..*size]))
+        }
+        DynSolValue::Bytes(bytes) => format!("hex\"{}\"", hex::encode(bytes)),
+        DynSolValue::String(s)
+            if s.chars().all(|c| c.is_ascii_graphic() || c == ' ') && !s.contains(['"', '\\']) =>
+        {
+            format!("\"{s}\"")
+        }
+        DynSolValue::String(s) => format!("string(hex\"{}\")", hex::encode(s)),
+        _ => return None,
+    })
+}
@@ -3,7 +3,10 @@
     Cell, Color, Row, Table, modifiers::UTF8_ROUND_CORNERS, presets::ASCII_MARKDOWN,
 };
 use foundry_common::shell;
-use foundry_evm::executors::invariant::InvariantMetrics;
+use foundry_evm::{
+    executors::invariant::InvariantMetrics,
+    inspectors::{GasProfile, GasUsage},
+};
 use itertools::Itertools;
 use serde_json::json;
 use std::{collections::HashMap, fmt::Display};
@@ -193,10 +196,77 @@ pub(crate) fn format_invariant_metrics_table(
     table
 }
 
+/// Formats the gas profile of a test, collected with `--gas-profile`.
+///
+/// Storage accesses, memory expansion, calldata, logs and precompile calls come first, followed by
+/// the opcodes sorted by the gas they spent.
+pub(crate) fn format_gas_profile_table(profile: &GasProfile) -> Table {
+    let mut table = Table::new();
+    if shell::is_markdown() {
+        table.load_preset(ASCII_MARKDOWN);
+    } else {
+        table.apply_modifier(UTF8_ROUND_CORNERS);
+    }
+
+    table.set_header(vec![
+        Cell::new("Category"),
+        Cell::new("Name"),
+        Cell::new("Count"),
+        Cell::new("Gas").fg(Color::Cyan),
+    ]);
+
+    let mut add_usage = |category: &str, name: &str, usage: &GasUsage| {
+        if usage.count > 0 {
+            table.add_row(vec![
+                Cell::new(category),
+                Cell::new(name),
+                Cell::new(usage.count),
+                Cell::new(usage.gas).fg(Color::Cyan),
+            ]);
+        }
+    };
+    add_usage("Storage", "SLOAD (cold)", &profile.cold_sloads);
+    add_usage("Storage", "SLOAD (warm)", &profile.warm_sloads);
+    add_usage("Storage", "SSTORE (cold)", &profile.cold_sstores);
+    add_usage("Storage", "SSTORE (warm)", &profile.warm_sstores);
+    add_usage("Logs", "LOG0-LOG4", &profile.logs);
+    for (name, usage) in &profile.precompiles {
+        add_usage("Precompile", name.as_str(), usage);
+    }
+
+    for (category, gas) in [("Memory", profile.memory_expansion), ("Calldata", profile.calldata)] {
+        if gas > 0 {
+            table.add_row(vec![
+                Cell::new(category),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(gas).fg(Color::Cyan),
+            ]);
+        }
+    }
+
+    for (name, usage) in profile
+        .opcodes
+        .iter()
+        .sorted_by(|(a_name, a), (b_name, b)| b.gas.cmp(&a.gas).then_with(|| a_name.cmp(b_name)))
+    {
+        table.add_row(vec![
+            Cell::new("Opcode"),
+            Cell::new(name),
+            Cell::new(usage.count),
+            Cell::new(usage.gas).fg(Color::Cyan),
+        ]);
+    }
+    table
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::cmd::test::summary::format_invariant_metrics_table;
-    use foundry_evm::executors::invariant::InvariantMetrics;
+    use crate::cmd::test::summary::{format_gas_profile_table, format_invariant_metrics_table};
+    use foundry_evm::{
+        executors::invariant::InvariantMetrics,
+        inspectors::{GasProfile, GasUsage},
+    };
     use std::collections::HashMap;
 
     #[test]
@@ -227,4 +297,34 @@ fn test_invariant_metrics_table() {
         assert_eq!(second_row_content.next().unwrap().content(), "2");
         assert_eq!(second_row_content.next().unwrap().content(), "2");
     }
+
+    #[test]
+    fn test_gas_profile_table() {
+        let profile = GasProfile {
+            opcodes: [
+                ("PUSH1".to_string(), GasUsage { count: 10, gas: 30 }),
+                ("SLOAD".to_string(), GasUsage { count: 2, gas: 2200 }),
+            ]
+            .into(),
+            cold_sloads: GasUsage { count: 1, gas: 2100 },
+            warm_sloads: GasUsage { count: 1, gas: 100 },
+            calldata: 64,
+            ..Default::default()
+        };
+        let table = format_gas_profile_table(&profile);
+        let rows = table
+            .row_iter()
+            .map(|row| row.cell_iter().map(|cell| cell.content()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                ["Storage", "SLOAD (cold)", "1", "2100"],
+                ["Storage", "SLOAD (warm)", "1", "100"],
+                ["Calldata", "", "", "64"],
+                ["Opcode", "SLOAD", "2", "2200"],
+                ["Opcode", "PUSH1", "10", "30"],
+            ]
+        );
+    }
 }
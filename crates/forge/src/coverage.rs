@@ -6,9 +6,12 @@
 };
 use evm_disassembler::disassemble_bytes;
 use foundry_common::{fs, shell};
+use foundry_config::{CoverageConfig, CoverageThresholds};
 use semver::Version;
 use std::{
-    collections::hash_map,
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, hash_map},
+    fmt::Write as _,
     io::Write,
     path::{Path, PathBuf},
 };
@@ -30,22 +33,27 @@ fn needs_source_maps(&self) -> bool {
 }
 
 /// A simple summary reporter that prints the coverage results in a table.
+///
+/// Fails if the coverage is below the configured thresholds.
 pub struct CoverageSummaryReporter {
     /// The summary table.
     table: Table,
     /// The total coverage of the entire project.
     total: CoverageSummary,
+    /// The coverage thresholds to enforce.
+    config: CoverageConfig,
 }
 
 impl Default for CoverageSummaryReporter {
     fn default() -> Self {
-        let mut table = Table::new();
-        if shell::is_markdown() {
-            table.load_preset(ASCII_MARKDOWN);
-        } else {
-            table.apply_modifier(UTF8_ROUND_CORNERS);
-        }
+        Self::new(CoverageConfig::default())
+    }
+}
 
+impl CoverageSummaryReporter {
+    /// Create a new summary reporter enforcing the thresholds of `config`.
+    pub fn new(config: CoverageConfig) -> Self {
+        let mut table = new_table();
         table.set_header(vec![
             Cell::new("File"),
             Cell::new("% Lines"),
@@ -54,11 +62,9 @@ fn default() -> Self {
             Cell::new("% Funcs"),
         ]);
 
-        Self { table, total: CoverageSummary::default() }
+        Self { table, total: CoverageSummary::default(), config }
     }
-}
 
-impl CoverageSummaryReporter {
     fn add_row(&mut self, name: impl Into<Cell>, summary: CoverageSummary) {
         let mut row = Row::new();
         row.add_cell(name.into())
@@ -76,13 +82,146 @@ fn name(&self) -> &'static str {
     }
 
     fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
+        let mut violations = Vec::new();
+        let mut checked_total = CoverageSummary::default();
         for (path, summary) in report.summary_by_file() {
             self.total.merge(&summary);
+            if !self.config.is_excluded(path) {
+                checked_total.merge(&summary);
+                for o in self.config.overrides_for(path) {
+                    check_thresholds(
+                        &path.display().to_string(),
+                        &summary,
+                        &o.thresholds,
+                        &mut violations,
+                    );
+                }
+            }
             self.add_row(path.display(), summary);
         }
+        check_thresholds("Total", &checked_total, &self.config.thresholds, &mut violations);
 
         self.add_row("Total", self.total.clone());
         sh_println!("\n{}", self.table)?;
+
+        if !violations.is_empty() {
+            let mut table = new_table();
+            table.set_header(vec![
+                Cell::new("File"),
+                Cell::new("Metric"),
+                Cell::new("Coverage"),
+                Cell::new("Minimum"),
+            ]);
+            for violation in &violations {
+                table.add_row(vec![
+                    Cell::new(&violation.scope),
+                    Cell::new(violation.metric),
+                    Cell::new(format!("{:.2}%", violation.coverage)).fg(Color::Red),
+                    Cell::new(format!("{:.2}%", violation.min)),
+                ]);
+            }
+            sh_println!("\nCoverage thresholds not met:\n{table}")?;
+            eyre::bail!("coverage is below the configured thresholds");
+        }
+        Ok(())
+    }
+}
+
+/// A coverage metric below its configured minimum.
+struct ThresholdViolation {
+    scope: String,
+    metric: &'static str,
+    coverage: f64,
+    min: f64,
+}
+
+/// Checks `summary` against `thresholds`, pushing the metrics below their minimum to `violations`.
+fn check_thresholds(
+    scope: &str,
+    summary: &CoverageSummary,
+    thresholds: &CoverageThresholds,
+    violations: &mut Vec<ThresholdViolation>,
+) {
+    let metrics = [
+        ("Lines", thresholds.min_lines, summary.line_hits, summary.line_count),
+        ("Branches", thresholds.min_branches, summary.branch_hits, summary.branch_count),
+        ("Functions", thresholds.min_functions, summary.function_hits, summary.function_count),
+    ];
+    for (metric, min, hits, total) in metrics {
+        let Some(min) = min else { continue };
+        let coverage = ratio(hits, total) * 100.;
+        if coverage < min {
+            violations.push(ThresholdViolation { scope: scope.to_string(), metric, coverage, min });
+        }
+    }
+}
+
+/// Creates a table in the style of the current shell.
+fn new_table() -> Table {
+    let mut table = Table::new();
+    if shell::is_markdown() {
+        table.load_preset(ASCII_MARKDOWN);
+    } else {
+        table.apply_modifier(UTF8_ROUND_CORNERS);
+    }
+    table
+}
+
+/// Prints the coverage of the lines changed since a git revision, optionally enforcing a minimum.
+pub struct DiffCoverageReporter {
+    /// The changed lines, by file relative to the project root.
+    changed: BTreeMap<PathBuf, BTreeSet<u32>>,
+    /// The minimum line coverage of the changed lines, in percent.
+    min_coverage: Option<f64>,
+}
+
+impl DiffCoverageReporter {
+    /// Create a new differential coverage reporter.
+    pub fn new(changed: BTreeMap<PathBuf, BTreeSet<u32>>, min_coverage: Option<f64>) -> Self {
+        Self { changed, min_coverage }
+    }
+}
+
+impl CoverageReporter for DiffCoverageReporter {
+    fn name(&self) -> &'static str {
+        "diff"
+    }
+
+    fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
+        let mut table = new_table();
+        table.set_header(vec![Cell::new("File"), Cell::new("% Lines"), Cell::new("% Branches")]);
+
+        let mut total = LineSummary::default();
+        for (path, items) in report.items_by_file() {
+            let Some(changed) = self.changed.get(path) else { continue };
+            let mut lines = line_coverage(&items);
+            lines.retain(|line, _| changed.contains(line));
+            let summary = LineSummary::from_lines(&lines);
+            if summary.line_count == 0 && summary.branch_count == 0 {
+                continue;
+            }
+            total.merge(&summary);
+            table.add_row(vec![
+                Cell::new(path.display()),
+                format_cell(summary.line_hits, summary.line_count),
+                format_cell(summary.branch_hits, summary.branch_count),
+            ]);
+        }
+        table.add_row(vec![
+            Cell::new("Total"),
+            format_cell(total.line_hits, total.line_count),
+            format_cell(total.branch_hits, total.branch_count),
+        ]);
+        sh_println!("\nCoverage of changed lines:\n{table}")?;
+
+        if let Some(min) = self.min_coverage {
+            let coverage = ratio(total.line_hits, total.line_count) * 100.;
+            if coverage < min {
+                eyre::bail!(
+                    "coverage of changed lines is {coverage:.2}%, below the minimum of {min}%"
+                );
+            }
+        }
         Ok(())
     }
 }
@@ -194,6 +333,420 @@ fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
     }
 }
 
+/// Writes the coverage report in [Cobertura]'s XML format.
+///
+/// Each directory is a package and each source file a class, with its functions as methods.
+///
+/// [Cobertura]: https://cobertura.github.io/cobertura/
+pub struct CoberturaReporter {
+    root: PathBuf,
+    path: PathBuf,
+}
+
+impl CoberturaReporter {
+    /// Create a new Cobertura reporter.
+    pub fn new(root: PathBuf, path: PathBuf) -> Self {
+        Self { root, path }
+    }
+}
+
+impl CoverageReporter for CoberturaReporter {
+    fn name(&self) -> &'static str {
+        "cobertura"
+    }
+
+    fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
+        let mut packages = BTreeMap::<String, Vec<(&Path, Vec<&CoverageItem>)>>::new();
+        for (path, items) in report.items_by_file() {
+            let package = path.parent().map(|p| p.to_string_lossy().replace('\\', "/"));
+            packages.entry(package.unwrap_or_default()).or_default().push((path, items));
+        }
+
+        let mut total = LineSummary::default();
+        let mut out_packages = String::new();
+        for (package, files) in &packages {
+            let mut package_total = LineSummary::default();
+            let mut out_classes = String::new();
+            for (path, items) in files {
+                let lines = line_coverage(items);
+                let summary = LineSummary::from_lines(&lines);
+                package_total.merge(&summary);
+
+                let mut out_methods = String::new();
+                for item in items {
+                    let CoverageItemKind::Function { ref name } = item.kind else { continue };
+                    let rate = if item.hits > 0 { 1 } else { 0 };
+                    writeln!(
+                        out_methods,
+                        r#"            <method name="{}" signature="" line-rate="{rate}" branch-rate="{rate}" complexity="0">"#,
+                        escape(&format!("{}.{name}", item.loc.contract_name)),
+                    )?;
+                    writeln!(out_methods, "              <lines>")?;
+                    writeln!(
+                        out_methods,
+                        r#"                <line number="{}" hits="{}"/>"#,
+                        item.loc.lines.start, item.hits
+                    )?;
+                    writeln!(out_methods, "              </lines>")?;
+                    writeln!(out_methods, "            </method>")?;
+                }
+
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                let file_path = path.to_string_lossy().replace('\\', "/");
+                writeln!(
+                    out_classes,
+                    r#"        <class name="{}" filename="{}" {} complexity="0">"#,
+                    escape(&file_name),
+                    escape(&file_path),
+                    summary.rates(),
+                )?;
+                writeln!(out_classes, "          <methods>")?;
+                out_classes.push_str(&out_methods);
+                writeln!(out_classes, "          </methods>")?;
+                writeln!(out_classes, "          <lines>")?;
+                for (number, line) in &lines {
+                    let hits = line.hits.unwrap_or_default();
+                    if line.branches == 0 {
+                        writeln!(
+                            out_classes,
+                            r#"            <line number="{number}" hits="{hits}"/>"#
+                        )?;
+                    } else {
+                        writeln!(
+                            out_classes,
+                            r#"            <line number="{number}" hits="{hits}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                            line.branches_hit * 100 / line.branches,
+                            line.branches_hit,
+                            line.branches,
+                        )?;
+                    }
+                }
+                writeln!(out_classes, "          </lines>")?;
+                writeln!(out_classes, "        </class>")?;
+            }
+
+            total.merge(&package_total);
+            writeln!(
+                out_packages,
+                r#"    <package name="{}" {} complexity="0">"#,
+                escape(package),
+                package_total.rates(),
+            )?;
+            writeln!(out_packages, "      <classes>")?;
+            out_packages.push_str(&out_classes);
+            writeln!(out_packages, "      </classes>")?;
+            writeln!(out_packages, "    </package>")?;
+        }
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        let mut out = std::io::BufWriter::new(fs::create_file(&self.path)?);
+        writeln!(out, r#"<?xml version="1.0" ?>"#)?;
+        writeln!(
+            out,
+            r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+        )?;
+        writeln!(
+            out,
+            r#"<coverage {} lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="{}" timestamp="{timestamp}">"#,
+            total.rates(),
+            total.line_hits,
+            total.line_count,
+            total.branch_hits,
+            total.branch_count,
+            foundry_common::version::SHORT_VERSION,
+        )?;
+        writeln!(out, "  <sources>")?;
+        writeln!(out, "    <source>{}</source>", escape(&self.root.to_string_lossy()))?;
+        writeln!(out, "  </sources>")?;
+        writeln!(out, "  <packages>")?;
+        out.write_all(out_packages.as_bytes())?;
+        writeln!(out, "  </packages>")?;
+        writeln!(out, "</coverage>")?;
+
+        out.flush()?;
+        sh_println!("Wrote Cobertura report.")?;
+
+        Ok(())
+    }
+}
+
+/// Writes a self-contained HTML report: a summary page, and an annotated page per source file
+/// with line, branch and function hit counts.
+pub struct HtmlReporter {
+    root: PathBuf,
+    destdir: PathBuf,
+}
+
+impl HtmlReporter {
+    /// Create a new HTML reporter.
+    pub fn new(root: PathBuf, destdir: PathBuf) -> Self {
+        Self { root, destdir }
+    }
+
+    fn write_source_page(
+        &self,
+        path: &Path,
+        items: &[&CoverageItem],
+        summary: &CoverageSummary,
+    ) -> eyre::Result<()> {
+        let src = fs::read_to_string(self.root.join(path))?;
+        let lines = line_coverage(items);
+        let title = escape(&path.to_string_lossy()).into_owned();
+        let index = "../".repeat(path.components().count().saturating_sub(1)) + "index.html";
+
+        let mut body = String::new();
+        writeln!(body, r#"<p><a href="{index}">Index</a> / {title}</p>"#)?;
+        body.push_str(&html_summary_table(&[(title.clone(), None, summary)]));
+
+        writeln!(body, "<h2>Functions</h2>")?;
+        writeln!(body, "<table><tr><th>Function</th><th>Line</th><th>Hits</th></tr>")?;
+        for item in items {
+            let CoverageItemKind::Function { ref name } = item.kind else { continue };
+            writeln!(
+                body,
+                r##"<tr class="{}"><td>{}.{}</td><td><a href="#L{line}">{line}</a></td><td>{}</td></tr>"##,
+                if item.hits > 0 { "hit" } else { "miss" },
+                escape(&item.loc.contract_name),
+                escape(name),
+                item.hits,
+                line = item.loc.lines.start,
+            )?;
+        }
+        writeln!(body, "</table>")?;
+
+        writeln!(body, "<h2>Source</h2>")?;
+        writeln!(
+            body,
+            r#"<table class="source"><tr><th>Line</th><th>Hits</th><th>Branches</th><th></th></tr>"#
+        )?;
+        for (i, code) in src.lines().enumerate() {
+            let number = i as u32 + 1;
+            let (class, hits, branches) = match lines.get(&number) {
+                Some(line) => {
+                    let class =
+                        if line.hits == Some(0) || (line.branches > 0 && line.branches_hit == 0) {
+                            "miss"
+                        } else if line.branches_hit < line.branches {
+                            "partial"
+                        } else {
+                            "hit"
+                        };
+                    let hits = line.hits.map(|h| h.to_string()).unwrap_or_default();
+                    let branches = if line.branches > 0 {
+                        format!("{}/{}", line.branches_hit, line.branches)
+                    } else {
+                        String::new()
+                    };
+                    (class, hits, branches)
+                }
+                None => ("", String::new(), String::new()),
+            };
+            writeln!(
+                body,
+                r##"<tr id="L{number}" class="{class}"><td class="num"><a href="#L{number}">{number}</a></td><td class="num">{hits}</td><td class="num">{branches}</td><td><pre>{}</pre></td></tr>"##,
+                escape(code),
+            )?;
+        }
+        writeln!(body, "</table>")?;
+
+        let dest = self.destdir.join(format!("{}.html", path.display()));
+        fs::create_dir_all(dest.parent().unwrap())?;
+        fs::write(dest, html_page(&title, &body))?;
+        Ok(())
+    }
+}
+
+impl CoverageReporter for HtmlReporter {
+    fn name(&self) -> &'static str {
+        "html"
+    }
+
+    fn report(&mut self, report: &CoverageReport) -> eyre::Result<()> {
+        fs::create_dir_all(&self.destdir)?;
+
+        let mut rows = Vec::new();
+        let mut total = CoverageSummary::default();
+        for (path, items) in report.items_by_file() {
+            let summary = CoverageSummary::from_items(items.iter().copied());
+            self.write_source_page(path, &items, &summary)?;
+            total.merge(&summary);
+            rows.push((path, summary));
+        }
+
+        let mut rows = rows
+            .iter()
+            .map(|(path, summary)| {
+                let name = escape(&path.to_string_lossy()).into_owned();
+                let link = format!("{}.html", path.to_string_lossy().replace('\\', "/"));
+                (name, Some(link), summary)
+            })
+            .collect::<Vec<_>>();
+        rows.push(("Total".to_string(), None, &total));
+
+        let body = format!("<h1>Coverage report</h1>\n{}", html_summary_table(&rows));
+        fs::write(self.destdir.join("index.html"), html_page("Coverage report", &body))?;
+
+        sh_println!(
+            "Wrote HTML report to {}.",
+            self.destdir.strip_prefix(&self.root).unwrap_or(&self.destdir).display()
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Coverage of a single source line.
+#[derive(Default)]
+struct LineCoverage {
+    /// Hits of the line, if it is executable.
+    hits: Option<u32>,
+    /// Number of branch paths starting on the line.
+    branches: usize,
+    /// Number of branch paths starting on the line that were taken.
+    branches_hit: usize,
+}
+
+/// Aggregates the line, statement and branch items of a source file by line number.
+fn line_coverage(items: &[&CoverageItem]) -> BTreeMap<u32, LineCoverage> {
+    let mut lines = BTreeMap::<u32, LineCoverage>::new();
+    for item in items {
+        match item.kind {
+            CoverageItemKind::Line | CoverageItemKind::Statement => {
+                let line = lines.entry(item.loc.lines.start).or_default();
+                line.hits = Some(line.hits.unwrap_or_default().max(item.hits));
+            }
+            CoverageItemKind::Branch { .. } => {
+                let line = lines.entry(item.loc.lines.start).or_default();
+                line.branches += 1;
+                if item.hits > 0 {
+                    line.branches_hit += 1;
+                }
+            }
+            CoverageItemKind::Function { .. } => {}
+        }
+    }
+    lines
+}
+
+/// Line and branch totals of [`LineCoverage`]s.
+#[derive(Default)]
+struct LineSummary {
+    line_count: usize,
+    line_hits: usize,
+    branch_count: usize,
+    branch_hits: usize,
+}
+
+impl LineSummary {
+    fn from_lines(lines: &BTreeMap<u32, LineCoverage>) -> Self {
+        let mut summary = Self::default();
+        for line in lines.values() {
+            if let Some(hits) = line.hits {
+                summary.line_count += 1;
+                summary.line_hits += (hits > 0) as usize;
+            }
+            summary.branch_count += line.branches;
+            summary.branch_hits += line.branches_hit;
+        }
+        summary
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.line_count += other.line_count;
+        self.line_hits += other.line_hits;
+        self.branch_count += other.branch_count;
+        self.branch_hits += other.branch_hits;
+    }
+
+    /// Returns the Cobertura `line-rate` and `branch-rate` attributes.
+    fn rates(&self) -> String {
+        format!(
+            r#"line-rate="{:.4}" branch-rate="{:.4}""#,
+            ratio(self.line_hits, self.line_count),
+            ratio(self.branch_hits, self.branch_count),
+        )
+    }
+}
+
+fn ratio(hits: usize, total: usize) -> f64 {
+    if total == 0 { 1. } else { hits as f64 / total as f64 }
+}
+
+/// Renders a summary table, with a row per `(name, link, summary)`.
+fn html_summary_table(rows: &[(String, Option<String>, &CoverageSummary)]) -> String {
+    let cell = |hits: usize, total: usize| {
+        let percentage = ratio(hits, total);
+        let class = match percentage {
+            _ if total == 0 => "none",
+            _ if percentage < 0.5 => "miss",
+            _ if percentage < 0.75 => "partial",
+            _ => "hit",
+        };
+        format!(r#"<td class="{class}">{:.2}% ({hits}/{total})</td>"#, percentage * 100.)
+    };
+
+    let mut table = String::from(
+        "<table><tr><th>File</th><th>% Lines</th><th>% Statements</th><th>% Branches</th>\
+         <th>% Funcs</th></tr>\n",
+    );
+    for (name, link, summary) in rows {
+        let name = match link {
+            Some(link) => format!(r#"<a href="{}">{name}</a>"#, escape(link)),
+            None => name.clone(),
+        };
+        table.push_str(&format!(
+            "<tr><td>{name}</td>{}{}{}{}</tr>\n",
+            cell(summary.line_hits, summary.line_count),
+            cell(summary.statement_hits, summary.statement_count),
+            cell(summary.branch_hits, summary.branch_count),
+            cell(summary.function_hits, summary.function_count),
+        ));
+    }
+    table.push_str("</table>\n");
+    table
+}
+
+fn html_page(title: &str, body: &str) -> String {
+    const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ddd; padding: 2px 8px; text-align: left; }
+td.num { text-align: right; color: #666; }
+table.source td { border: none; }
+pre { margin: 0; }
+.hit { background: #e6ffed; }
+.partial { background: #fff5b1; }
+.miss { background: #ffeef0; }
+.none { color: #999; }";
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>\n{STYLE}\n</style>\n</head>\n<body>\n{body}</body>\n</html>\n"
+    )
+}
+
+/// Escapes a string for use in HTML and XML text and attributes.
+fn escape(s: &str) -> Cow<'_, str> {
+    if !s.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(s);
+    }
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
 /// A super verbose reporter for debugging coverage while it is still unstable.
 pub struct DebugReporter;
 
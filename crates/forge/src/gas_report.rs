@@ -6,12 +6,17 @@
 };
 use alloy_primitives::map::HashSet;
 use comfy_table::{Cell, Color, Table, modifiers::UTF8_ROUND_CORNERS, presets::ASCII_MARKDOWN};
-use foundry_common::{TestFunctionExt, calc, shell};
+use eyre::{Context, Result};
+use foundry_common::{TestFunctionExt, calc, fs, shell};
 use foundry_evm::traces::CallKind;
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::{collections::BTreeMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    path::Path,
+};
 
 /// Represents the gas report for a set of contracts.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -250,6 +255,287 @@ fn format_table_output(&self, contract: &ContractInfo, name: &str) -> Table {
     }
 }
 
+impl GasReport {
+    /// Compares the report against a baseline.
+    pub fn diff(&self, baseline: &GasReportBaseline) -> GasReportDiff {
+        let current = self
+            .contracts
+            .iter()
+            .filter(|(_, contract)| !contract.functions.is_empty())
+            .map(|(name, contract)| (name.as_str(), contract))
+            .collect::<BTreeMap<_, _>>();
+        let names = current
+            .keys()
+            .copied()
+            .chain(baseline.contracts.keys().map(String::as_str))
+            .collect::<BTreeSet<_>>();
+
+        let contracts = names
+            .into_iter()
+            .map(|name| {
+                let new = current.get(name).copied();
+                let old = baseline.contracts.get(name);
+                let new_functions = new
+                    .map(|contract| {
+                        contract
+                            .functions
+                            .values()
+                            .flatten()
+                            .map(|(sig, gas_info)| (sig.replace(':', ""), gas_info))
+                            .collect::<BTreeMap<_, _>>()
+                    })
+                    .unwrap_or_default();
+                let signatures = new_functions
+                    .keys()
+                    .map(String::as_str)
+                    .chain(old.iter().flat_map(|old| old.functions.keys().map(String::as_str)))
+                    .collect::<BTreeSet<_>>();
+                let functions = signatures
+                    .into_iter()
+                    .map(|sig| FunctionDiff {
+                        signature: sig.to_string(),
+                        old: old.and_then(|old| old.functions.get(sig)).cloned(),
+                        new: new_functions.get(sig).map(|gas_info| (*gas_info).clone()),
+                    })
+                    .collect();
+                ContractDiff {
+                    name: name.to_string(),
+                    deployment_gas: Delta::new(
+                        old.map(|old| old.deployment.gas),
+                        new.map(|new| new.gas),
+                    ),
+                    deployment_size: Delta::new(
+                        old.map(|old| old.deployment.size as u64),
+                        new.map(|new| new.size as u64),
+                    ),
+                    functions,
+                }
+            })
+            .collect();
+        GasReportDiff { contracts }
+    }
+}
+
+/// A gas report of a previous run, loaded from its JSON output.
+#[derive(Clone, Debug, Default)]
+pub struct GasReportBaseline {
+    contracts: BTreeMap<String, BaselineContract>,
+}
+
+impl GasReportBaseline {
+    /// Loads the output of `forge test --gas-report --json`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contracts: Vec<BaselineContract> = fs::read_json_file(path)
+            .wrap_err_with(|| format!("failed to read gas report baseline {}", path.display()))?;
+        Ok(Self {
+            contracts: contracts
+                .into_iter()
+                .map(|contract| (contract.contract.clone(), contract))
+                .collect(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct BaselineContract {
+    contract: String,
+    deployment: BaselineDeployment,
+    functions: BTreeMap<String, GasInfo>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+struct BaselineDeployment {
+    gas: u64,
+    size: usize,
+}
+
+/// Differences between a gas report and a baseline.
+#[derive(Clone, Debug)]
+pub struct GasReportDiff {
+    contracts: Vec<ContractDiff>,
+}
+
+impl GasReportDiff {
+    /// Returns the increases of deployment cost and average function gas above the given
+    /// percentage.
+    pub fn regressions(&self, threshold_pct: f64) -> Vec<String> {
+        let mut regressions = Vec::new();
+        for contract in &self.contracts {
+            if contract.deployment_gas.exceeds(threshold_pct) {
+                regressions.push(format!(
+                    "{} deployment cost: {}",
+                    contract.name, contract.deployment_gas
+                ));
+            }
+            for function in &contract.functions {
+                let mean = Delta::new(
+                    function.old.as_ref().map(|old| old.mean),
+                    function.new.as_ref().map(|new| new.mean),
+                );
+                if mean.exceeds(threshold_pct) {
+                    regressions.push(format!(
+                        "{}::{} average gas: {mean}",
+                        contract.name, function.signature
+                    ));
+                }
+            }
+        }
+        regressions
+    }
+}
+
+impl Display for GasReportDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let changed =
+            self.contracts.iter().filter(|contract| contract.is_changed()).collect::<Vec<_>>();
+        if changed.is_empty() {
+            return writeln!(f, "\nNo gas changes against the baseline.");
+        }
+        for contract in changed {
+            writeln!(f, "\n{}", contract.format_table_output())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ContractDiff {
+    name: String,
+    deployment_gas: Delta,
+    deployment_size: Delta,
+    functions: Vec<FunctionDiff>,
+}
+
+impl ContractDiff {
+    fn is_changed(&self) -> bool {
+        self.deployment_gas.is_changed()
+            || self.deployment_size.is_changed()
+            || self.functions.iter().any(FunctionDiff::is_changed)
+    }
+
+    fn format_table_output(&self) -> Table {
+        let mut table = Table::new();
+        if shell::is_markdown() {
+            table.load_preset(ASCII_MARKDOWN);
+        } else {
+            table.apply_modifier(UTF8_ROUND_CORNERS);
+        }
+
+        table.set_header(vec![Cell::new(format!("{} Contract", self.name)).fg(Color::Magenta)]);
+
+        table.add_row(vec![
+            Cell::new("Deployment Cost").fg(Color::Cyan),
+            Cell::new("Deployment Size").fg(Color::Cyan),
+        ]);
+        table.add_row(vec![self.deployment_gas.cell(), self.deployment_size.cell()]);
+
+        // Add a blank row to separate deployment info from function info.
+        table.add_row(vec![Cell::new("")]);
+
+        table.add_row(vec![
+            Cell::new("Function Name"),
+            Cell::new("Min").fg(Color::Green),
+            Cell::new("Avg").fg(Color::Yellow),
+            Cell::new("Median").fg(Color::Yellow),
+            Cell::new("Max").fg(Color::Red),
+            Cell::new("# Calls").fg(Color::Cyan),
+        ]);
+
+        for function in &self.functions {
+            let delta = |f: fn(&GasInfo) -> u64| {
+                Delta::new(function.old.as_ref().map(f), function.new.as_ref().map(f)).cell()
+            };
+            table.add_row(vec![
+                Cell::new(&function.signature),
+                delta(|gas_info| gas_info.min),
+                delta(|gas_info| gas_info.mean),
+                delta(|gas_info| gas_info.median),
+                delta(|gas_info| gas_info.max),
+                delta(|gas_info| gas_info.calls),
+            ]);
+        }
+
+        table
+    }
+}
+
+#[derive(Clone, Debug)]
+struct FunctionDiff {
+    signature: String,
+    old: Option<GasInfo>,
+    new: Option<GasInfo>,
+}
+
+impl FunctionDiff {
+    fn is_changed(&self) -> bool {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => {
+                (old.calls, old.min, old.mean, old.median, old.max)
+                    != (new.calls, new.min, new.mean, new.median, new.max)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// A value in the baseline and in the current report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Delta {
+    old: Option<u64>,
+    new: Option<u64>,
+}
+
+impl Delta {
+    fn new(old: Option<u64>, new: Option<u64>) -> Self {
+        Self { old, new }
+    }
+
+    fn is_changed(&self) -> bool {
+        self.old != self.new
+    }
+
+    /// Returns the relative change in percent, if the value is in both reports.
+    fn pct_change(&self) -> Option<f64> {
+        let (old, new) = (self.old?, self.new?);
+        Some(if old == 0 {
+            if new == 0 { 0.0 } else { f64::INFINITY }
+        } else {
+            (new as f64 - old as f64) / old as f64 * 100.0
+        })
+    }
+
+    /// Returns whether the value increased by more than the given percentage.
+    fn exceeds(&self, threshold_pct: f64) -> bool {
+        self.pct_change().is_some_and(|change| change > threshold_pct)
+    }
+
+    fn cell(&self) -> Cell {
+        match (self.old, self.new) {
+            (Some(old), Some(new)) if old == new => Cell::new(new.to_string()),
+            (Some(old), Some(new)) => {
+                let change = new as i128 - old as i128;
+                let color = if change > 0 { Color::Red } else { Color::Green };
+                Cell::new(format!("{new} ({change:+}, {:+.2}%)", self.pct_change().unwrap()))
+                    .fg(color)
+            }
+            (None, Some(new)) => Cell::new(format!("{new} (new)")).fg(Color::Cyan),
+            (Some(old), None) => Cell::new(format!("- (was {old})")).fg(Color::DarkGrey),
+            (None, None) => Cell::new(""),
+        }
+    }
+}
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.old, self.new, self.pct_change()) {
+            (Some(old), Some(new), Some(change)) => write!(f, "{old} → {new} ({change:+.2}%)"),
+            (None, Some(new), _) => write!(f, "{new} (new)"),
+            (Some(old), None, _) => write!(f, "removed (was {old})"),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ContractInfo {
     pub gas: u64,
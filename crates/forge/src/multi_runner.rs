@@ -302,6 +302,8 @@ pub struct TestRunnerConfig {
 
     /// Whether to collect line coverage info
     pub line_coverage: bool,
+    /// Whether to profile the gas spent by unit tests
+    pub gas_profile: bool,
     /// Whether to collect debug info
     pub debug: bool,
     /// Whether to enable steps tracking in the tracer.
@@ -327,6 +329,7 @@ pub fn reconfigure_with(&mut self, config: Arc<Config>) {
 
         // Specific to Forge, not present in config.
         // self.line_coverage = N/A;
+        // self.gas_profile = N/A;
         // self.debug = N/A;
         // self.decode_internal = N/A;
 
@@ -350,6 +353,7 @@ pub fn configure_executor(&self, executor: &mut Executor) {
         }
         inspector.tracing(self.trace_mode());
         inspector.collect_line_coverage(self.line_coverage);
+        inspector.collect_gas_profile(self.gas_profile);
         inspector.enable_isolation(self.isolation);
         inspector.networks(self.networks);
         // inspector.set_create2_deployer(self.evm_opts.create2_deployer);
@@ -381,6 +385,7 @@ pub fn executor(
                     .cheatcodes(cheats_config)
                     .trace_mode(self.trace_mode())
                     .line_coverage(self.line_coverage)
+                    .gas_profile(self.gas_profile)
                     .enable_isolation(self.isolation)
                     .networks(self.networks)
                     .create2_deployer(self.evm_opts.create2_deployer)
@@ -418,6 +423,8 @@ pub struct MultiContractRunnerBuilder {
     pub config: Arc<Config>,
     /// Whether or not to collect line coverage info
     pub line_coverage: bool,
+    /// Whether or not to profile the gas spent by unit tests
+    pub gas_profile: bool,
     /// Whether or not to collect debug info
     pub debug: bool,
     /// Whether to enable steps tracking in the tracer.
@@ -439,6 +446,7 @@ pub fn new(config: Arc<Config>) -> Self {
             evm_spec: Default::default(),
             fork: Default::default(),
             line_coverage: Default::default(),
+            gas_profile: Default::default(),
             debug: Default::default(),
             isolation: Default::default(),
             decode_internal: Default::default(),
@@ -472,6 +480,11 @@ pub fn set_coverage(mut self, enable: bool) -> Self {
         self
     }
 
+    pub fn set_gas_profile(mut self, enable: bool) -> Self {
+        self.gas_profile = enable;
+        self
+    }
+
     pub fn set_debug(mut self, enable: bool) -> Self {
         self.debug = enable;
         self
@@ -605,6 +618,7 @@ pub fn build<C: Compiler<CompilerContract = Contract>>(
                 spec_id: self.evm_spec.unwrap_or_else(|| self.config.evm_spec_id()),
                 sender: self.sender.unwrap_or(self.config.sender),
                 line_coverage: self.line_coverage,
+                gas_profile: self.gas_profile,
                 debug: self.debug,
                 decode_internal: self.decode_internal,
                 inline_config: Arc::new(InlineConfig::new_parsed(output, &self.config)?),
@@ -1,9 +1,9 @@
 use crate::cmd::{
     bind::BindArgs, bind_json, build::BuildArgs, cache::CacheArgs, clone::CloneArgs,
     compiler::CompilerArgs, config, coverage, create::CreateArgs, doc::DocArgs, eip712, flatten,
-    fmt::FmtArgs, geiger, generate, init::InitArgs, inspect, install::InstallArgs, lint::LintArgs,
-    remappings::RemappingArgs, remove::RemoveArgs, selectors::SelectorsSubcommands, snapshot,
-    soldeer, test, tree, update,
+    fmt::FmtArgs, fuzz, geiger, generate, init::InitArgs, inspect, install::InstallArgs,
+    lint::LintArgs, remappings::RemappingArgs, remove::RemoveArgs, selectors::SelectorsSubcommands,
+    snapshot, soldeer, test, tree, update,
 };
 use clap::{Parser, Subcommand, ValueHint};
 use forge_script::ScriptArgs;
@@ -36,6 +36,9 @@ pub enum ForgeSubcommand {
     #[command(visible_alias = "t")]
     Test(test::TestArgs),
 
+    /// Run a long-running fuzzing campaign.
+    Fuzz(fuzz::FuzzArgs),
+
     /// Run a smart contract as a script, building transactions that can be sent onchain.
     Script(ScriptArgs),
 
@@ -6,7 +6,7 @@
     gas_report::GasReport,
 };
 use alloy_primitives::{
-    Address, Log,
+    Address, Bytes, Log,
     map::{AddressHashMap, HashMap},
 };
 use eyre::Report;
@@ -17,6 +17,7 @@
     decode::SkipReason,
     executors::{RawCallResult, invariant::InvariantMetrics},
     fuzz::{CounterExample, FuzzCase, FuzzFixtures, FuzzTestResult},
+    inspectors::GasProfile,
     traces::{CallTraceArena, CallTraceDecoder, TraceKind, Traces},
 };
 use serde::{Deserialize, Serialize};
@@ -383,6 +384,19 @@ pub fn is_skipped(self) -> bool {
     }
 }
 
+/// The invariant broken by a failed invariant test.
+#[derive(Clone, Debug)]
+pub struct BrokenInvariant {
+    /// The address of the invariant test contract.
+    pub addr: Address,
+    /// The calldata of the broken invariant function.
+    pub calldata: Bytes,
+    /// Whether the invariant failed because a call reverted.
+    pub fail_on_revert: bool,
+    /// The reason the invariant was broken, if known.
+    pub reason: Option<String>,
+}
+
 /// The result of an executed test.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TestResult {
@@ -399,6 +413,10 @@ pub struct TestResult {
     /// Minimal reproduction test case for failing test
     pub counterexample: Option<CounterExample>,
 
+    /// The broken invariant, used to generate regression tests.
+    #[serde(skip)]
+    pub invariant_failure: Option<BrokenInvariant>,
+
     /// Any captured & parsed as strings logs along the test's execution which should
     /// be printed to the user.
     pub logs: Vec<Log>,
@@ -423,6 +441,14 @@ pub struct TestResult {
     #[serde(skip)]
     pub line_coverage: Option<HitMaps>,
 
+    /// Gas profile of unit tests, collected with `--gas-profile`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<GasProfile>,
+
+    /// Number of edges seen by coverage guided fuzz and invariant tests.
+    #[serde(skip)]
+    pub edges_seen: usize,
+
     /// Labeled addresses
     #[serde(rename = "labeled_addresses")] // Backwards compatibility.
     pub labels: AddressHashMap<String>,
@@ -573,6 +599,7 @@ pub fn single_result(
         self.reason = reason;
         self.duration = Duration::default();
         self.gas_report_traces = Vec::new();
+        self.gas_profile = raw_call_result.gas_profile;
 
         if let Some(cheatcodes) = raw_call_result.cheatcodes {
             self.breakpoints = cheatcodes.breakpoints;
@@ -591,6 +618,7 @@ pub fn fuzz_result(&mut self, result: FuzzTestResult) {
             runs: result.gas_by_case.len(),
             failed_corpus_replays: result.failed_corpus_replays,
         };
+        self.edges_seen = result.edges_seen;
 
         // Record logs, labels, traces and merge coverages.
         extend!(self, result, TraceKind::Execution);
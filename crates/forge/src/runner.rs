@@ -46,7 +46,7 @@
     fuzz::{BaseCounterExample, FuzzTestResult},
     multi_runner::{TestContract, TestRunnerConfig},
     progress::{TestsProgress, start_fuzz_progress},
-    result::{SuiteResult, TestResult, TestSetup},
+    result::{BrokenInvariant, SuiteResult, TestResult, TestSetup},
 };
 
 /// When running tests, we deploy all external libraries present in the project. To avoid additional
@@ -452,7 +452,7 @@ pub fn run_tests(mut self, filter: &dyn TestFilter) -> SuiteResult {
 
                 // Set fail fast flag if current test failed.
                 if res.status.is_failure() {
-                    early_exit.record_exit();
+                    early_exit.record_failure();
                 }
 
                 Some((sig, res))
@@ -747,6 +747,8 @@ fn run_invariant_test(
         executor
             .inspector_mut()
             .collect_edge_coverage(invariant_config.corpus.collect_edge_coverage());
+        // Record comparison operands to feed the dictionary with coverage guided fuzzing.
+        executor.inspector_mut().collect_cmp_log(invariant_config.corpus.is_coverage_guided());
         let mut config = invariant_config.clone();
         let (failure_dir, failure_file) = test_paths(
             &mut config.corpus,
@@ -779,8 +781,8 @@ fn run_invariant_test(
         );
 
         // Try to replay recorded failure if any.
-        if let Some(mut call_sequence) =
-            persisted_call_sequence(failure_file.as_path(), test_bytecode)
+        if let Some(InvariantPersistedFailure { mut call_sequence, reason, .. }) =
+            persisted_failure(failure_file.as_path(), test_bytecode)
         {
             // Create calls from failed sequence and check if invariant still broken.
             let txes = call_sequence
@@ -845,6 +847,7 @@ fn run_invariant_test(
                                 failure_file.as_path(),
                                 &call_sequence,
                                 test_bytecode,
+                                reason.as_deref(),
                             );
                         }
                     }
@@ -853,6 +856,12 @@ fn run_invariant_test(
                     }
                 }
 
+                self.result.invariant_failure = Some(BrokenInvariant {
+                    addr: invariant_contract.address,
+                    calldata: func.selector().to_vec().into(),
+                    fail_on_revert: invariant_config.fail_on_revert,
+                    reason,
+                });
                 self.result.invariant_replay_fail(
                     replayed_entirely,
                     &invariant_contract.invariant_function.name,
@@ -887,6 +896,13 @@ fn run_invariant_test(
             Some(error) => match error {
                 InvariantFuzzError::BrokenInvariant(case_data)
                 | InvariantFuzzError::Revert(case_data) => {
+                    self.result.invariant_failure = Some(BrokenInvariant {
+                        addr: case_data.addr,
+                        calldata: case_data.calldata.clone(),
+                        fail_on_revert: case_data.fail_on_revert,
+                        reason: reason.clone(),
+                    });
+
                     // Replay error to create counterexample and to collect logs, traces and
                     // coverage.
                     match case_data.test_error {
@@ -915,6 +931,7 @@ fn run_invariant_test(
                                             failure_file.as_path(),
                                             &call_sequence,
                                             test_bytecode,
+                                            reason.as_deref(),
                                         );
 
                                         let original_seq_len = if let TestError::Fail(_, calls) =
@@ -971,6 +988,7 @@ fn run_invariant_test(
             invariant_result.metrics,
             invariant_result.failed_corpus_replays,
         );
+        self.result.edges_seen = invariant_result.edges_seen;
         self.result
     }
 
@@ -1011,6 +1029,8 @@ fn run_fuzz_test(mut self, func: &Function) -> TestResult {
         // Enable edge coverage if running with coverage guided fuzzing or with edge coverage
         // metrics (useful for benchmarking the fuzzer).
         executor.inspector_mut().collect_edge_coverage(fuzz_config.corpus.collect_edge_coverage());
+        // Record comparison operands to feed the dictionary with coverage guided fuzzing.
+        executor.inspector_mut().collect_cmp_log(fuzz_config.corpus.is_coverage_guided());
         // Load persisted counterexample, if any.
         let persisted_failure =
             foundry_common::fs::read_json_file::<BaseCounterExample>(failure_file.as_path()).ok();
@@ -1160,11 +1180,14 @@ struct InvariantPersistedFailure {
     /// Bytecode of the test contract that generated the counterexample.
     #[serde(skip_serializing_if = "Option::is_none")]
     driver_bytecode: Option<Bytes>,
+    /// The reason the invariant was broken.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
 }
 
 /// Helper function to load failed call sequence from file.
 /// Ignores failure if generated with different test contract than the current one.
-fn persisted_call_sequence(path: &Path, bytecode: &Bytes) -> Option<Vec<BaseCounterExample>> {
+fn persisted_failure(path: &Path, bytecode: &Bytes) -> Option<InvariantPersistedFailure> {
     foundry_common::fs::read_json_file::<InvariantPersistedFailure>(path).ok().and_then(
         |persisted_failure| {
             if let Some(persisted_bytecode) = &persisted_failure.driver_bytecode {
@@ -1177,7 +1200,7 @@ fn persisted_call_sequence(path: &Path, bytecode: &Bytes) -> Option<Vec<BaseCoun
                     return None;
                 }
             };
-            Some(persisted_failure.call_sequence)
+            Some(persisted_failure)
         },
     )
 }
@@ -1204,6 +1227,7 @@ fn record_invariant_failure(
     failure_file: &Path,
     call_sequence: &[BaseCounterExample],
     test_bytecode: &Bytes,
+    reason: Option<&str>,
 ) {
     if let Err(err) = foundry_common::fs::create_dir_all(failure_dir) {
         error!(%err, "Failed to create invariant failure dir");
@@ -1215,6 +1239,7 @@ fn record_invariant_failure(
         &InvariantPersistedFailure {
             call_sequence: call_sequence.to_owned(),
             driver_bytecode: Some(test_bytecode.clone()),
+            reason: reason.map(str::to_string),
         },
     ) {
         error!(%err, "Failed to record call sequence");
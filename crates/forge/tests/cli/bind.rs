@@ -24,3 +24,91 @@
 Bindings have been generated to [..]
 "#]]);
 });
+
+forgetest!(bind_typescript, |prj, cmd| {
+    prj.add_source(
+        "Counter.sol",
+        r#"
+contract Counter {
+    uint256 public number;
+
+    event Incremented(uint256 indexed number);
+
+    error Unauthorized(address caller);
+
+    function increment() public {
+        number++;
+        emit Incremented(number);
+    }
+}
+   "#,
+    );
+    cmd.args(["bind", "--select", "^Counter$", "--lang", "typescript"]).assert_success().stdout_eq(
+        str![[r#"
+[COMPILING_FILES] with [SOLC_VERSION]
+[SOLC_VERSION] [ELAPSED]
+Compiler run successful!
+Generating bindings for 1 contracts
+Bindings have been generated to [..]
+"#]],
+    );
+
+    let bindings = prj.root().join("out/bindings");
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(bindings.join("index.ts")).unwrap(),
+        str![[r#"
+// This file is autogenerated by `forge bind`.
+// Do not manually edit this file, it may be overwritten by the codegen system at any time.
+export * from "./Counter.js";
+
+"#]]
+    );
+    let counter = std::fs::read_to_string(bindings.join("Counter.ts")).unwrap();
+    assert!(counter.contains("export const counterAbi = ["), "{counter}");
+    assert!(counter.contains("export const counterBytecode = \"0x"), "{counter}");
+    assert!(counter.contains("Incremented: \"0x"), "{counter}");
+    assert!(counter.contains("export type CounterIncrementedEvent = { number: bigint };"));
+    assert!(counter.contains("\"0x8e4a23d6\": \"Unauthorized\","), "{counter}");
+    assert!(
+        counter.contains("export type CounterUnauthorizedError = readonly [`0x${string}`];"),
+        "{counter}"
+    );
+    assert!(counter.contains("export function decodeCounterErrorName("), "{counter}");
+
+    // Existing bindings are checked for consistency.
+    cmd.forge_fuse()
+        .args(["bind", "--select", "^Counter$", "--lang", "typescript", "--skip-build"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Bindings found. Checking for consistency.
+Checking bindings for 1 contracts
+OK.
+
+"#]]);
+
+    std::fs::write(bindings.join("Counter.ts"), "export {};\n").unwrap();
+    cmd.forge_fuse()
+        .args(["bind", "--select", "^Counter$", "--lang", "ts", "--skip-build"])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: File contents do not match expected contents for "[..]Counter.ts"
+
+"#]]);
+
+    // Single file bindings are written to `index.ts`.
+    cmd.forge_fuse()
+        .args([
+            "bind",
+            "--select",
+            "^Counter$",
+            "--lang",
+            "typescript",
+            "--single-file",
+            "--overwrite",
+            "--skip-build",
+        ])
+        .assert_success();
+    assert!(!bindings.join("Counter.ts").exists());
+    let index = std::fs::read_to_string(bindings.join("index.ts")).unwrap();
+    assert!(index.contains("export const counterAbi = ["), "{index}");
+});
@@ -2727,6 +2727,64 @@ struct Bar {
         );
 });
 
+forgetest!(gas_report_baseline, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "Counter.sol",
+        r#"
+import "./test.sol";
+
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number++;
+    }
+}
+
+contract CounterTest is DSTest {
+    function testIncrement() public {
+        new Counter().increment();
+    }
+}
+"#,
+    );
+
+    let report =
+        cmd.args(["test", "--gas-report", "--json"]).assert_success().get_output().stdout_lossy();
+    let baseline = prj.root().join("baseline.json");
+    fs::write(&baseline, &report).unwrap();
+
+    cmd.forge_fuse()
+        .args(["test", "--gas-report", "--gas-report-baseline"])
+        .arg(&baseline)
+        .args(["--gas-report-threshold", "5"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+No gas changes against the baseline.
+...
+"#]]);
+
+    // Lower the recorded gas to simulate a regression.
+    let mut report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    report[0]["deployment"]["gas"] = 1.into();
+    report[0]["functions"]["increment()"]["mean"] = 1.into();
+    fs::write(&baseline, report.to_string()).unwrap();
+
+    cmd.forge_fuse()
+        .args(["test", "--gas-report", "--gas-report-baseline"])
+        .arg(&baseline)
+        .args(["--gas-report-threshold", "5"])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: gas regressions above 5% against the baseline:
+src/Counter.sol:Counter deployment cost: 1 → [..] ([..]%)
+src/Counter.sol:Counter::increment() average gas: 1 → [..] ([..]%)
+
+"#]]);
+});
+
 // <https://github.com/foundry-rs/foundry/issues/9858>
 forgetest_init!(gas_report_fallback_with_calldata, |prj, cmd| {
     prj.initialize_default_contracts();
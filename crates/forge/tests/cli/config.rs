@@ -150,6 +150,7 @@
     "ERC",
     "URI",
 ]
+custom = []
 
 [doc]
 out = "docs"
@@ -158,6 +159,10 @@
 homepage = "README.md"
 ignore = []
 
+[coverage]
+exclude = []
+overrides = []
+
 [fuzz]
 runs = 256
 fail_on_revert = true
@@ -197,6 +202,7 @@
 failure_persist_dir = "cache/invariant"
 show_metrics = true
 show_solidity = false
+threads = 1
 
 [labels]
 
@@ -337,6 +343,7 @@
         fmt: Default::default(),
         lint: Default::default(),
         doc: Default::default(),
+        coverage: Default::default(),
         bind_json: Default::default(),
         fs_permissions: Default::default(),
         labels: Default::default(),
@@ -1274,7 +1281,8 @@
     "timeout": null,
     "show_solidity": false,
     "max_time_delay": null,
-    "max_block_delay": null
+    "max_block_delay": null,
+    "threads": 1
   },
   "ffi": false,
   "allow_internal_expect_revert": false,
@@ -1344,7 +1352,8 @@
     "mixed_case_exceptions": [
       "ERC",
       "URI"
-    ]
+    ],
+    "custom": []
   },
   "doc": {
     "out": "docs",
@@ -1353,6 +1362,10 @@
     "homepage": "README.md",
     "ignore": []
   },
+  "coverage": {
+    "exclude": [],
+    "overrides": []
+  },
   "bind_json": {
     "out": "utils/JsonBindings.sol",
     "include": [],
@@ -1,4 +1,5 @@
 use foundry_common::fs::{self, files_with_ext};
+use foundry_config::{CoverageOverride, CoverageThresholds};
 use foundry_test_utils::{
     TestCommand, TestProject,
     snapbox::{Data, IntoData},
@@ -596,6 +597,220 @@ fn basic_base(prj: TestProject, mut cmd: TestCommand) {
     );
 });
 
+forgetest!(cobertura_and_html, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo() public {
+        i = 1;
+    }
+}
+    "#,
+    );
+
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+
+contract AContractTest is DSTest {
+    AContract a = new AContract();
+
+    function testFoo() public {
+        a.foo();
+    }
+}
+    "#,
+    );
+
+    cmd.args(["coverage", "--report=cobertura", "--report-file"]).assert_file(str![[r#"
+<?xml version="1.0" ?>
+<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">
+<coverage line-rate="1.0000" branch-rate="1.0000" lines-covered="2" lines-valid="2" branches-covered="0" branches-valid="0" complexity="0" version="[..]" timestamp="[..]">
+  <sources>
+    <source>[..]</source>
+  </sources>
+  <packages>
+    <package name="src" line-rate="1.0000" branch-rate="1.0000" complexity="0">
+      <classes>
+        <class name="AContract.sol" filename="src/AContract.sol" line-rate="1.0000" branch-rate="1.0000" complexity="0">
+          <methods>
+            <method name="AContract.foo" signature="" line-rate="1" branch-rate="1" complexity="0">
+              <lines>
+                <line number="7" hits="1"/>
+              </lines>
+            </method>
+          </methods>
+          <lines>
+            <line number="7" hits="1"/>
+            <line number="8" hits="1"/>
+          </lines>
+        </class>
+      </classes>
+    </package>
+  </packages>
+</coverage>
+
+"#]]);
+
+    cmd.forge_fuse().args(["coverage", "--report=html"]).assert_success();
+    let index = fs::read_to_string(prj.root().join("coverage-report/index.html")).unwrap();
+    assert!(index.contains(r#"<a href="src/AContract.sol.html">src/AContract.sol</a>"#));
+    let page =
+        fs::read_to_string(prj.root().join("coverage-report/src/AContract.sol.html")).unwrap();
+    assert!(page.contains(r#"<a href="../index.html">Index</a>"#));
+    assert!(page.contains(r#"<tr id="L8" class="hit">"#));
+});
+
+forgetest!(diff_coverage, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo() public {
+        i = 1;
+    }
+}
+    "#,
+    );
+
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+
+contract AContractTest is DSTest {
+    AContract a = new AContract();
+
+    function testFoo() public {
+        a.foo();
+    }
+}
+    "#,
+    );
+
+    cmd.git_init();
+    cmd.git_add();
+    cmd.git_commit("init");
+
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo() public {
+        i = 1;
+    }
+
+    function bar() public {
+        i = 2;
+    }
+}
+    "#,
+    );
+
+    cmd.forge_fuse()
+        .args(["coverage", "--diff-base", "HEAD", "--min-diff-coverage", "80"])
+        .assert_failure()
+        .stdout_eq(str![[r#"
+...
+Coverage of changed lines:
+╭-------------------+-------------+---------------╮
+| File              | % Lines     | % Branches    |
++=================================================+
+| src/AContract.sol | 0.00% (0/2) | 100.00% (0/0) |
+|-------------------+-------------+---------------|
+| Total             | 0.00% (0/2) | 100.00% (0/0) |
+╰-------------------+-------------+---------------╯
+
+"#]])
+        .stderr_eq(str![[r#"
+...
+Error: coverage of changed lines is 0.00%, below the minimum of 80%
+
+"#]]);
+});
+
+forgetest!(coverage_thresholds, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo() public {
+        i = 1;
+    }
+
+    function bar() public {
+        i = 2;
+    }
+}
+    "#,
+    );
+
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+
+contract AContractTest is DSTest {
+    AContract a = new AContract();
+
+    function testFoo() public {
+        a.foo();
+    }
+}
+    "#,
+    );
+
+    prj.update_config(|config| {
+        config.coverage.thresholds.min_lines = Some(90.);
+        config.coverage.overrides = vec![CoverageOverride {
+            path: "src/AContract.sol".parse().unwrap(),
+            thresholds: CoverageThresholds { min_functions: Some(100.), ..Default::default() },
+        }];
+    });
+
+    cmd.arg("coverage")
+        .assert_failure()
+        .stdout_eq(str![[r#"
+...
+Coverage thresholds not met:
+╭-------------------+-----------+----------+---------╮
+| File              | Metric    | Coverage | Minimum |
++====================================================+
+| src/AContract.sol | Functions | 50.00%   | 100.00% |
+|-------------------+-----------+----------+---------|
+| Total             | Lines     | 50.00%   | 90.00%  |
+╰-------------------+-----------+----------+---------╯
+
+"#]])
+        .stderr_eq(str![[r#"
+...
+Error: coverage is below the configured thresholds
+
+"#]]);
+
+    // Excluded files are not subject to thresholds.
+    prj.update_config(|config| {
+        config.coverage.exclude = vec!["src/AContract.sol".parse().unwrap()];
+    });
+    cmd.forge_fuse().arg("coverage").assert_success();
+});
+
 forgetest!(branch, |prj, cmd| {
     prj.insert_ds_test();
     prj.add_source(
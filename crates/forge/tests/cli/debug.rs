@@ -102,3 +102,71 @@
         std::mem::forget(prj);
     }
 );
+
+/// Encodes Debug Adapter Protocol requests with their headers.
+fn dap_requests(requests: &[serde_json::Value]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (seq, request) in requests.iter().enumerate() {
+        let mut request = request.clone();
+        request["seq"] = (seq + 1).into();
+        request["type"] = "request".into();
+        let content = request.to_string();
+        out.extend(format!("Content-Length: {}\r\n\r\n{content}", content.len()).into_bytes());
+    }
+    out
+}
+
+// Serves the debugger over the Debug Adapter Protocol on stdio.
+forgetest!(debug_dap_stdio, |prj, cmd| {
+    let path = prj.add_raw_source(
+        "CounterTest.sol",
+        r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract CounterTest {
+    uint256 public count;
+
+    function testIncrement() public {
+        uint256 a = 1;
+        count += a;
+        count += 2;
+    }
+}
+"#,
+    );
+
+    let stdin = dap_requests(&[
+        serde_json::json!({ "command": "initialize", "arguments": { "adapterID": "forge" } }),
+        serde_json::json!({ "command": "launch", "arguments": { "stopOnEntry": true } }),
+        serde_json::json!({
+            "command": "setBreakpoints",
+            "arguments": { "source": { "path": path }, "breakpoints": [{ "line": 10 }] }
+        }),
+        serde_json::json!({ "command": "configurationDone" }),
+        serde_json::json!({ "command": "continue", "arguments": { "threadId": 1 } }),
+        serde_json::json!({ "command": "stackTrace", "arguments": { "threadId": 1 } }),
+        serde_json::json!({ "command": "scopes", "arguments": { "frameId": 0 } }),
+        serde_json::json!({ "command": "variables", "arguments": { "variablesReference": 4 } }),
+        serde_json::json!({ "command": "disconnect" }),
+    ]);
+
+    let output = cmd
+        .args(["test", "--mt", "testIncrement", "--debug", "--dap"])
+        .stdin(stdin)
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+
+    // Only protocol messages are written to stdout.
+    assert!(output.starts_with("Content-Length: "), "{output}");
+    assert!(output.contains(r#""event":"initialized""#), "{output}");
+    assert!(output.contains(r#""reason":"entry""#), "{output}");
+    assert!(output.contains(r#""reason":"breakpoint""#), "{output}");
+    assert!(output.contains(r#""verified":true"#), "{output}");
+    assert!(output.contains(r#""name":"CounterTest::testIncrement""#), "{output}");
+    assert!(output.contains(r#""line":10"#), "{output}");
+    assert!(output.contains(r#""name":"Storage""#), "{output}");
+    // Storage slots are identified with the storage layout.
+    assert!(output.contains(r#""name":"count""#), "{output}");
+    assert!(output.contains(r#""command":"disconnect""#), "{output}");
+});
@@ -112,3 +112,91 @@ fn can_generate_solmate_docs() {
         content.lines().find(|line| line.contains("[IBase]")).unwrap_or("not found")
     );
 });
+
+forgetest!(reports_natspec_coverage, |prj, cmd| {
+    prj.add_source(
+        "Token.sol",
+        r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract Token {
+    /// @notice Emitted on transfers.
+    event Transfer(address indexed from, address indexed to);
+
+    /// @notice Transfers tokens.
+    /// @param to The recipient.
+    function transfer(address to) external {}
+
+    function _burn() internal {}
+}
+"#,
+    );
+
+    cmd.args(["doc", "--coverage"]).assert_success().stdout_eq(str![[r#"
+[..]Token.sol:Token 1/2 (50.00%)
+  event Transfer: missing @param from, @param to
+NatSpec coverage: 1/2 (50.00%)
+
+"#]]);
+    assert!(!prj.root().join("docs").exists());
+
+    cmd.forge_fuse()
+        .args(["doc", "--coverage", "--coverage-threshold", "80"])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: NatSpec coverage 50.00% is below the threshold of 80%
+
+"#]]);
+});
+
+forgetest!(can_generate_json_and_html_docs, |prj, cmd| {
+    prj.add_source(
+        "Token.sol",
+        r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/// @title Token
+contract Token {
+    /// @notice Transfers tokens.
+    /// @param to The recipient.
+    function transfer(address to) external {}
+}
+"#,
+    );
+
+    cmd.args(["doc", "--format", "json"]).assert_success();
+
+    let index: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(prj.root().join("docs/index.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        index,
+        serde_json::json!([{
+            "identity": "Token",
+            "path": "src/Token.sol/contract.Token.json",
+            "source": "src/Token.sol",
+        }])
+    );
+
+    let doc: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(prj.root().join("docs/src/src/Token.sol/contract.Token.json"))
+            .unwrap(),
+    )
+    .unwrap();
+    let function = &doc["items"][0]["items"][0];
+    assert_eq!(function["signature"], "transfer(address)");
+    assert_eq!(
+        function["natspec"][0],
+        serde_json::json!({ "tag": "notice", "value": "Transfers tokens." })
+    );
+    assert!(!prj.root().join("docs/book.toml").exists());
+
+    cmd.forge_fuse().args(["doc", "--format", "html"]).assert_success();
+
+    let html = std::fs::read_to_string(prj.root().join("docs/index.html")).unwrap();
+    assert!(html.contains("<section id=\"Token\""), "{html}");
+    assert!(html.contains("<p class=\"notice\">Transfers tokens.</p>"), "{html}");
+    assert!(html.contains("<input id=\"search\""), "{html}");
+});
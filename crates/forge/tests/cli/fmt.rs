@@ -162,3 +162,72 @@
     cmd.forge_fuse().args(["fmt", "--check", "src/FmtTest.sol"]);
     cmd.assert_success();
 });
+
+// Test that only the given lines are formatted
+forgetest!(fmt_range, |prj, cmd| {
+    prj.add_raw_source("Test.sol", UNFORMATTED);
+    cmd.args(["fmt", "--range", "7:7", "src/Test.sol"]).assert_success();
+
+    // Only the statement is formatted, not the enclosing function and contract.
+    assert_eq!(
+        std::fs::read_to_string(prj.root().join("src/Test.sol")).unwrap(),
+        r#"// SPDX-License-Identifier: MIT
+pragma         solidity  =0.8.30    ;
+
+contract  Test  {
+    uint256    public    value ;
+    function   setValue ( uint256   _value )   public   {
+        value = _value;
+    }
+}"#
+    );
+
+    // Selecting the header of the function formats it as a whole.
+    cmd.forge_fuse().args(["fmt", "--range", "6:6", "src/Test.sol"]).assert_success();
+    assert_eq!(
+        std::fs::read_to_string(prj.root().join("src/Test.sol")).unwrap(),
+        r#"// SPDX-License-Identifier: MIT
+pragma         solidity  =0.8.30    ;
+
+contract  Test  {
+    uint256    public    value ;
+    function setValue(uint256 _value) public {
+        value = _value;
+    }
+}"#
+    );
+
+    cmd.forge_fuse().args(["fmt", "--range", "7:3", "src/Test.sol"]).assert_failure();
+});
+
+// Test that only the lines changed since a git revision are formatted
+forgetest!(fmt_changed_since, |prj, cmd| {
+    prj.add_raw_source("Test.sol", UNFORMATTED);
+    prj.add_raw_source("Other.sol", UNFORMATTED);
+    cmd.git_init();
+    cmd.git_add();
+    cmd.git_commit("init");
+
+    prj.add_raw_source(
+        "Test.sol",
+        &UNFORMATTED.replace("value   =   _value ;", "value   =   _value   +   1 ;"),
+    );
+    // Untracked files are entirely changed.
+    prj.add_raw_source("New.sol", UNFORMATTED);
+    cmd.forge_fuse().args(["fmt", "--changed-since", "HEAD"]).assert_success();
+
+    assert_eq!(
+        std::fs::read_to_string(prj.root().join("src/Test.sol")).unwrap(),
+        r#"// SPDX-License-Identifier: MIT
+pragma         solidity  =0.8.30    ;
+
+contract  Test  {
+    uint256    public    value ;
+    function   setValue ( uint256   _value )   public   {
+        value = _value + 1;
+    }
+}"#
+    );
+    assert_eq!(std::fs::read_to_string(prj.root().join("src/Other.sol")).unwrap(), UNFORMATTED);
+    assert_eq!(std::fs::read_to_string(prj.root().join("src/New.sol")).unwrap(), FORMATTED);
+});
@@ -0,0 +1,103 @@
+//! Tests for the `forge fuzz` command.
+
+const COUNTER_TEST: &str = r#"
+import {Test} from "forge-std/Test.sol";
+
+contract CounterTest is Test {
+    uint256 public count;
+
+    function testFuzz_Branches(uint256 x) public {
+        if (x % 2 == 0) {
+            count += 1;
+        } else if (x % 3 == 0) {
+            count += 2;
+        } else {
+            count += 3;
+        }
+    }
+
+    function test_NotFuzzed() public pure {
+        revert("unit tests are not fuzzed");
+    }
+}
+"#;
+
+// Runs a campaign until the end of its time budget, reporting stats and persisting the corpus.
+forgetest_init!(fuzz_campaign_with_duration, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test("CounterTest.t.sol", COUNTER_TEST);
+
+    cmd.args(["fuzz", "testFuzz_Branches", "--duration", "2", "--stats-interval", "1"])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+[1s] execs: [..] ([..]/s), edges: [..], corpus: [..], failures: 0
+...
+"#]]);
+
+    let corpus_dir = prj.root().join("cache/fuzz/corpus/CounterTest/testFuzz_Branches");
+    assert!(std::fs::read_dir(corpus_dir).unwrap().next().is_some());
+});
+
+// Records unique failures in the failure persist dir and exits with an error.
+forgetest_init!(fuzz_campaign_records_failures, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "FailTest.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract FailTest is Test {
+    function testFuzz_Fail(uint256 x) public pure {
+        require(x < 100, "too big");
+    }
+}
+"#,
+    );
+
+    cmd.args(["fuzz", "--duration", "2", "--stats-interval", "1"]).assert_failure().stdout_eq(
+        str![[r#"
+...
+New failure in FailTest::testFuzz_Fail(uint256): too big
+Written to [..]
+...
+[1s] execs: [..] ([..]/s), edges: [..], corpus: [..], failures: 1
+...
+"#]],
+    );
+
+    let failures_dir = prj.root().join("cache/fuzz/failures/FailTest");
+    let failures = std::fs::read_dir(failures_dir)
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("testFuzz_Fail-"))
+        .count();
+    assert_eq!(failures, 1);
+});
+
+// Minimizes the persisted corpus of a previous campaign.
+forgetest_init!(fuzz_minimize_corpus, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test("CounterTest.t.sol", COUNTER_TEST);
+
+    cmd.args(["fuzz", "testFuzz_Branches", "--duration", "1"]).assert_success();
+
+    cmd.forge_fuse().args(["fuzz", "minimize", "testFuzz_Branches"]).assert_success().stdout_eq(
+        str![[r#"
+...
+Minimized corpus from [..] to [..] entries
+
+"#]],
+    );
+});
+
+// Reports an error when no fuzz or invariant test matches the pattern.
+forgetest_init!(fuzz_no_matching_tests, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test("CounterTest.t.sol", COUNTER_TEST);
+
+    cmd.args(["fuzz", "test_NotFuzzed", "--duration", "1"]).assert_failure().stderr_eq(str![[r#"
+Error: no fuzz or invariant tests match the provided pattern
+
+"#]]);
+});
@@ -0,0 +1,67 @@
+//! Tests for the `forge generate` command.
+
+// Generates a handler wrapping the state-changing functions of a token, with bounded inputs,
+// actors and ghost balances.
+forgetest_init!(generate_invariant_handler, |prj, cmd| {
+    prj.add_source(
+        "Token.sol",
+        r#"
+contract Token {
+    mapping(address => uint256) public balanceOf;
+
+    function mint(address to, uint256 amount) public {
+        balanceOf[to] += amount;
+    }
+
+    function transfer(address to, uint96 amount) public returns (bool) {
+        balanceOf[msg.sender] -= amount;
+        balanceOf[to] += amount;
+        return true;
+    }
+
+    function burn(uint256 calls, int8 callValue) public {
+        balanceOf[msg.sender] -= calls;
+    }
+
+    function deposit() public payable {
+        balanceOf[msg.sender] += msg.value;
+    }
+
+    function total() public pure returns (uint256) {
+        return 0;
+    }
+}
+"#,
+    );
+
+    cmd.args(["generate", "invariant-handler", "Token"]).assert_success().stdout_eq(str![[r#"
+...
+Generated invariant handler: [..]/test/handlers/TokenHandler.sol
+
+"#]]);
+
+    let handler =
+        std::fs::read_to_string(prj.root().join("test/handlers/TokenHandler.sol")).unwrap();
+    for expected in [
+        r#"import {Token} from "src/Token.sol";"#,
+        "contract TokenHandler is CommonBase, StdUtils {",
+        "uint256 public ghost_sumBalances;",
+        r#"countCall("mint(address,uint256)")"#,
+        "token.mint(to, amount);",
+        "to = actors[bound(uint256(uint160(to)), 0, actors.length - 1)];",
+        "token.deposit{value: callValue}();",
+        "amount = bound(amount, 0, type(uint96).max); // TODO: narrow down the range",
+        "amount = uint96(bound(uint256(amount), 0, type(uint96).max)); // TODO: narrow down the range",
+        // Parameters shadowing the members of the handler are renamed.
+        "uint256 calls_, int8 callValue_",
+        "callValue_ = int8(bound(int256(callValue_), type(int8).min, type(int8).max));",
+        "token.burn(calls_, callValue_);",
+    ] {
+        assert!(handler.contains(expected), "missing `{expected}` in:\n{handler}");
+    }
+    // View and pure functions are not wrapped.
+    assert!(!handler.contains("function total("));
+
+    // The generated handler compiles.
+    cmd.forge_fuse().arg("build").assert_success();
+});
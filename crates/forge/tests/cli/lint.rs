@@ -1,5 +1,5 @@
 use forge_lint::{linter::Lint, sol::med::REGISTERED_LINTS};
-use foundry_config::{DenyLevel, LintSeverity, LinterConfig};
+use foundry_config::{CustomLintPattern, CustomLintRule, DenyLevel, LintSeverity, LinterConfig};
 
 mod geiger;
 
@@ -54,6 +54,20 @@
 }
 "#;
 
+const CUSTOM_LINTS: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract CustomLints {
+    /// @notice Documented.
+    function documented(address target) external {
+        target.delegatecall("");
+    }
+
+    function undocumented() external {}
+}
+"#;
+
 const COUNTER_A: &str = r#"
 // SPDX-License-Identifier: MIT
 pragma solidity ^0.8.0;
@@ -190,6 +204,7 @@
             ignore: vec!["src/ContractWithLints.sol".into()],
             lint_on_build: true,
             mixed_case_exceptions: vec!["MIXED".to_string()],
+            custom: vec![],
         };
     });
     cmd.arg("lint").assert_success().stderr_eq(str![[""]]);
@@ -278,6 +293,67 @@
     ]]);
 });
 
+forgetest!(can_use_custom_lints, |prj, cmd| {
+    prj.add_source("CustomLints", CUSTOM_LINTS);
+    prj.create_file(
+        "lints/natspec.toml",
+        r#"
+[[rule]]
+id = "external-natspec"
+severity = "info"
+description = "external functions must be documented"
+help = "https://example.com/external-natspec"
+kind = "require-natspec"
+"#,
+    );
+
+    prj.update_config(|config| {
+        config.lint = LinterConfig {
+            custom: vec![CustomLintRule {
+                id: "no-delegatecall".into(),
+                severity: LintSeverity::High,
+                description: "delegatecall is forbidden".into(),
+                help: Some("https://example.com/no-delegatecall".into()),
+                pattern: CustomLintPattern::ForbiddenCall {
+                    functions: vec!["*.delegatecall".into()],
+                },
+            }],
+            ..Default::default()
+        };
+    });
+    cmd.arg("lint")
+        .args(["--only-lint", "no-delegatecall", "external-natspec"])
+        .assert_success()
+        .stderr_eq(str![[r#"
+warning[no-delegatecall]: delegatecall is forbidden
+ [FILE]:8:9
+  |
+8 |         target.delegatecall("");
+  |         ^^^^^^^^^^^^^^^^^^^^^^^
+  |
+  = help: https://example.com/no-delegatecall
+
+note[external-natspec]: external functions must be documented
+  [FILE]:11:14
+   |
+11 |     function undocumented() external {}
+   |              ^^^^^^^^^^^^
+   |
+   = help: https://example.com/external-natspec
+
+
+"#]]);
+
+    // Custom lints can't shadow built-in lints.
+    prj.update_config(|config| {
+        config.lint.custom[0].id = "incorrect-shift".into();
+    });
+    cmd.forge_fuse().arg("lint").assert_failure().stderr_eq(str![[r#"
+Error: Duplicate lint ID: incorrect-shift
+
+"#]]);
+});
+
 forgetest!(build_runs_linter_by_default, |prj, cmd| {
     prj.add_source("ContractWithLints", CONTRACT);
 
@@ -495,6 +571,49 @@
 "#]]);
 });
 
+forgetest!(can_use_lint_baseline, |prj, cmd| {
+    prj.add_source("CounterAWithLints", COUNTER_A);
+
+    // Record the existing findings.
+    cmd.args(["lint", "--write-baseline"]).assert_success().stdout_eq(str![[r#"
+[COMPILING_FILES] with [SOLC_VERSION]
+[SOLC_VERSION] [ELAPSED]
+Compiler run successful!
+Wrote 1 finding(s) to lint-baseline.json
+
+"#]]);
+    assert!(prj.root().join("lint-baseline.json").exists());
+
+    // Shift the existing finding and add a new one, only the new one is reported.
+    prj.add_source(
+        "CounterAWithLints",
+        r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+contract CounterA {
+    uint256 public counter;
+
+    uint256 public CounterA_Fail_Lint;
+    uint256 public New_Fail_Lint;
+}
+"#,
+    );
+    cmd.forge_fuse().args(["lint", "--baseline", "lint-baseline.json"]).assert_success().stderr_eq(
+        str![[r#"
+note[mixed-case-variable]: mutable variables should use mixedCase
+ [FILE]:9:20
+  |
+9 |     uint256 public New_Fail_Lint;
+  |                    ^^^^^^^^^^^^^ help: consider using: `newFailLint`
+  |
+  = help: https://book.getfoundry.sh/reference/forge/forge-lint#mixed-case-variable
+
+
+"#]],
+    );
+});
+
 // <https://github.com/foundry-rs/foundry/issues/11392>
 forgetest!(can_lint_param_constants, |prj, cmd| {
     prj.add_source("Counter", COUNTER_WITH_CONST);
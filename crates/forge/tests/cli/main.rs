@@ -19,6 +19,8 @@
 mod doc;
 mod eip712;
 mod failure_assertions;
+mod fuzz;
+mod generate;
 mod inline_config;
 mod install;
 mod json;
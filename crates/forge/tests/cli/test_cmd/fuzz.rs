@@ -837,3 +837,111 @@ struct TestTuple {
     test_literal(600, "testFuzz_String", "string", "\"xyzzy\"", 35);
     test_literal(999, "testFuzz_BytesFromString", "bytes", "0x78797a7a79", 19); // abi.encodePacked("xyzzy")
 });
+
+// Values compared at runtime are fed to the dictionary with coverage guided fuzzing.
+forgetest_init!(test_fuzz_cmp_log, |prj, cmd| {
+    prj.update_config(|config| {
+        config.fuzz.seed = Some(U256::from(100u32));
+        config.fuzz.runs = 1000;
+        config.fuzz.corpus.corpus_dir = Some("fuzz_corpus".into());
+    });
+    prj.add_test(
+        "Magic.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract MagicTest is Test {
+    function testFuzz_Magic(uint256 x) public view {
+        uint256 magic = uint256(keccak256(abi.encode(address(this))));
+        require(x != magic, "magic found");
+    }
+}
+   "#,
+    );
+
+    cmd.args(["test", "--mt", "testFuzz_Magic"]).assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: magic found; counterexample: [..]] testFuzz_Magic(uint256) (runs: [..], [AVG_GAS])
+...
+"#]]);
+});
+
+// Emits a regression test calling the fuzz test with its counterexample.
+forgetest_init!(fuzz_emit_regressions, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "FuzzRegression.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract FuzzRegression is Test {
+    function testFuzz_Bound(uint256 x, address owner, bytes memory data) public pure {
+        require(x < 100 || owner == address(0) || data.length > 100, "too big");
+    }
+}
+"#,
+    );
+
+    cmd.args(["test", "--emit-regressions", "test/regressions"]).assert_failure().stdout_eq(str![
+        [r#"
+...
+[FAIL: too big; counterexample: [..]] testFuzz_Bound(uint256,address,bytes) (runs: [..], [AVG_GAS])
+...
+Wrote 1 regression tests:
+  [..]/test/regressions/test/FuzzRegression.t/FuzzRegression.testFuzz_Bound.t.sol
+
+"#]
+    ]);
+
+    assert_data_eq!(
+        std::fs::read_to_string(
+            prj.root()
+                .join("test/regressions/test/FuzzRegression.t/FuzzRegression.testFuzz_Bound.t.sol")
+        )
+        .unwrap(),
+        str![[r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.6.2;
+
+import {FuzzRegression} from "test/FuzzRegression.t.sol";
+
+interface RegressionVm {
+    function computeCreateAddress(address deployer, uint256 nonce) external pure returns (address);
+    function deal(address account, uint256 newBalance) external;
+    function prank(address msgSender) external;
+    function roll(uint256 newHeight) external;
+    function warp(uint256 newTimestamp) external;
+}
+
+// Regression test for the failure of `test/FuzzRegression.t.sol:FuzzRegression::testFuzz_Bound`.
+// too big
+contract FuzzRegression_testFuzz_Bound_Regression {
+    RegressionVm internal constant vm =
+        RegressionVm(0x7109709ECfa91a80626fF3989D68f67F5b1DD12D);
+
+    FuzzRegression internal harness;
+
+    function setUp() public {
+        harness = new FuzzRegression();
+        vm.deal(address(harness), address(this).balance);
+    }
+
+    function test_testFuzz_Bound_regression() public {
+        harness.testFuzz_Bound([..], 0x[..], hex"[..]");
+    }
+}
+"#]]
+    );
+
+    // The regression test compiles, runs on its own and fails the same way.
+    cmd.forge_fuse()
+        .args(["test", "--match-path", "test/regressions/**"])
+        .assert_failure()
+        .stdout_eq(str![[r#"
+...
+Ran 1 test for [..]
+...
+[FAIL: too big] test_testFuzz_Bound_regression() ([GAS])
+...
+"#]]);
+});
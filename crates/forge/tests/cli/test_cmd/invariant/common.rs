@@ -1386,6 +1386,286 @@ struct FuzzSelector {
 "#]]);
 });
 
+forgetest_init!(invariant_shrink_arguments, |prj, cmd| {
+    prj.update_config(|config| {
+        config.fuzz.seed = Some(U256::from(100u32));
+        config.invariant.runs = 1;
+        config.invariant.depth = 5;
+    });
+
+    prj.add_test(
+        "InvariantShrinkArguments.t.sol",
+        r#"
+import "forge-std/Test.sol";
+
+contract Counter {
+    uint256 public number;
+
+    function setNumber(uint256 newNumber) public {
+        number = newNumber;
+    }
+}
+
+contract InvariantShrinkArguments is Test {
+    Counter public counter;
+
+    function setUp() public {
+        counter = new Counter();
+    }
+
+    function invariant_zero() public view {
+        require(counter.number() == 0, "number set");
+    }
+}
+"#,
+    );
+
+    // The random argument is shrunk to the smallest value breaking the invariant.
+    cmd.args(["test"]).assert_failure().stdout_eq(str![[r#"
+...
+Ran 1 test for test/InvariantShrinkArguments.t.sol:InvariantShrinkArguments
+[FAIL: number set]
+	[Sequence] (original: [..], shrunk: 1)
+		sender=[..] addr=[test/InvariantShrinkArguments.t.sol:Counter][..] calldata=setNumber(uint256) args=[1]
+ invariant_zero() ([..])
+...
+"#]]);
+});
+
+forgetest_init!(invariant_threads, |prj, cmd| {
+    prj.update_config(|config| {
+        config.invariant.runs = 50;
+        config.invariant.depth = 10;
+        config.invariant.threads = 4;
+    });
+
+    prj.add_test(
+        "InvariantThreads.t.sol",
+        r#"
+import "forge-std/Test.sol";
+
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number++;
+    }
+}
+
+contract InvariantThreads is Test {
+    Counter public counter;
+
+    function setUp() public {
+        counter = new Counter();
+    }
+
+    function invariant_bounded() public view {
+        require(counter.number() <= 10, "unbounded");
+    }
+
+    function invariant_never_five() public view {
+        require(counter.number() != 5, "five");
+    }
+}
+"#,
+    );
+
+    // Runs are split between workers and merged into a single result.
+    cmd.args(["test"]).assert_failure().stdout_eq(str![[r#"
+...
+Ran 2 tests for test/InvariantThreads.t.sol:InvariantThreads
+[PASS] invariant_bounded() (runs: 50, calls: 500, reverts: 0)
+...
+[FAIL: five]
+	[Sequence] (original: 5, shrunk: 5)
+...
+ invariant_never_five() ([..])
+...
+"#]]);
+});
+
+// Emits a regression test replaying the shrunk sequence of a failing invariant.
+forgetest_init!(invariant_emit_regressions, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.update_config(|config| {
+        config.invariant.depth = 10;
+    });
+
+    prj.add_test(
+        "InvariantRegression.t.sol",
+        r#"
+import "forge-std/Test.sol";
+
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number++;
+    }
+}
+
+contract InvariantRegression is Test {
+    Counter public counter;
+
+    function setUp() public {
+        counter = new Counter();
+    }
+
+    function invariant_below_three() public view {
+        require(counter.number() < 3, "three");
+    }
+}
+"#,
+    );
+
+    cmd.args(["test", "--emit-regressions", "test/regressions"]).assert_failure().stdout_eq(str![
+        [r#"
+...
+[FAIL: three]
+	[Sequence] (original: 3, shrunk: 3)
+...
+Wrote 1 regression tests:
+  [..]/test/regressions/test/InvariantRegression.t/InvariantRegression.invariant_below_three.t.sol
+
+"#]
+    ]);
+
+    assert_data_eq!(
+        std::fs::read_to_string(prj.root().join(
+            "test/regressions/test/InvariantRegression.t/InvariantRegression.invariant_below_three.t.sol"
+        ))
+        .unwrap(),
+        str![[r#"
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.6.2;
+
+import {Counter, InvariantRegression} from "test/InvariantRegression.t.sol";
+
+interface RegressionVm {
+    function computeCreateAddress(address deployer, uint256 nonce) external pure returns (address);
+    function deal(address account, uint256 newBalance) external;
+    function prank(address msgSender) external;
+    function roll(uint256 newHeight) external;
+    function warp(uint256 newTimestamp) external;
+}
+
+// Regression test for the failure of `test/InvariantRegression.t.sol:InvariantRegression::invariant_below_three`.
+// three
+contract InvariantRegression_invariant_below_three_Regression {
+    RegressionVm internal constant vm =
+        RegressionVm(0x7109709ECfa91a80626fF3989D68f67F5b1DD12D);
+
+    InvariantRegression internal harness;
+
+    function setUp() public {
+        harness = new InvariantRegression();
+        vm.deal(address(harness), address(this).balance);
+        harness.setUp();
+    }
+
+    function test_invariant_below_three_regression() public {
+        vm.prank([..]);
+        try Counter(vm.computeCreateAddress(address(harness), 1)).increment() {} catch {}
+        vm.prank([..]);
+        try Counter(vm.computeCreateAddress(address(harness), 1)).increment() {} catch {}
+        vm.prank([..]);
+        try Counter(vm.computeCreateAddress(address(harness), 1)).increment() {} catch {}
+        harness.invariant_below_three();
+    }
+}
+"#]]
+    );
+
+    // The regression test compiles, runs on its own and fails the same way.
+    cmd.forge_fuse()
+        .args(["test", "--match-path", "test/regressions/**"])
+        .assert_failure()
+        .stdout_eq(str![[r#"
+...
+Ran 1 test for [..]
+...
+[FAIL: three] test_invariant_below_three_regression() ([GAS])
+...
+"#]]);
+
+    // Regressions of replayed failures keep the reason the invariant was broken with.
+    cmd.forge_fuse()
+        .args([
+            "test",
+            "--match-path",
+            "test/InvariantRegression.t.sol",
+            "--emit-regressions",
+            "test/replayed",
+        ])
+        .assert_failure();
+    let replayed = std::fs::read_to_string(prj.root().join(
+        "test/replayed/test/InvariantRegression.t/InvariantRegression.invariant_below_three.t.sol",
+    ))
+    .unwrap();
+    assert!(replayed.contains("::invariant_below_three`.\n// three\n"), "{replayed}");
+});
+
+// The persisted corpus is replayed once for all workers, and corpus entries found by workers are
+// persisted.
+forgetest_init!(invariant_threads_share_corpus, |prj, cmd| {
+    prj.update_config(|config| {
+        config.invariant.runs = 50;
+        config.invariant.depth = 10;
+        config.invariant.threads = 4;
+        config.invariant.corpus.corpus_dir = Some("invariant_corpus".into());
+    });
+
+    prj.add_test(
+        "InvariantThreadsCorpus.t.sol",
+        r#"
+import "forge-std/Test.sol";
+
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number++;
+    }
+}
+
+contract InvariantThreadsCorpus is Test {
+    Counter public counter;
+
+    function setUp() public {
+        counter = new Counter();
+    }
+
+    function invariant_bounded() public view {
+        require(counter.number() <= 10, "unbounded");
+    }
+}
+"#,
+    );
+
+    // A persisted sequence of two calls that can't be replayed, as they target no contract.
+    let corpus_dir = prj
+        .root()
+        .join("invariant_corpus")
+        .join("InvariantThreadsCorpus")
+        .join("invariant_bounded");
+    std::fs::create_dir_all(&corpus_dir).unwrap();
+    let tx = r#"{"warp":null,"roll":null,"sender":"0x0000000000000000000000000000000000000001","call_details":{"target":"0x000000000000000000000000000000000000dead","calldata":"0x12345678"}}"#;
+    std::fs::write(
+        corpus_dir.join("5d3c1a3e-4d5b-4a0e-9d7c-2f1e8b6a9c01.json"),
+        format!("[{tx},{tx}]"),
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_success().stdout_eq(str![[r#"
+...
+[PASS] invariant_bounded() (runs: 50, calls: 500, reverts: [..], failed corpus replays: 2)
+...
+"#]]);
+
+    // Sequences with new coverage found by workers are persisted next to the loaded one.
+    assert!(std::fs::read_dir(&corpus_dir).unwrap().count() > 1);
+});
+
 forgetest_init!(invariant_test1, |prj, cmd| {
     prj.update_config(|config| {
         config.invariant.depth = 10;
@@ -4323,3 +4323,68 @@ struct SomeStruct {
 
 "#]]);
 });
+
+forgetest!(gas_profile, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "GasProfile.t.sol",
+        r#"
+import "./test.sol";
+
+contract Counter {
+    uint256 public number;
+
+    event Incremented(uint256 number);
+
+    function increment() external {
+        number++;
+        emit Incremented(number);
+    }
+}
+
+contract GasProfileTest is DSTest {
+    Counter counter;
+
+    function setUp() public {
+        counter = new Counter();
+    }
+
+    function test_increment() public {
+        counter.increment();
+        sha256("");
+    }
+}
+"#,
+    );
+
+    cmd.args(["test", "--gas-profile"]).assert_success().stdout_eq(str![[r#"
+...
+[PASS] test_increment() ([GAS])
+
+╭[..]╮
+| Category   | Name [..]| Count | Gas [..]|
++[..]+
+| Storage    | SLOAD (cold) [..]
+...
+| Storage    | SSTORE (warm) [..]
+| Logs       | LOG0-LOG4 [..]| 1 [..]
+| Precompile | SHA-256 [..]| 1 [..]| 60 [..]
+| Memory [..]
+| Calldata [..]
+...
+"#]]);
+
+    let stdout = cmd
+        .forge_fuse()
+        .args(["test", "--gas-profile", "--json"])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    assert!(stdout.contains(r#""gas_profile":{"opcodes":{"#), "{stdout}");
+    assert!(stdout.contains(r#""SHA-256":{"count":1,"gas":60}"#), "{stdout}");
+
+    // The profile is only collected when requested.
+    let stdout =
+        cmd.forge_fuse().args(["test", "--json"]).assert_success().get_output().stdout_lossy();
+    assert!(!stdout.contains("gas_profile"), "{stdout}");
+});
@@ -0,0 +1,136 @@
+use alloy_primitives::{B256, keccak256};
+use eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path, sync::Mutex};
+
+/// Default file name of a lint baseline.
+pub const DEFAULT_BASELINE_FILE: &str = "lint-baseline.json";
+
+/// A set of known findings, used to only report new ones.
+///
+/// Findings are keyed by lint ID, file and a fingerprint of the source code they point at, so
+/// they survive line shifts.
+#[derive(Debug)]
+pub struct LintBaseline {
+    mode: BaselineMode,
+    findings: Mutex<BTreeMap<FindingKey, usize>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BaselineMode {
+    /// Records every finding instead of reporting it.
+    Record,
+    /// Suppresses the recorded findings.
+    Suppress,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct FindingKey {
+    lint: String,
+    file: String,
+    fingerprint: B256,
+}
+
+/// On-disk representation of a [`LintBaseline`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BaselineFile {
+    findings: Vec<BaselineFinding>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFinding {
+    lint: String,
+    file: String,
+    fingerprint: B256,
+    /// Number of identical findings in the file.
+    #[serde(default = "default_count", skip_serializing_if = "is_one")]
+    count: usize,
+}
+
+impl LintBaseline {
+    /// Creates an empty baseline that records all findings.
+    pub fn record() -> Self {
+        Self { mode: BaselineMode::Record, findings: Default::default() }
+    }
+
+    /// Loads a baseline from `path`, suppressing the findings it contains.
+    pub fn load(path: &Path) -> Result<Self> {
+        let file: BaselineFile = foundry_common::fs::read_json_file(path)
+            .wrap_err_with(|| format!("failed to read lint baseline {}", path.display()))?;
+        let findings = file
+            .findings
+            .into_iter()
+            .map(|f| {
+                (FindingKey { lint: f.lint, file: f.file, fingerprint: f.fingerprint }, f.count)
+            })
+            .collect();
+        Ok(Self { mode: BaselineMode::Suppress, findings: Mutex::new(findings) })
+    }
+
+    /// Writes the findings to `path`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let findings = self.findings.lock().unwrap();
+        let file = BaselineFile {
+            findings: findings
+                .iter()
+                .map(|(key, &count)| BaselineFinding {
+                    lint: key.lint.clone(),
+                    file: key.file.clone(),
+                    fingerprint: key.fingerprint,
+                    count,
+                })
+                .collect(),
+        };
+        foundry_common::fs::write_pretty_json_file(path, &file)
+            .wrap_err_with(|| format!("failed to write lint baseline {}", path.display()))
+    }
+
+    /// Returns the total number of findings in the baseline.
+    pub fn len(&self) -> usize {
+        self.findings.lock().unwrap().values().sum()
+    }
+
+    /// Returns `true` if the baseline has no findings.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Processes a finding of `lint` in `file`, pointing at `snippet`.
+    ///
+    /// Returns `true` if the finding must be reported, i.e. it is not part of the baseline.
+    /// When recording, the finding is added to the baseline and never reported.
+    pub fn check(&self, lint: &str, file: &Path, snippet: &str) -> bool {
+        let key = FindingKey {
+            lint: lint.to_string(),
+            file: file.to_string_lossy().replace('\\', "/"),
+            fingerprint: fingerprint(snippet),
+        };
+        let mut findings = self.findings.lock().unwrap();
+        match self.mode {
+            BaselineMode::Record => {
+                *findings.entry(key).or_default() += 1;
+                false
+            }
+            BaselineMode::Suppress => match findings.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            },
+        }
+    }
+}
+
+/// Hashes the source code of a finding, ignoring whitespace differences.
+fn fingerprint(snippet: &str) -> B256 {
+    keccak256(snippet.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn default_count() -> usize {
+    1
+}
+
+fn is_one(count: &usize) -> bool {
+    *count == 1
+}
@@ -1,6 +1,8 @@
+mod baseline;
 mod early;
 mod late;
 
+pub use baseline::{DEFAULT_BASELINE_FILE, LintBaseline};
 pub use early::{EarlyLintPass, EarlyLintVisitor};
 pub use late::{LateLintPass, LateLintVisitor};
 
@@ -16,7 +18,7 @@
     },
     sema::Compiler,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Trait representing a generic linter for analyzing and reporting issues in smart contract source
 /// code files.
@@ -56,6 +58,10 @@ pub struct LintContext<'s, 'c> {
 pub struct LinterConfig<'s> {
     pub inline: &'s InlineConfig<Vec<String>>,
     pub mixed_case_exceptions: &'s [String],
+    /// Known findings that must not be reported.
+    pub baseline: Option<&'s LintBaseline>,
+    /// Path of the linted source, relative to the project root.
+    pub file: &'s Path,
 }
 
 impl<'s, 'c> LintContext<'s, 'c> {
@@ -81,9 +87,23 @@ pub fn is_lint_enabled(&self, id: &'static str) -> bool {
         self.active_lints.contains(&id)
     }
 
+    // Helper method to check if a finding must be reported.
+    //
+    // Findings are not reported if the lint is disabled, either globally or inline, or if they are
+    // part of the baseline.
+    fn should_emit(&self, id: &'static str, span: Span) -> bool {
+        if self.config.inline.is_id_disabled(span, id) || !self.is_lint_enabled(id) {
+            return false;
+        }
+
+        self.config.baseline.is_none_or(|baseline| {
+            baseline.check(id, self.config.file, &self.span_to_snippet(span).unwrap_or_default())
+        })
+    }
+
     /// Helper method to emit diagnostics easily from passes
     pub fn emit<L: Lint>(&self, lint: &'static L, span: Span) {
-        if self.config.inline.is_id_disabled(span, lint.id()) || !self.is_lint_enabled(lint.id()) {
+        if !self.should_emit(lint.id(), span) {
             return;
         }
 
@@ -95,11 +115,13 @@ pub fn emit<L: Lint>(&self, lint: &'static L, span: Span) {
             .code(DiagId::new_str(lint.id()))
             .span(MultiSpan::from_span(span));
 
-        // Avoid ANSI characters when using a JSON emitter
-        if self.with_json_emitter {
-            diag = diag.help(lint.help());
-        } else {
-            diag = diag.help(hyperlink(lint.help()));
+        // Avoid ANSI characters when using a JSON emitter. User-defined lints may have no help.
+        if !lint.help().is_empty() {
+            diag = if self.with_json_emitter {
+                diag.help(lint.help())
+            } else {
+                diag.help(hyperlink(lint.help()))
+            };
         }
 
         diag.emit();
@@ -114,7 +136,7 @@ pub fn emit_with_suggestion<L: Lint>(
         span: Span,
         suggestion: Suggestion,
     ) {
-        if self.config.inline.is_id_disabled(span, lint.id()) || !self.is_lint_enabled(lint.id()) {
+        if !self.should_emit(lint.id(), span) {
             return;
         }
 
@@ -144,11 +166,13 @@ pub fn emit_with_suggestion<L: Lint>(
             }
         };
 
-        // Avoid ANSI characters when using a JSON emitter
-        if self.with_json_emitter {
-            diag = diag.help(lint.help());
-        } else {
-            diag = diag.help(hyperlink(lint.help()));
+        // Avoid ANSI characters when using a JSON emitter. User-defined lints may have no help.
+        if !lint.help().is_empty() {
+            diag = if self.with_json_emitter {
+                diag.help(lint.help())
+            } else {
+                diag.help(hyperlink(lint.help()))
+            };
         }
 
         diag.emit();
@@ -0,0 +1,340 @@
+//! User-defined lint rules, declared in the project config rather than in this crate.
+
+use crate::{
+    linter::{EarlyLintPass, Lint, LintContext},
+    sol::{ALL_REGISTERED_LINTS, SolLint, SolLintError},
+};
+use foundry_config::{
+    filter::GlobMatcher,
+    lint::{CustomLintPattern, CustomLintRule},
+};
+use semver::{Version, VersionReq};
+use solar::ast::{self as ast, Visibility};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+/// The set of user-defined lint rules, evaluated by a single early lint pass.
+///
+/// Cheap to clone.
+#[derive(Clone, Debug, Default)]
+pub struct CustomLints {
+    /// The lint metadata of each rule, indexed like `rules`.
+    lints: &'static [SolLint],
+    rules: Arc<[Rule]>,
+}
+
+#[derive(Debug)]
+enum Rule {
+    ForbiddenCall(Vec<Vec<String>>),
+    ForbiddenImport(Vec<GlobMatcher>),
+    ForbiddenPragma(Vec<Version>),
+    RequireNatspec { visibility: Vec<Visibility>, tags: Vec<String> },
+}
+
+impl CustomLints {
+    /// Validates and compiles the given rules.
+    pub fn new(rules: &[CustomLintRule]) -> Result<Self, SolLintError> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        for (i, rule) in rules.iter().enumerate() {
+            if ALL_REGISTERED_LINTS.iter().any(|id| *id == rule.id)
+                || rules[..i].iter().any(|other| other.id == rule.id)
+            {
+                return Err(SolLintError::DuplicateId(rule.id.clone()));
+            }
+
+            let invalid = |reason: String| SolLintError::InvalidRule(rule.id.clone(), reason);
+            compiled.push(match &rule.pattern {
+                CustomLintPattern::ForbiddenCall { functions } => Rule::ForbiddenCall(
+                    functions
+                        .iter()
+                        .map(|f| f.split('.').rev().map(str::to_string).collect())
+                        .collect(),
+                ),
+                CustomLintPattern::ForbiddenImport { paths } => Rule::ForbiddenImport(
+                    paths
+                        .iter()
+                        .map(|p| p.parse::<GlobMatcher>().map_err(|e| invalid(e.to_string())))
+                        .collect::<Result<_, _>>()?,
+                ),
+                CustomLintPattern::ForbiddenPragma { versions } => {
+                    Rule::ForbiddenPragma(versions.clone())
+                }
+                CustomLintPattern::RequireNatspec { visibility, tags } => Rule::RequireNatspec {
+                    visibility: visibility
+                        .iter()
+                        .map(|v| {
+                            parse_visibility(v)
+                                .ok_or_else(|| invalid(format!("unknown visibility `{v}`")))
+                        })
+                        .collect::<Result<_, _>>()?,
+                    tags: tags.iter().map(|t| t.trim_start_matches('@').to_string()).collect(),
+                },
+            });
+        }
+
+        Ok(Self { lints: intern_lints(rules), rules: compiled.into() })
+    }
+
+    /// Returns the lints declared by the rules.
+    pub fn lints(&self) -> &'static [SolLint] {
+        self.lints
+    }
+
+    /// Returns the lint with the given ID, if declared by one of the rules.
+    pub fn get(&self, id: &str) -> Option<SolLint> {
+        self.lints.iter().find(|lint| lint.id() == id).copied()
+    }
+
+    /// Returns `true` if no rules are declared.
+    pub fn is_empty(&self) -> bool {
+        self.lints.is_empty()
+    }
+
+    fn rules(&self) -> impl Iterator<Item = (&'static SolLint, &Rule)> {
+        self.lints.iter().zip(self.rules.iter())
+    }
+}
+
+impl<'ast> EarlyLintPass<'ast> for CustomLints {
+    fn check_expr(&mut self, ctx: &LintContext, expr: &'ast ast::Expr<'ast>) {
+        let ast::ExprKind::Call(callee, _) = &expr.kind else { return };
+        let path = callee_path(callee);
+        for (lint, rule) in self.rules() {
+            if let Rule::ForbiddenCall(functions) = rule
+                && functions.iter().any(|f| path_matches(&path, f))
+            {
+                ctx.emit(lint, expr.span);
+            }
+        }
+    }
+
+    fn check_import_directive(
+        &mut self,
+        ctx: &LintContext,
+        import: &'ast ast::ImportDirective<'ast>,
+    ) {
+        let path = Path::new(import.path.value.as_str());
+        for (lint, rule) in self.rules() {
+            if let Rule::ForbiddenImport(globs) = rule
+                && globs.iter().any(|glob| glob.is_match(path))
+            {
+                ctx.emit(lint, import.path.span);
+            }
+        }
+    }
+
+    fn check_full_source_unit(
+        &mut self,
+        ctx: &LintContext<'ast, '_>,
+        ast: &'ast ast::SourceUnit<'ast>,
+    ) {
+        for item in ast.items.iter() {
+            match &item.kind {
+                ast::ItemKind::Pragma(pragma) => self.check_pragma(ctx, pragma, item.span),
+                ast::ItemKind::Function(_) => self.check_natspec(ctx, item),
+                ast::ItemKind::Contract(contract) => {
+                    for item in contract.body.iter() {
+                        self.check_natspec(ctx, item);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl CustomLints {
+    fn check_pragma(&self, ctx: &LintContext, pragma: &ast::PragmaDirective<'_>, span: ast::Span) {
+        let ast::PragmaTokens::Version(ident, req) = &pragma.tokens else { return };
+        if ident.as_str() != "solidity" {
+            return;
+        }
+
+        let req = req.to_string();
+        for (lint, rule) in self.rules() {
+            if let Rule::ForbiddenPragma(versions) = rule
+                && versions.iter().any(|version| pragma_allows(&req, version))
+            {
+                ctx.emit(lint, span);
+            }
+        }
+    }
+
+    fn check_natspec(&self, ctx: &LintContext, item: &ast::Item<'_>) {
+        let ast::ItemKind::Function(func) = &item.kind else { return };
+        if !func.kind.is_function() {
+            return;
+        }
+        let Some(visibility) = func.header.visibility() else { return };
+
+        let docs = item.docs.iter().map(|doc| doc.symbol.as_str()).collect::<Vec<_>>().join("\n");
+        let tags = natspec_tags(&docs);
+        if tags.iter().any(|(tag, _)| *tag == "inheritdoc") {
+            return;
+        }
+
+        let span = func.header.name.map_or(item.span, |name| name.span);
+        for (lint, rule) in self.rules() {
+            let Rule::RequireNatspec { visibility: visibilities, tags: required } = rule else {
+                continue;
+            };
+            if !visibilities.contains(&visibility) {
+                continue;
+            }
+
+            let missing = required.iter().any(|required| match required.as_str() {
+                "param" => func.header.parameters.iter().filter_map(|p| p.name).any(|name| {
+                    !tags.iter().any(|(tag, content)| {
+                        *tag == "param" && content.split_whitespace().next() == Some(name.as_str())
+                    })
+                }),
+                "return" => {
+                    tags.iter().filter(|(tag, _)| *tag == "return").count()
+                        < func.header.returns().len()
+                }
+                tag => !tags.iter().any(|(t, _)| *t == tag),
+            });
+            if missing {
+                ctx.emit(lint, span);
+            }
+        }
+    }
+}
+
+/// Returns the `(tag, content)` pairs of a NatSpec comment.
+///
+/// Text before the first tag is an implicit `@notice`.
+fn natspec_tags(docs: &str) -> Vec<(&str, &str)> {
+    let mut tags = Vec::new();
+    for line in docs.lines() {
+        let line = line.trim().trim_start_matches('*').trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(tagged) = line.strip_prefix('@') {
+            let (tag, content) = tagged.split_once(char::is_whitespace).unwrap_or((tagged, ""));
+            tags.push((tag, content.trim()));
+        } else if tags.is_empty() {
+            tags.push(("notice", line));
+        }
+    }
+    tags
+}
+
+/// Returns the `.`-separated path of a callee, from its last segment to its first.
+///
+/// Segments that are not identifiers, such as the call in `f().g`, are `None`.
+fn callee_path<'a>(mut expr: &'a ast::Expr<'_>) -> Vec<Option<&'a str>> {
+    if let ast::ExprKind::CallOptions(inner, _) = &expr.kind {
+        expr = inner;
+    }
+
+    let mut path = Vec::new();
+    loop {
+        match &expr.kind {
+            ast::ExprKind::Member(base, member) => {
+                path.push(Some(member.as_str()));
+                expr = base;
+            }
+            ast::ExprKind::Ident(ident) => {
+                path.push(Some(ident.as_str()));
+                break;
+            }
+            _ => {
+                path.push(None);
+                break;
+            }
+        }
+    }
+    path
+}
+
+/// Returns `true` if the end of the callee `path` matches `pattern`, both in reverse order.
+fn path_matches(path: &[Option<&str>], pattern: &[String]) -> bool {
+    pattern.len() <= path.len()
+        && pattern
+            .iter()
+            .zip(path)
+            .all(|(expected, segment)| expected == "*" || *segment == Some(expected.as_str()))
+}
+
+/// Returns `true` if the Solidity version requirement `req` allows `version`.
+///
+/// Solidity separates comparators with whitespace, supports `a - b` ranges and `||`, and treats
+/// bare versions as exact.
+fn pragma_allows(req: &str, version: &Version) -> bool {
+    req.split("||").any(|range| {
+        let comparators = match range.split_once(" - ") {
+            Some((lo, hi)) => vec![format!(">={}", lo.trim()), format!("<={}", hi.trim())],
+            None => {
+                let mut comparators: Vec<String> = Vec::new();
+                let mut pending_op = String::new();
+                for token in range.split_whitespace() {
+                    if token.chars().all(|c| "^~<>=".contains(c)) {
+                        pending_op.push_str(token);
+                        continue;
+                    }
+                    let token = std::mem::take(&mut pending_op) + token;
+                    if token.starts_with(|c: char| c.is_ascii_digit()) {
+                        comparators.push(format!("={token}"));
+                    } else {
+                        comparators.push(token);
+                    }
+                }
+                comparators
+            }
+        };
+        !comparators.is_empty()
+            && VersionReq::parse(&comparators.join(", ")).is_ok_and(|req| req.matches(version))
+    })
+}
+
+fn parse_visibility(s: &str) -> Option<Visibility> {
+    match s {
+        "external" => Some(Visibility::External),
+        "public" => Some(Visibility::Public),
+        "internal" => Some(Visibility::Internal),
+        "private" => Some(Visibility::Private),
+        _ => None,
+    }
+}
+
+/// Returns the lint metadata of the given rules, leaking it the first time it is seen, as
+/// diagnostics require `'static` lints.
+///
+/// Interning keeps the leaked memory bounded when the same rules are compiled repeatedly, e.g. on
+/// each run in watch mode.
+fn intern_lints(rules: &[CustomLintRule]) -> &'static [SolLint] {
+    static INTERNED: OnceLock<Mutex<Vec<&'static [SolLint]>>> = OnceLock::new();
+
+    let is_rule = |lint: &SolLint, rule: &CustomLintRule| {
+        lint.id == rule.id
+            && lint.severity == rule.severity
+            && lint.description == rule.description
+            && lint.help == rule.help.as_deref().unwrap_or_default()
+    };
+    let mut interned = INTERNED.get_or_init(Default::default).lock().unwrap();
+    if let Some(lints) = interned.iter().copied().find(|lints| {
+        lints.len() == rules.len()
+            && lints.iter().zip(rules).all(|(lint, rule)| is_rule(lint, rule))
+    }) {
+        return lints;
+    }
+
+    let leak = |s: &str| -> &'static str { Box::leak(Box::<str>::from(s)) };
+    let lints: &'static [SolLint] = Box::leak(
+        rules
+            .iter()
+            .map(|rule| SolLint {
+                id: leak(&rule.id),
+                severity: rule.severity,
+                description: leak(&rule.description),
+                help: rule.help.as_deref().map(leak).unwrap_or_default(),
+            })
+            .collect::<Box<[_]>>(),
+    );
+    interned.push(lints);
+    lints
+}
@@ -1,6 +1,6 @@
 use crate::linter::{
-    EarlyLintPass, EarlyLintVisitor, LateLintPass, LateLintVisitor, Lint, LintContext, Linter,
-    LinterConfig,
+    EarlyLintPass, EarlyLintVisitor, LateLintPass, LateLintVisitor, Lint, LintBaseline,
+    LintContext, Linter, LinterConfig,
 };
 use foundry_common::{
     comments::{
@@ -30,10 +30,13 @@
 };
 use thiserror::Error;
 
+use custom::CustomLints;
+
 #[macro_use]
 pub mod macros;
 
 pub mod codesize;
+pub mod custom;
 pub mod gas;
 pub mod high;
 pub mod info;
@@ -59,6 +62,10 @@ pub struct SolidityLinter<'a> {
     lints_excluded: Option<Vec<SolLint>>,
     with_description: bool,
     with_json_emitter: bool,
+    // user-defined lints
+    custom_lints: CustomLints,
+    // known findings that must not be reported
+    baseline: Option<&'a LintBaseline>,
     // lint-specific configuration
     mixed_case_exceptions: &'a [String],
 }
@@ -72,6 +79,8 @@ pub fn new(path_config: ProjectPathsConfig) -> Self {
             lints_included: None,
             lints_excluded: None,
             with_json_emitter: false,
+            custom_lints: CustomLints::default(),
+            baseline: None,
             mixed_case_exceptions: &[],
         }
     }
@@ -101,13 +110,28 @@ pub fn with_json_emitter(mut self, with: bool) -> Self {
         self
     }
 
+    pub fn with_custom_lints(mut self, lints: CustomLints) -> Self {
+        self.custom_lints = lints;
+        self
+    }
+
+    pub fn with_baseline(mut self, baseline: Option<&'a LintBaseline>) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
     pub fn with_mixed_case_exceptions(mut self, exceptions: &'a [String]) -> Self {
         self.mixed_case_exceptions = exceptions;
         self
     }
 
-    fn config(&'a self, inline: &'a InlineConfig<Vec<String>>) -> LinterConfig<'a> {
-        LinterConfig { inline, mixed_case_exceptions: self.mixed_case_exceptions }
+    fn config(&'a self, inline: &'a InlineConfig<Vec<String>>, path: &'a Path) -> LinterConfig<'a> {
+        LinterConfig {
+            inline,
+            mixed_case_exceptions: self.mixed_case_exceptions,
+            baseline: self.baseline,
+            file: path.strip_prefix(&self.path_config.root).unwrap_or(path),
+        }
     }
 
     fn include_lint(&self, lint: SolLint) -> bool {
@@ -135,6 +159,14 @@ fn process_source_ast<'gcx>(
             passes_and_lints.extend(codesize::create_early_lint_passes());
         }
 
+        // User-defined lints apply to all sources
+        if !self.custom_lints.is_empty() {
+            passes_and_lints.push((
+                Box::new(self.custom_lints.clone()) as Box<dyn EarlyLintPass<'_>>,
+                self.custom_lints.lints(),
+            ));
+        }
+
         // Filter passes based on linter config
         let (mut passes, lints): (Vec<Box<dyn EarlyLintPass<'_>>>, Vec<_>) = passes_and_lints
             .into_iter()
@@ -157,7 +189,7 @@ fn process_source_ast<'gcx>(
             sess,
             self.with_description,
             self.with_json_emitter,
-            self.config(inline_config),
+            self.config(inline_config, path),
             lints,
         );
         let mut early_visitor = EarlyLintVisitor::new(&ctx, &mut passes);
@@ -208,7 +240,7 @@ fn process_source_hir<'gcx>(
             gcx.sess,
             self.with_description,
             self.with_json_emitter,
-            self.config(inline_config),
+            self.config(inline_config, path),
             lints,
         );
         let mut late_visitor = LateLintVisitor::new(&ctx, &mut passes, &gcx.hir);
@@ -256,6 +288,12 @@ fn lint(
 
             let gcx = compiler.gcx();
 
+            let lint_ids = ALL_REGISTERED_LINTS
+                .iter()
+                .copied()
+                .chain(self.custom_lints.lints().iter().map(|lint| lint.id))
+                .collect::<Vec<_>>();
+
             input.par_iter().for_each(|path| {
                 let path = &self.path_config.root.join(path);
                 let Some((_, ast_source)) = gcx.get_ast_source(path) else {
@@ -271,7 +309,7 @@ fn lint(
                 // Parse inline config.
                 let file = &ast_source.file;
                 let comments = Comments::new(file, gcx.sess.source_map(), false, false, None);
-                let inline_config = parse_inline_config(gcx.sess, &comments, ast);
+                let inline_config = parse_inline_config(gcx.sess, &comments, ast, &lint_ids);
 
                 // Early lints.
                 let _ = self.process_source_ast(gcx.sess, ast, path, &inline_config);
@@ -325,6 +363,7 @@ fn parse_inline_config<'ast>(
     sess: &Session,
     comments: &Comments,
     ast: &'ast ast::SourceUnit<'ast>,
+    lint_ids: &[&str],
 ) -> InlineConfig<Vec<String>> {
     let items = comments.iter().filter_map(|comment| {
         let mut item = comment.lines.first()?.as_str();
@@ -336,7 +375,7 @@ fn parse_inline_config<'ast>(
         }
         let item = item.trim_start().strip_prefix("forge-lint:")?.trim();
         let span = comment.span;
-        match InlineConfigItem::parse(item, &ALL_REGISTERED_LINTS) {
+        match InlineConfigItem::parse(item, lint_ids) {
             Ok(item) => Some((span, item)),
             Err(e) => {
                 sess.dcx.warn(e.to_string()).span(span).emit();
@@ -352,6 +391,10 @@ fn parse_inline_config<'ast>(
 pub enum SolLintError {
     #[error("Unknown lint ID: {0}")]
     InvalidId(String),
+    #[error("Duplicate lint ID: {0}")]
+    DuplicateId(String),
+    #[error("Invalid lint rule `{0}`: {1}")]
+    InvalidRule(String, String),
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
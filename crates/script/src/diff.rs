@@ -0,0 +1,289 @@
+//! Diffing of simulated script transactions against a recorded broadcast.
+
+use alloy_primitives::hex;
+use eyre::{Result, WrapErr};
+use forge_script_sequence::{ScriptSequence, TransactionWithMetadata};
+use std::{fmt, path::Path};
+use yansi::Paint;
+
+/// A difference between the recorded and the simulated transactions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TransactionDiff {
+    /// A simulated transaction that was not recorded.
+    Added { index: usize, summary: String },
+    /// A recorded transaction that was not simulated.
+    Removed { index: usize, summary: String },
+    /// A transaction that was both recorded and simulated, with different fields.
+    Changed { index: usize, summary: String, changes: Vec<String> },
+}
+
+impl fmt::Display for TransactionDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { index, summary } => {
+                write!(f, "{}", format!("+ Transaction {index}: {summary}").green())
+            }
+            Self::Removed { index, summary } => {
+                write!(f, "{}", format!("- Transaction {index}: {summary}").red())
+            }
+            Self::Changed { index, summary, changes } => {
+                write!(f, "{}", format!("~ Transaction {index}: {summary}").yellow())?;
+                for change in changes {
+                    write!(f, "\n    {change}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Compares the simulated sequences against the recorded broadcast at `path`.
+///
+/// Returns an error if the transactions of the recorded chain differ.
+pub fn diff_against(path: &Path, simulated: &[ScriptSequence]) -> Result<()> {
+    let recorded: ScriptSequence = foundry_common::fs::read_json_file(path)
+        .wrap_err_with(|| format!("failed to read broadcast `{}`", path.display()))?;
+    let simulated = simulated
+        .iter()
+        .find(|sequence| sequence.chain == recorded.chain)
+        .map(|sequence| sequence.transactions.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let diffs = diff_transactions(&recorded.transactions.iter().collect::<Vec<_>>(), &simulated);
+    if diffs.is_empty() {
+        sh_println!("\nNo differences against {} (chain {}).", path.display(), recorded.chain)?;
+        return Ok(());
+    }
+
+    sh_println!("\nDifferences against {} (chain {}):\n", path.display(), recorded.chain)?;
+    for diff in &diffs {
+        sh_println!("{diff}")?;
+    }
+    eyre::bail!("found {} differing transactions against the recorded broadcast", diffs.len())
+}
+
+/// Aligns the recorded and simulated transactions on their longest common subsequence of
+/// [`alignment_key`]s, then compares the aligned pairs.
+fn diff_transactions(
+    recorded: &[&TransactionWithMetadata],
+    simulated: &[&TransactionWithMetadata],
+) -> Vec<TransactionDiff> {
+    let (n, m) = (recorded.len(), simulated.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if alignment_key(recorded[i]) == alignment_key(simulated[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diffs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && alignment_key(recorded[i]) == alignment_key(simulated[j]) {
+            let changes = diff_transaction(recorded[i], simulated[j]);
+            if !changes.is_empty() {
+                diffs.push(TransactionDiff::Changed {
+                    index: j + 1,
+                    summary: summary(simulated[j]),
+                    changes,
+                });
+            }
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diffs.push(TransactionDiff::Added { index: j + 1, summary: summary(simulated[j]) });
+            j += 1;
+        } else {
+            diffs.push(TransactionDiff::Removed { index: i + 1, summary: summary(recorded[i]) });
+            i += 1;
+        }
+    }
+    diffs
+}
+
+/// Transactions with the same kind, contract and function are considered the same transaction.
+fn alignment_key(tx: &TransactionWithMetadata) -> (&'static str, Option<&str>, Option<&str>) {
+    (kind(tx), non_empty(&tx.contract_name), non_empty(&tx.function))
+}
+
+/// Returns the differences between the fields of two aligned transactions.
+fn diff_transaction(
+    recorded: &TransactionWithMetadata,
+    simulated: &TransactionWithMetadata,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    let (old, new) = (recorded.tx(), simulated.tx());
+    push_change(&mut changes, "from", display(old.from()), display(new.from()));
+    push_change(
+        &mut changes,
+        "address",
+        display(recorded.contract_address),
+        display(simulated.contract_address),
+    );
+    push_change(&mut changes, "nonce", display(old.nonce()), display(new.nonce()));
+    push_change(&mut changes, "value", display(old.value()), display(new.value()));
+
+    let (old_input, new_input) =
+        (old.input().cloned().unwrap_or_default(), new.input().cloned().unwrap_or_default());
+    if recorded.opcode.is_any_create() {
+        if old_input != new_input {
+            changes.push(format!(
+                "init code: changed ({} → {} bytes)",
+                old_input.len(),
+                new_input.len()
+            ));
+        }
+    } else if let (Some(old_args), Some(new_args)) = (&recorded.arguments, &simulated.arguments)
+        && non_empty(&recorded.function).is_some()
+    {
+        if old_args.len() == new_args.len() {
+            for (i, (old, new)) in old_args.iter().zip(new_args).enumerate() {
+                push_change(&mut changes, &format!("argument {i}"), old.clone(), new.clone());
+            }
+        } else {
+            push_change(
+                &mut changes,
+                "arguments",
+                format!("({})", old_args.join(", ")),
+                format!("({})", new_args.join(", ")),
+            );
+        }
+    } else {
+        push_change(
+            &mut changes,
+            "calldata",
+            hex::encode_prefixed(&old_input),
+            hex::encode_prefixed(&new_input),
+        );
+    }
+    changes
+}
+
+fn push_change(changes: &mut Vec<String>, field: &str, old: String, new: String) {
+    if old != new {
+        changes.push(format!("{field}: {old} → {new}"));
+    }
+}
+
+/// Returns a one-line description of a transaction, e.g. `CALL Counter::setNumber(uint256)`.
+fn summary(tx: &TransactionWithMetadata) -> String {
+    let target = match (non_empty(&tx.contract_name), tx.contract_address) {
+        (Some(name), _) => name.to_string(),
+        (None, Some(address)) => address.to_string(),
+        (None, None) => "<unknown>".to_string(),
+    };
+    match non_empty(&tx.function) {
+        Some(function) => format!("{} {target}::{function}", kind(tx)),
+        None => format!("{} {target}", kind(tx)),
+    }
+}
+
+fn kind(tx: &TransactionWithMetadata) -> &'static str {
+    if tx.is_create2() {
+        "CREATE2"
+    } else if tx.opcode.is_any_create() {
+        "CREATE"
+    } else {
+        "CALL"
+    }
+}
+
+/// Recorded broadcasts default missing strings to empty ones.
+fn non_empty(s: &Option<String>) -> Option<&str> {
+    s.as_deref().filter(|s| !s.is_empty())
+}
+
+fn display<T: fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "none".to_string(), |value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(
+        kind: &str,
+        contract: &str,
+        function: Option<&str>,
+        args: &[&str],
+        nonce: u64,
+    ) -> TransactionWithMetadata {
+        serde_json::from_value(serde_json::json!({
+            "hash": null,
+            "transactionType": kind,
+            "contractName": contract,
+            "contractAddress": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+            "function": function,
+            "arguments": args,
+            "transaction": {
+                "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "to": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+                "value": "0x0",
+                "input": "0x",
+                "nonce": format!("{nonce:#x}"),
+                "chainId": "0x7a69"
+            },
+            "additionalContracts": [],
+            "isFixedGasLimit": false
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn diffs_transactions() {
+        let recorded = [
+            tx("CREATE", "Counter", None, &[], 0),
+            tx("CALL", "Counter", Some("setNumber(uint256)"), &["1"], 1),
+            tx("CALL", "Counter", Some("increment()"), &[], 2),
+        ];
+        let simulated = [
+            tx("CREATE", "Counter", None, &[], 0),
+            tx("CALL", "Counter", Some("setNumber(uint256)"), &["2"], 1),
+            tx("CALL", "Counter", Some("reset()"), &[], 2),
+        ];
+
+        let diffs = diff_transactions(
+            &recorded.iter().collect::<Vec<_>>(),
+            &simulated.iter().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            diffs,
+            [
+                TransactionDiff::Changed {
+                    index: 2,
+                    summary: "CALL Counter::setNumber(uint256)".to_string(),
+                    changes: vec!["argument 0: 1 → 2".to_string()],
+                },
+                TransactionDiff::Added { index: 3, summary: "CALL Counter::reset()".to_string() },
+                TransactionDiff::Removed {
+                    index: 3,
+                    summary: "CALL Counter::increment()".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_nonces() {
+        let recorded = [tx("CALL", "Counter", Some("increment()"), &[], 2)];
+        let simulated = [tx("CALL", "Counter", Some("increment()"), &[], 5)];
+
+        let diffs = diff_transactions(
+            &recorded.iter().collect::<Vec<_>>(),
+            &simulated.iter().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            diffs,
+            [TransactionDiff::Changed {
+                index: 1,
+                summary: "CALL Counter::increment()".to_string(),
+                changes: vec!["nonce: 2 → 5".to_string()],
+            }]
+        );
+        assert!(diff_transactions(&[&recorded[0]], &[&recorded[0]]).is_empty());
+    }
+}
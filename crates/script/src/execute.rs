@@ -1,13 +1,13 @@
 use super::{JsonResult, NestedValue, ScriptResult, runner::ScriptRunner};
 use crate::{
-    ScriptArgs, ScriptConfig,
+    ScriptArgs, ScriptConfig, ScriptState,
     build::{CompiledState, LinkedBuildData},
     simulate::PreSimulationState,
 };
 use alloy_dyn_abi::FunctionExt;
 use alloy_json_abi::{Function, InternalType, JsonAbi};
 use alloy_primitives::{
-    Address, Bytes,
+    Address, Bytes, U256,
     map::{HashMap, HashSet},
 };
 use alloy_provider::Provider;
@@ -20,9 +20,10 @@
     provider::get_http_provider,
 };
 use foundry_config::NamedChain;
-use foundry_debugger::Debugger;
+use foundry_debugger::{DapTransport, Debugger};
 use foundry_evm::{
     decode::decode_console_logs,
+    executors::RawCallResult,
     inspectors::cheatcodes::BroadcastableTransactions,
     traces::{
         CallTraceDecoder, CallTraceDecoderBuilder, TraceKind, decode_trace_arena,
@@ -32,7 +33,7 @@
 };
 use futures::future::join_all;
 use itertools::Itertools;
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 use yansi::Paint;
 
 /// State after linking, contains the linked build data along with library addresses and optional
@@ -172,6 +173,55 @@ pub async fn execute_with_runner(&self, runner: &mut ScriptRunner) -> Result<Scr
         Ok(setup_result)
     }
 
+    /// Executes the script and commits its state changes, returning the state after execution.
+    ///
+    /// Unlike [Self::execute], the changes of the script function are kept in the backend, so that
+    /// it can be used as the starting point of another session, e.g. in `chisel`.
+    pub async fn execute_and_commit(mut self) -> Result<ScriptState> {
+        let mut runner = self
+            .script_config
+            .get_runner_with_cheatcodes(
+                self.build_data.known_contracts.clone(),
+                self.script_wallets.clone(),
+                false,
+                self.build_data.build_data.target.clone(),
+            )
+            .await?;
+        let (address, mut result) = runner.setup(
+            &self.build_data.predeploy_libraries,
+            self.execution_data.bytecode.clone(),
+            needs_setup(&self.execution_data.abi),
+            &self.script_config,
+            false,
+        )?;
+
+        if result.success {
+            // Commit the script call once, without searching for its gas limit, which would run
+            // the script again for each step of the search.
+            let RawCallResult {
+                result: returned, reverted, gas_used, logs, traces, labels, ..
+            } = runner.executor.transact_raw(
+                self.script_config.evm_opts.sender,
+                address,
+                self.execution_data.calldata.clone(),
+                U256::ZERO,
+            )?;
+
+            result.success &= !reverted;
+            result.gas_used = gas_used;
+            result.logs.extend(logs);
+            result.traces.extend(traces.map(|traces| (TraceKind::Execution, traces)));
+            result.labeled_addresses.extend(labels);
+            result.returned = returned;
+        }
+
+        Ok(ScriptState {
+            result,
+            backend: runner.executor.backend().clone(),
+            known_contracts: self.build_data.known_contracts,
+        })
+    }
+
     /// It finds the deployer from the running script and uses it to predeploy libraries.
     ///
     /// If there are multiple candidate addresses, it skips everything and lets `--sender` deploy
@@ -499,6 +549,11 @@ pub fn run_debugger(self) -> Result<()> {
         Ok(())
     }
 
+    pub fn run_dap(self, transport: DapTransport) -> Result<()> {
+        let root = self.script_config.config.root.clone();
+        self.create_debugger().run_dap(transport, &root)
+    }
+
     pub fn dump_debugger(self, path: &Path) -> Result<()> {
         self.create_debugger().dump_to_file(path)?;
         Ok(())
@@ -514,6 +569,9 @@ fn create_debugger(self) -> Debugger {
                     .collect(),
             )
             .decoder(&self.execution_artifacts.decoder)
+            .storage_layouts(self.build_data.build_data.output.artifact_ids().filter_map(
+                |(id, artifact)| Some((id.name, Arc::new(artifact.storage_layout.clone()?))),
+            ))
             .sources(self.build_data.sources)
             .breakpoints(self.execution_result.breakpoints)
             .build()
@@ -36,7 +36,7 @@
     provider::tempo::TempoRetryProvider,
     shell,
 };
-use foundry_compilers::ArtifactId;
+use foundry_compilers::{ArtifactId, artifacts::output_selection::ContractOutputSelection};
 use foundry_config::{
     Config, figment,
     figment::{
@@ -44,6 +44,7 @@
         value::{Dict, Map},
     },
 };
+use foundry_debugger::DapTransport;
 use foundry_evm::{
     backend::Backend,
     core::Breakpoints,
@@ -61,6 +62,7 @@
 
 mod broadcast;
 mod build;
+mod diff;
 mod execute;
 mod multi_sequence;
 mod progress;
@@ -193,6 +195,34 @@ pub struct ScriptArgs {
     )]
     pub dump: Option<PathBuf>,
 
+    /// Serve the debugger over the Debug Adapter Protocol instead of opening the TUI.
+    ///
+    /// Messages are exchanged over stdio by default, or over a TCP connection accepted on the
+    /// given address, e.g. `--dap=127.0.0.1:4711`.
+    #[arg(
+        long,
+        requires = "debug",
+        conflicts_with = "dump",
+        value_name = "ADDRESS",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "stdio"
+    )]
+    pub dap: Option<DapTransport>,
+
+    /// Compares the simulated transactions against a recorded broadcast, e.g.
+    /// `broadcast/Deploy.s.sol/1/run-latest.json`, instead of broadcasting them.
+    ///
+    /// Reports added and removed transactions, and changed calldata, deploy bytecode and nonces.
+    /// Exits with a non-zero status code if there are differences.
+    #[arg(
+        long,
+        conflicts_with_all = &["broadcast", "resume", "debug"],
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH"
+    )]
+    pub diff_against: Option<PathBuf>,
+
     /// Makes sure a transaction is sent,
     /// only after its previous one has been confirmed and succeeded.
     #[arg(long)]
@@ -256,7 +286,12 @@ impl ScriptArgs {
     pub async fn preprocess(self) -> Result<PreprocessedState> {
         let script_wallets = Wallets::new(self.wallets.get_multi_wallet().await?, self.evm.sender);
 
-        let (config, mut evm_opts) = self.load_config_and_evm_opts()?;
+        let (mut config, mut evm_opts) = self.load_config_and_evm_opts()?;
+
+        // The debugger identifies storage slots with the storage layout.
+        if self.debug && !config.extra_output.contains(&ContractOutputSelection::StorageLayout) {
+            config.extra_output.push(ContractOutputSelection::StorageLayout);
+        }
 
         if let Some(sender) = self.maybe_load_private_key()? {
             evm_opts.sender = sender;
@@ -267,10 +302,29 @@ pub async fn preprocess(self) -> Result<PreprocessedState> {
         Ok(PreprocessedState { args: self, script_config, script_wallets })
     }
 
+    /// Executes the script locally and commits its state changes, without simulating or
+    /// broadcasting its transactions.
+    pub async fn execute_and_commit(self) -> Result<ScriptState> {
+        self.preprocess()
+            .await?
+            .compile()?
+            .link()
+            .await?
+            .prepare_execution()
+            .await?
+            .execute_and_commit()
+            .await
+    }
+
     /// Executes the script
     pub async fn run_script(self) -> Result<()> {
         trace!(target: "script", "executing script command");
 
+        // Keep stdout for the protocol messages.
+        if self.dap == Some(DapTransport::Stdio) {
+            shell::Shell::get().set_output_mode(shell::OutputMode::Quiet);
+        }
+
         let state = self.preprocess().await?;
         let create2_deployer = state.script_config.evm_opts.create2_deployer;
         let compiled = state.compile()?;
@@ -292,6 +346,9 @@ pub async fn run_script(self) -> Result<()> {
                 .await?;
 
             if pre_simulation.args.debug {
+                if let Some(transport) = pre_simulation.args.dap {
+                    return pre_simulation.run_dap(transport);
+                }
                 return match pre_simulation.args.dump.clone() {
                     Some(path) => pre_simulation.dump_debugger(&path),
                     None => pre_simulation.run_debugger(),
@@ -312,6 +369,9 @@ pub async fn run_script(self) -> Result<()> {
                 .as_ref()
                 .is_none_or(|txs| txs.is_empty())
             {
+                if let Some(path) = &pre_simulation.args.diff_against {
+                    return diff::diff_against(path, &[]);
+                }
                 if pre_simulation.args.broadcast {
                     sh_warn!("No transactions to broadcast.")?;
                 }
@@ -321,6 +381,9 @@ pub async fn run_script(self) -> Result<()> {
 
             // Check if there are any missing RPCs and exit early to avoid hard error.
             if pre_simulation.execution_artifacts.rpc_data.missing_rpc {
+                if pre_simulation.args.diff_against.is_some() {
+                    eyre::bail!("`--diff-against` requires an RPC URL to simulate transactions");
+                }
                 if !shell::is_json() {
                     sh_println!("\nIf you wish to simulate on-chain transactions pass a RPC URL.")?;
                 }
@@ -337,6 +400,10 @@ pub async fn run_script(self) -> Result<()> {
             pre_simulation.fill_metadata().await?.bundle().await?
         };
 
+        if let Some(path) = &bundled.args.diff_against {
+            return diff::diff_against(path, bundled.sequence.sequences());
+        }
+
         // Exit early in case user didn't provide any broadcast/verify related flags.
         if !bundled.args.should_broadcast() {
             if !shell::is_json() {
@@ -590,6 +657,16 @@ pub fn get_created_contracts(
     }
 }
 
+/// The state after a local execution of a script.
+pub struct ScriptState {
+    /// The result of the script execution.
+    pub result: ScriptResult,
+    /// The backend holding the state changes of the script.
+    pub backend: Backend,
+    /// The contracts compiled with the script.
+    pub known_contracts: ContractsByArtifact,
+}
+
 #[derive(Serialize)]
 struct JsonResult<'a> {
     logs: Vec<String>,
@@ -746,6 +823,29 @@ fn can_parse_unlocked() {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn can_parse_diff_against() {
+        let args = ScriptArgs::parse_from([
+            "foundry-cli",
+            "Contract.sol",
+            "--diff-against",
+            "broadcast/Contract.sol/1/run-latest.json",
+        ]);
+        assert_eq!(
+            args.diff_against,
+            Some(PathBuf::from("broadcast/Contract.sol/1/run-latest.json"))
+        );
+
+        let args = ScriptArgs::try_parse_from([
+            "foundry-cli",
+            "Contract.sol",
+            "--diff-against",
+            "run-latest.json",
+            "--broadcast",
+        ]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn can_merge_script_config() {
         let args = ScriptArgs::parse_from([
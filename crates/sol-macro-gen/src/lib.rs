@@ -1,6 +1,9 @@
-//! This crate contains the logic for Rust bindings generating from Solidity contracts
+//! This crate contains the logic for Rust and TypeScript bindings generating from Solidity
+//! contracts
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 pub mod sol_macro_gen;
+pub mod typescript;
 
 pub use sol_macro_gen::*;
+pub use typescript::*;
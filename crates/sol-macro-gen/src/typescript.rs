@@ -0,0 +1,358 @@
+//! TsBindingGen and MultiTsBindingGen
+//!
+//! These types generate TypeScript bindings from contract artifacts: the ABI as an `as const`
+//! array, the deploy bytecode, event and custom error types mirroring the shapes used by `viem`,
+//! and helpers to identify the custom error encoded in revert data.
+
+use alloy_json_abi::{EventParam, JsonAbi, Param};
+use eyre::{Context, OptionExt, Result};
+use foundry_common::fs;
+use heck::ToLowerCamelCase;
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+const HEADER: &str = "// This file is autogenerated by `forge bind`.
+// Do not manually edit this file, it may be overwritten by the codegen system at any time.
+";
+
+pub struct TsBindingGen {
+    pub path: PathBuf,
+    pub name: String,
+    pub expansion: Option<String>,
+}
+
+impl TsBindingGen {
+    pub fn new(path: PathBuf, name: String) -> Self {
+        Self { path, name, expansion: None }
+    }
+
+    /// Reads the ABI and the deploy bytecode from the artifact.
+    fn read_artifact(&self) -> Result<(JsonAbi, Option<String>)> {
+        let artifact: serde_json::Value = fs::read_json_file(&self.path)?;
+        let abi = serde_json::from_value(artifact.get("abi").cloned().ok_or_eyre("missing ABI")?)
+            .wrap_err("failed to parse ABI")?;
+        let bytecode =
+            artifact
+                .get("bytecode")
+                .and_then(|bytecode| bytecode.get("object").or(Some(bytecode)))
+                .and_then(|object| object.as_str())
+                .filter(|object| !object.is_empty() && *object != "0x")
+                .map(|object| {
+                    if object.starts_with("0x") {
+                        object.to_string()
+                    } else {
+                        format!("0x{object}")
+                    }
+                });
+        Ok((abi, bytecode))
+    }
+}
+
+pub struct MultiTsBindingGen {
+    pub instances: Vec<TsBindingGen>,
+}
+
+impl MultiTsBindingGen {
+    pub fn new(instances: Vec<TsBindingGen>) -> Self {
+        Self { instances }
+    }
+
+    pub fn generate_bindings(&mut self) -> Result<()> {
+        for instance in &mut self.instances {
+            let (abi, bytecode) = instance.read_artifact().wrap_err_with(|| {
+                format!(
+                    "failed to generate bindings for {}:{}",
+                    instance.path.display(),
+                    instance.name
+                )
+            })?;
+            instance.expansion = Some(expand(&instance.name, &abi, bytecode.as_deref())?);
+        }
+        Ok(())
+    }
+
+    /// Writes one `<Contract>.ts` file per contract and an `index.ts` re-exporting them, or
+    /// everything to `index.ts` if `single_file` is set.
+    pub fn write_to_dir(&mut self, bindings_path: &Path, single_file: bool) -> Result<()> {
+        self.generate_bindings()?;
+
+        fs::create_dir_all(bindings_path)?;
+        for (path, contents) in self.files(bindings_path, single_file)? {
+            fs::write(&path, contents)
+                .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the existing bindings match the generated ones.
+    ///
+    /// Returns `Ok(())` if the bindings are up to date, otherwise it returns `Err(_)`.
+    pub fn check_consistency(&self, bindings_path: &Path, single_file: bool) -> Result<()> {
+        for (path, expected) in self.files(bindings_path, single_file)? {
+            eyre::ensure!(path.is_file(), "{} is not a file", path.display());
+            let contents = fs::read_to_string(&path).wrap_err("Failed to read file")?;
+            eyre::ensure!(
+                contents == expected,
+                "File contents do not match expected contents for {path:?}"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the paths and contents of the files making up the bindings.
+    fn files(&self, bindings_path: &Path, single_file: bool) -> Result<Vec<(PathBuf, String)>> {
+        let mut files = Vec::new();
+        let mut index = HEADER.to_string();
+        for instance in &self.instances {
+            let expansion = instance
+                .expansion
+                .as_ref()
+                .ok_or_eyre(format!("bindings for {} do not exist", instance.name))?;
+            if single_file {
+                write!(index, "\n{}", expansion.strip_prefix(HEADER).unwrap_or(expansion))?;
+            } else {
+                writeln!(index, "export * from \"./{}.js\";", instance.name)?;
+                files
+                    .push((bindings_path.join(format!("{}.ts", instance.name)), expansion.clone()));
+            }
+        }
+        files.push((bindings_path.join("index.ts"), index));
+        Ok(files)
+    }
+}
+
+/// Generates the TypeScript bindings of a single contract.
+fn expand(name: &str, abi: &JsonAbi, bytecode: Option<&str>) -> Result<String> {
+    let ident = name.to_lower_camel_case();
+    let mut out = HEADER.to_string();
+
+    writeln!(out, "\nexport const {ident}Abi = {} as const;", serde_json::to_string_pretty(abi)?)?;
+
+    if let Some(bytecode) = bytecode {
+        writeln!(out, "\nexport const {ident}Bytecode = \"{bytecode}\" as const;")?;
+    }
+
+    let events = abi
+        .events
+        .iter()
+        .flat_map(|(event_name, overloads)| {
+            overloads
+                .iter()
+                .enumerate()
+                .map(move |(i, event)| (overloaded_name(event_name, i, overloads.len()), event))
+        })
+        .collect::<Vec<_>>();
+    if !events.is_empty() {
+        writeln!(out, "\n/** Event topics, by event name. */")?;
+        writeln!(out, "export const {ident}EventTopics = {{")?;
+        for (event_name, event) in &events {
+            writeln!(out, "  {event_name}: \"{}\",", event.selector())?;
+        }
+        writeln!(out, "}} as const;")?;
+
+        for (event_name, event) in &events {
+            writeln!(
+                out,
+                "\nexport type {name}{event_name}Event = {};",
+                event_params_type(&event.inputs)
+            )?;
+        }
+        writeln!(out, "\nexport type {name}Event =")?;
+        for (event_name, event) in &events {
+            writeln!(
+                out,
+                "  | {{ eventName: \"{}\"; args: {name}{event_name}Event }}",
+                event.name
+            )?;
+        }
+        out.pop();
+        writeln!(out, ";")?;
+    }
+
+    let errors = abi
+        .errors
+        .iter()
+        .flat_map(|(error_name, overloads)| {
+            overloads
+                .iter()
+                .enumerate()
+                .map(move |(i, error)| (overloaded_name(error_name, i, overloads.len()), error))
+        })
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        writeln!(out, "\n/** Custom error names, by selector. */")?;
+        writeln!(out, "export const {ident}ErrorSelectors = {{")?;
+        for (_, error) in &errors {
+            writeln!(out, "  \"{}\": \"{}\",", error.selector(), error.name)?;
+        }
+        writeln!(out, "}} as const;")?;
+
+        for (error_name, error) in &errors {
+            writeln!(out, "\nexport type {name}{error_name}Error = {};", args_type(&error.inputs))?;
+        }
+        writeln!(out, "\nexport type {name}Error =")?;
+        for (error_name, error) in &errors {
+            writeln!(
+                out,
+                "  | {{ errorName: \"{}\"; args: {name}{error_name}Error }}",
+                error.name
+            )?;
+        }
+        out.pop();
+        writeln!(out, ";")?;
+
+        writeln!(
+            out,
+            r#"
+export type {name}ErrorName = (typeof {ident}ErrorSelectors)[keyof typeof {ident}ErrorSelectors];
+
+/** Returns the name of the `{name}` custom error encoded in the given revert data, if any. */
+export function decode{name}ErrorName(data: `0x${{string}}`): {name}ErrorName | undefined {{
+  const selector = data.slice(0, 10).toLowerCase();
+  return selector in {ident}ErrorSelectors
+    ? {ident}ErrorSelectors[selector as keyof typeof {ident}ErrorSelectors]
+    : undefined;
+}}"#
+        )?;
+    }
+
+    Ok(out)
+}
+
+/// Overloaded events and errors are suffixed with their index, like in the Rust bindings.
+///
+/// The suffix is only used in the TypeScript identifiers: the `eventName` and `errorName`
+/// discriminants hold the Solidity name, as decoded by `viem`.
+fn overloaded_name(name: &str, index: usize, overloads: usize) -> String {
+    if overloads > 1 { format!("{name}_{index}") } else { name.to_string() }
+}
+
+fn event_params_type(params: &[EventParam]) -> String {
+    fields_type(params.iter().map(|param| (param.name.as_str(), &param.ty, &param.components[..])))
+}
+
+fn params_type(params: &[Param]) -> String {
+    fields_type(params.iter().map(|param| (param.name.as_str(), &param.ty, &param.components[..])))
+}
+
+/// Returns the tuple type of the arguments of a custom error, as decoded by `viem`.
+fn args_type(params: &[Param]) -> String {
+    let fields =
+        params.iter().map(|param| ts_type(&param.ty, &param.components)).collect::<Vec<_>>();
+    format!("readonly [{}]", fields.join(", "))
+}
+
+/// Returns an object type if all the fields are named, and a tuple type otherwise.
+fn fields_type<'a>(fields: impl Iterator<Item = (&'a str, &'a String, &'a [Param])>) -> String {
+    let fields = fields.collect::<Vec<_>>();
+    if fields.is_empty() {
+        return "readonly []".to_string();
+    }
+    if fields.iter().all(|(name, ..)| !name.is_empty()) {
+        let fields = fields
+            .iter()
+            .map(|(name, ty, components)| format!("{name}: {}", ts_type(ty, components)))
+            .collect::<Vec<_>>();
+        format!("{{ {} }}", fields.join("; "))
+    } else {
+        let fields =
+            fields.iter().map(|(_, ty, components)| ts_type(ty, components)).collect::<Vec<_>>();
+        format!("readonly [{}]", fields.join(", "))
+    }
+}
+
+/// Returns the TypeScript type of a Solidity type.
+///
+/// Integers of up to 48 bits are numbers, larger integers are bigints.
+fn ts_type(ty: &str, components: &[Param]) -> String {
+    if let Some((inner, _)) = ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('[')) {
+        let inner = ts_type(inner, components);
+        return if inner.starts_with("readonly") {
+            format!("readonly ({inner})[]")
+        } else {
+            format!("readonly {inner}[]")
+        };
+    }
+
+    match ty {
+        "bool" => "boolean".to_string(),
+        "string" => "string".to_string(),
+        "tuple" => params_type(components),
+        "address" | "function" => "`0x${string}`".to_string(),
+        _ if ty.starts_with("bytes") => "`0x${string}`".to_string(),
+        _ => match ty.strip_prefix("uint").or_else(|| ty.strip_prefix("int")) {
+            Some(bits) if bits.parse::<u16>().is_ok_and(|bits| bits <= 48) => "number".to_string(),
+            Some(_) => "bigint".to_string(),
+            None => "unknown".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_types() {
+        let point = vec![Param::parse("uint256 x").unwrap(), Param::parse("uint8 y").unwrap()];
+        assert_eq!(ts_type("uint256", &[]), "bigint");
+        assert_eq!(ts_type("int48", &[]), "number");
+        assert_eq!(ts_type("bytes32[]", &[]), "readonly `0x${string}`[]");
+        assert_eq!(ts_type("uint256[2][]", &[]), "readonly (readonly bigint[])[]");
+        assert_eq!(ts_type("tuple", &point), "{ x: bigint; y: number }");
+        assert_eq!(ts_type("tuple[]", &point), "readonly { x: bigint; y: number }[]");
+    }
+
+    #[test]
+    fn expands_contract() {
+        let abi = JsonAbi::parse([
+            "event Incremented(uint256 indexed number)",
+            "error Unauthorized(address caller)",
+            "function increment()",
+        ])
+        .unwrap();
+        let ts = expand("Counter", &abi, Some("0x6080")).unwrap();
+
+        assert!(ts.contains("export const counterAbi = [\n"), "{ts}");
+        assert!(ts.contains("] as const;\n"), "{ts}");
+        assert!(ts.contains("export const counterBytecode = \"0x6080\" as const;"), "{ts}");
+        assert!(ts.contains("export type CounterIncrementedEvent = { number: bigint };"), "{ts}");
+        assert!(
+            ts.contains(
+                "export type CounterEvent =\n  | { eventName: \"Incremented\"; args: CounterIncrementedEvent };"
+            ),
+            "{ts}"
+        );
+        assert!(ts.contains("  \"0x8e4a23d6\": \"Unauthorized\",\n"), "{ts}");
+        assert!(
+            ts.contains("export type CounterUnauthorizedError = readonly [`0x${string}`];"),
+            "{ts}"
+        );
+        assert!(ts.contains("export function decodeCounterErrorName("), "{ts}");
+    }
+
+    #[test]
+    fn expands_overloads() {
+        let abi = JsonAbi::parse([
+            "event Transfer(address to)",
+            "event Transfer(address to, uint256 amount)",
+            "error Failed()",
+            "error Failed(uint8 code)",
+        ])
+        .unwrap();
+        let ts = expand("Token", &abi, None).unwrap();
+
+        assert!(ts.contains("export type TokenTransfer_0Event = { to: `0x${string}` };"), "{ts}");
+        assert!(
+            ts.contains("  | { eventName: \"Transfer\"; args: TokenTransfer_1Event };"),
+            "{ts}"
+        );
+        assert!(!ts.contains("eventName: \"Transfer_"), "{ts}");
+        assert!(ts.contains("export type TokenFailed_1Error = readonly [number];"), "{ts}");
+        assert!(ts.contains("  | { errorName: \"Failed\"; args: TokenFailed_0Error }"), "{ts}");
+        assert_eq!(ts.matches("\": \"Failed\",\n").count(), 2, "{ts}");
+        assert!(!ts.contains("\"Failed_"), "{ts}");
+    }
+}